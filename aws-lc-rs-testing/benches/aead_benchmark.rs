@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use aws_lc_rs::{test, test_file};
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 
 #[derive(Debug)]
 pub enum AeadAlgorithm {
@@ -137,6 +137,7 @@ fn test_aes_128_gcm(c: &mut Criterion) {
             test_aead_separate(c, &config);
             test_aead_append(c, &config);
             test_aead_open(c, &config);
+            test_aead_batch(c, &config);
             Ok(())
         },
     );
@@ -156,6 +157,7 @@ fn test_aes_256_gcm(c: &mut Criterion) {
             test_aead_separate(c, &config);
             test_aead_append(c, &config);
             test_aead_open(c, &config);
+            test_aead_batch(c, &config);
             Ok(())
         },
     );
@@ -175,6 +177,7 @@ fn test_chacha20(c: &mut Criterion) {
             test_aead_separate(c, &config);
             test_aead_append(c, &config);
             test_aead_open(c, &config);
+            test_aead_batch(c, &config);
             Ok(())
         },
     );
@@ -272,5 +275,64 @@ fn test_aead_open(c: &mut Criterion, config: &AeadConfig) {
     }
 }
 
+const BATCH_SIZE: usize = 64;
+
+fn batch_nonce(config: &AeadConfig, index: usize) -> aws_lc_rs::aead::Nonce {
+    let mut nonce = [0u8; aws_lc_rs::aead::NONCE_LEN];
+    nonce.copy_from_slice(&config.nonce[0..aws_lc_rs::aead::NONCE_LEN]);
+    nonce[aws_lc_rs::aead::NONCE_LEN - 8..].copy_from_slice(&(index as u64).to_be_bytes());
+    aws_lc_rs::aead::Nonce::assume_unique_for_key(nonce)
+}
+
+fn test_aead_batch(c: &mut Criterion, config: &AeadConfig) {
+    use aws_lc_rs::aead::{Aad, LessSafeKey, UnboundKey};
+
+    let algorithm = match config.algorithm {
+        AeadAlgorithm::Aes128Gcm => &aws_lc_rs::aead::AES_128_GCM,
+        AeadAlgorithm::Aes256Gcm => &aws_lc_rs::aead::AES_256_GCM,
+        AeadAlgorithm::Chacha20Poly1305 => &aws_lc_rs::aead::CHACHA20_POLY1305,
+    };
+    let key = LessSafeKey::new(UnboundKey::new(algorithm, &config.key).unwrap());
+    let mut in_outs = vec![config.in_out.clone(); BATCH_SIZE];
+
+    let bench_group_name = format!(
+        "AEAD-{:?}-batch-{}x{}-bytes",
+        config.algorithm,
+        BATCH_SIZE,
+        config.in_out.len()
+    );
+    let mut group = c.benchmark_group(bench_group_name);
+    group.throughput(Throughput::Bytes((BATCH_SIZE * config.in_out.len()) as u64));
+
+    group.bench_function("AWS-LC-individual", |b| {
+        b.iter(|| {
+            for (i, in_out) in in_outs.iter_mut().enumerate() {
+                let _tag = key
+                    .seal_in_place_separate_tag(
+                        batch_nonce(config, i),
+                        Aad::from(config.aad.as_bytes()),
+                        black_box(in_out),
+                    )
+                    .unwrap();
+            }
+        });
+    });
+
+    group.bench_function("AWS-LC-batch", |b| {
+        b.iter(|| {
+            let tags = key.seal_in_place_separate_tag_batch(in_outs.iter_mut().enumerate().map(
+                |(i, in_out)| {
+                    (
+                        batch_nonce(config, i),
+                        Aad::from(config.aad.as_bytes()),
+                        black_box(in_out.as_mut_slice()),
+                    )
+                },
+            ));
+            black_box(tags);
+        });
+    });
+}
+
 criterion_group!(benches, test_aes_128_gcm, test_aes_256_gcm, test_chacha20,);
 criterion_main!(benches);
@@ -52,6 +52,21 @@
 //! Systems developers creating TLS protocol implementations should use
 //! [`TlsRecordSealingKey`] and [`TlsRecordOpeningKey`] respectively for AEAD.
 //!
+//! # Streaming AEAD APIs
+//!
+//! [`StreamSealingKey`] and [`StreamOpeningKey`] seal and open a stream of data in fixed-size
+//! segments, detecting segments that have been reordered, dropped, or truncated.
+//!
+//! # Scatter/Gather AEAD APIs
+//!
+//! [`ScatterGatherKey`] seals and opens messages whose input and output are split across several
+//! `IoSlice` and `IoSliceMut` buffers, without first copying them into a contiguous buffer.
+//!
+//! # Deterministic AEAD APIs
+//!
+//! [`siv`] provides AES-SIV (RFC 5297), which accepts a vector of associated data components
+//! and does not require a nonce.
+//!
 //! # Nonce Sequence APIs
 //!
 //! The [`UnboundKey`], [`OpeningKey`], [`SealingKey`], and [`LessSafeKey`] types are the
@@ -115,10 +130,11 @@
 //! ```
 
 use crate::{derive_debug_via_id, error::Unspecified, hkdf};
-use aead_ctx::AeadCtx;
 use core::{fmt::Debug, ops::RangeFrom};
+use unbound_key::KeyCtx;
 
 mod aead_ctx;
+mod aes_ccm;
 mod aes_gcm;
 mod chacha;
 pub mod chacha20_poly1305_openssh;
@@ -127,14 +143,23 @@ pub mod nonce_sequence;
 mod poly1305;
 pub mod quic;
 mod rand_nonce;
+mod scatter_gather;
+pub mod siv;
+mod stream;
 mod tls;
 mod unbound_key;
 
 pub use self::{
-    aes_gcm::{AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV},
-    chacha::CHACHA20_POLY1305,
-    nonce::{Nonce, NONCE_LEN},
+    aes_ccm::{aes_128_ccm, AES_128_CCM_BLUETOOTH, AES_128_CCM_BLUETOOTH_8, AES_128_CCM_MATTER},
+    aes_gcm::{AES_128_GCM, AES_128_GCM_SIV, AES_192_GCM, AES_256_GCM, AES_256_GCM_SIV},
+    chacha::{CHACHA20_POLY1305, XCHACHA20_POLY1305},
+    nonce::{ExtendedNonce, Nonce, EXTENDED_NONCE_LEN, NONCE_LEN},
     rand_nonce::RandomizedNonceKey,
+    scatter_gather::ScatterGatherKey,
+    stream::{
+        StreamOpeningKey, StreamSealingKey, STREAM_HEADER_LEN, STREAM_NONCE_PREFIX_LEN,
+        STREAM_SALT_LEN,
+    },
     tls::{TlsProtocolId, TlsRecordOpeningKey, TlsRecordSealingKey},
     unbound_key::UnboundKey,
 };
@@ -158,6 +183,18 @@ pub trait NonceSequence {
     /// sent/received under a key this way. Once `advance()` fails, it must
     /// fail for all subsequent calls.
     fn advance(&mut self) -> Result<Nonce, Unspecified>;
+
+    /// Returns the next nonce in the sequence for keys whose algorithm doesn't
+    /// use `NONCE_LEN` byte nonces, such as `XCHACHA20_POLY1305`.
+    ///
+    /// `OpeningKey` and `SealingKey` always obtain their nonces from this
+    /// method. The default implementation returns the result of `advance()`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` under the same conditions as `advance()`.
+    fn advance_extended(&mut self) -> Result<ExtendedNonce, Unspecified> {
+        self.advance().map(ExtendedNonce::from)
+    }
 }
 
 /// An AEAD key bound to a nonce sequence.
@@ -233,8 +270,12 @@ impl<N: NonceSequence> OpeningKey<N> {
     where
         A: AsRef<[u8]>,
     {
-        self.key
-            .open_within(self.nonce_sequence.advance()?, aad.as_ref(), in_out, 0..)
+        self.key.open_within(
+            self.nonce_sequence.advance_extended()?,
+            aad.as_ref(),
+            in_out,
+            0..,
+        )
     }
 
     /// Authenticates and decrypts (“opens”) data in place, with a shift.
@@ -301,7 +342,7 @@ impl<N: NonceSequence> OpeningKey<N> {
         A: AsRef<[u8]>,
     {
         self.key.open_within(
-            self.nonce_sequence.advance()?,
+            self.nonce_sequence.advance_extended()?,
             aad.as_ref(),
             in_out,
             ciphertext_and_tag,
@@ -396,7 +437,11 @@ impl<N: NonceSequence> SealingKey<N> {
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
         self.key
-            .seal_in_place_append_tag(Some(self.nonce_sequence.advance()?), aad.as_ref(), in_out)
+            .seal_in_place_append_tag(
+                Some(self.nonce_sequence.advance_extended()?),
+                aad.as_ref(),
+                in_out,
+            )
             .map(|_| ())
     }
 
@@ -430,7 +475,11 @@ impl<N: NonceSequence> SealingKey<N> {
         A: AsRef<[u8]>,
     {
         self.key
-            .seal_in_place_separate_tag(Some(self.nonce_sequence.advance()?), aad.as_ref(), in_out)
+            .seal_in_place_separate_tag(
+                Some(self.nonce_sequence.advance_extended()?),
+                aad.as_ref(),
+                in_out,
+            )
             .map(|(_, tag)| tag)
     }
 }
@@ -510,18 +559,49 @@ impl LessSafeKey {
     /// # Errors
     /// `error::Unspecified` when ciphertext is invalid.
     #[inline]
-    pub fn open_in_place<'in_out, A>(
+    pub fn open_in_place<'in_out, N, A>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_out: &'in_out mut [u8],
     ) -> Result<&'in_out mut [u8], Unspecified>
     where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
     {
         self.open_within(nonce, aad, in_out, 0..)
     }
 
+    /// Authenticates and decrypts (“opens”) a batch of independent messages in place.
+    ///
+    /// Each item is the `nonce`, `aad` and `in_out` of a single [`Self::open_in_place`]
+    /// operation, and the messages are opened in order. A message that fails to open does not
+    /// affect the others.
+    ///
+    /// Returns the plaintext, or an error, for each item, in order.
+    ///
+    // # FIPS
+    // Use this method with one of the following algorithms:
+    // * `AES_128_GCM`
+    // * `AES_256_GCM`
+    //
+    #[inline]
+    pub fn open_in_place_batch<'in_out, N, A, I>(
+        &self,
+        items: I,
+    ) -> Vec<Result<&'in_out mut [u8], Unspecified>>
+    where
+        N: Into<ExtendedNonce>,
+        A: AsRef<[u8]>,
+        I: IntoIterator<Item = (N, Aad<A>, &'in_out mut [u8])>,
+    {
+        self.key.open_in_place_batch(
+            items
+                .into_iter()
+                .map(|(nonce, aad, in_out)| (nonce.into(), aad, in_out)),
+        )
+    }
+
     /// Like [`OpeningKey::open_within()`], except it accepts an arbitrary nonce.
     ///
     /// `nonce` must be unique for every use of the key to open data.
@@ -537,18 +617,19 @@ impl LessSafeKey {
     /// `error::Unspecified` when ciphertext is invalid.
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
-    pub fn open_within<'in_out, A>(
+    pub fn open_within<'in_out, N, A>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_out: &'in_out mut [u8],
         ciphertext_and_tag: RangeFrom<usize>,
     ) -> Result<&'in_out mut [u8], Unspecified>
     where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
     {
         self.key
-            .open_within(nonce, aad.as_ref(), in_out, ciphertext_and_tag)
+            .open_within(nonce.into(), aad.as_ref(), in_out, ciphertext_and_tag)
     }
 
     /// Authenticates and decrypts (“opens”) data into another provided slice.
@@ -566,19 +647,25 @@ impl LessSafeKey {
     ///
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
-    pub fn open_separate_gather<A>(
+    pub fn open_separate_gather<N, A>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_ciphertext: &[u8],
         in_tag: &[u8],
         out_plaintext: &mut [u8],
     ) -> Result<(), Unspecified>
     where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
     {
-        self.key
-            .open_separate_gather(&nonce, aad.as_ref(), in_ciphertext, in_tag, out_plaintext)
+        self.key.open_separate_gather(
+            &nonce.into(),
+            aad.as_ref(),
+            in_ciphertext,
+            in_tag,
+            out_plaintext,
+        )
     }
 
     /// Deprecated. Renamed to `seal_in_place_append_tag()`.
@@ -591,13 +678,14 @@ impl LessSafeKey {
     #[deprecated(note = "Renamed to `seal_in_place_append_tag`.")]
     #[inline]
     #[allow(clippy::missing_errors_doc)]
-    pub fn seal_in_place<A, InOut>(
+    pub fn seal_in_place<N, A, InOut>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_out: &mut InOut,
     ) -> Result<(), Unspecified>
     where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
@@ -618,18 +706,19 @@ impl LessSafeKey {
     /// `error::Unspecified` if encryption operation fails.
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
-    pub fn seal_in_place_append_tag<A, InOut>(
+    pub fn seal_in_place_append_tag<N, A, InOut>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_out: &mut InOut,
     ) -> Result<(), Unspecified>
     where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
         self.key
-            .seal_in_place_append_tag(Some(nonce), aad.as_ref(), in_out)
+            .seal_in_place_append_tag(Some(nonce.into()), aad.as_ref(), in_out)
             .map(|_| ())
     }
 
@@ -647,20 +736,59 @@ impl LessSafeKey {
     /// `error::Unspecified` if encryption operation fails.
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
-    pub fn seal_in_place_separate_tag<A>(
+    pub fn seal_in_place_separate_tag<N, A>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_out: &mut [u8],
     ) -> Result<Tag, Unspecified>
     where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
     {
         self.key
-            .seal_in_place_separate_tag(Some(nonce), aad.as_ref(), in_out)
+            .seal_in_place_separate_tag(Some(nonce.into()), aad.as_ref(), in_out)
             .map(|(_, tag)| tag)
     }
 
+    /// Encrypts and signs (“seals”) a batch of independent messages in place.
+    ///
+    /// Each item is the `nonce`, `aad` and `in_out` of a single
+    /// [`Self::seal_in_place_separate_tag`] operation, and the messages are sealed in order. A
+    /// message that fails to seal does not affect the others.
+    ///
+    /// The key schedule is computed once, when the key is constructed, and is reused by every
+    /// sealing operation. A batch additionally looks up the key's `EVP_AEAD_CTX` once rather
+    /// than once per message, but each message is still sealed by its own call into AWS-LC.
+    ///
+    /// Each `nonce` must be unique for every use of the key to seal data.
+    ///
+    /// Returns the tag, or an error, for each item, in order.
+    ///
+    // # FIPS
+    // This method must not be used.
+    //
+    #[inline]
+    pub fn seal_in_place_separate_tag_batch<'in_out, N, A, I>(
+        &self,
+        items: I,
+    ) -> Vec<Result<Tag, Unspecified>>
+    where
+        N: Into<ExtendedNonce>,
+        A: AsRef<[u8]>,
+        I: IntoIterator<Item = (N, Aad<A>, &'in_out mut [u8])>,
+    {
+        self.key
+            .seal_in_place_separate_tag_batch(
+                items
+                    .into_iter()
+                    .map(|(nonce, aad, in_out)| (Some(nonce.into()), aad, in_out)),
+            )
+            .into_iter()
+            .map(|result| result.map(|(_, tag)| tag))
+            .collect()
+    }
+
     /// Encrypts and signs (“seals”) data in place with extra plaintext.
     ///
     /// `aad` is the additional authenticated data (AAD), if any. This is
@@ -682,19 +810,20 @@ impl LessSafeKey {
     /// `error::Unspecified` if encryption operation fails.
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
-    pub fn seal_in_place_scatter<A>(
+    pub fn seal_in_place_scatter<N, A>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_out: &mut [u8],
         extra_in: &[u8],
         extra_out_and_tag: &mut [u8],
     ) -> Result<(), Unspecified>
     where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
     {
         self.key.seal_in_place_separate_scatter(
-            nonce,
+            nonce.into(),
             aad.as_ref(),
             in_out,
             extra_in,
@@ -720,8 +849,10 @@ impl Debug for LessSafeKey {
 
 /// An AEAD Algorithm.
 pub struct Algorithm {
-    init: fn(key: &[u8], tag_len: usize) -> Result<AeadCtx, Unspecified>,
+    init: fn(key: &[u8], tag_len: usize) -> Result<KeyCtx, Unspecified>,
     key_len: usize,
+    tag_len: usize,
+    nonce_len: usize,
     id: AlgorithmID,
 
     // /// Use `max_input_len!()` to initialize this.
@@ -743,14 +874,14 @@ impl Algorithm {
     #[inline]
     #[must_use]
     pub fn tag_len(&self) -> usize {
-        TAG_LEN
+        self.tag_len
     }
 
     /// The length of the nonces.
     #[inline]
     #[must_use]
     pub fn nonce_len(&self) -> usize {
-        NONCE_LEN
+        self.nonce_len
     }
 }
 
@@ -760,10 +891,13 @@ derive_debug_via_id!(Algorithm);
 #[allow(non_camel_case_types)]
 enum AlgorithmID {
     AES_128_GCM,
+    AES_192_GCM,
     AES_256_GCM,
     AES_128_GCM_SIV,
     AES_256_GCM_SIV,
     CHACHA20_POLY1305,
+    XCHACHA20_POLY1305,
+    AES_128_CCM { tag_len: usize, nonce_len: usize },
 }
 
 impl PartialEq for Algorithm {
@@ -789,7 +923,7 @@ impl AsRef<[u8]> for Tag {
 #[allow(dead_code)]
 const MAX_KEY_LEN: usize = 32;
 
-// All the AEADs we support use 128-bit tags.
+// The AES-GCM and ChaCha20-Poly1305 AEADs use 128-bit tags. AES-CCM supports shorter tags.
 const TAG_LEN: usize = 16;
 
 /// The maximum length of a tag for the algorithms in this module.
@@ -798,7 +932,7 @@ pub const MAX_TAG_LEN: usize = TAG_LEN;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{iv::FixedLength, test::from_hex};
+    use crate::test::from_hex;
 
     #[cfg(feature = "fips")]
     mod fips;
@@ -822,14 +956,18 @@ mod tests {
         #[allow(deprecated)]
         less_safe_key
             // Test coverage for `seal_in_place`, which calls `seal_in_place_append_tag`.
-            .seal_in_place(Nonce(FixedLength::from(nonce)), Aad::empty(), &mut in_out)
+            .seal_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
             .unwrap();
 
         let mut in_out_clone = in_out.clone();
         let nonce: [u8; NONCE_LEN] = og_nonce.as_slice().try_into().unwrap();
         assert!(less_safe_key
             .open_in_place(
-                Nonce(FixedLength::from(nonce)),
+                Nonce::assume_unique_for_key(nonce),
                 Aad::from("test"),
                 &mut in_out_clone
             )
@@ -840,7 +978,7 @@ mod tests {
         nonce[0] = 0;
         assert!(less_safe_key
             .open_in_place(
-                Nonce(FixedLength::from(nonce)),
+                Nonce::assume_unique_for_key(nonce),
                 Aad::empty(),
                 &mut in_out_clone
             )
@@ -848,7 +986,11 @@ mod tests {
 
         let nonce: [u8; NONCE_LEN] = og_nonce.as_slice().try_into().unwrap();
         less_safe_key
-            .open_in_place(Nonce(FixedLength::from(nonce)), Aad::empty(), &mut in_out)
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::empty(),
+                &mut in_out,
+            )
             .unwrap();
 
         assert_eq!(plaintext, in_out[..plaintext.len()]);
@@ -0,0 +1,453 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::aead::aead_ctx::AeadCtx;
+use crate::aead::unbound_key::KeyCtx;
+use crate::aead::{Algorithm, AlgorithmID};
+use crate::cipher::aes::AES_128_KEY_LEN;
+use crate::error::Unspecified;
+
+#[cfg(not(feature = "fips"))]
+pub(crate) use self::cipher::AesCcmKey;
+
+/// The nonce length used by the Bluetooth and Matter profiles of AES-CCM.
+const PROFILE_NONCE_LEN: usize = 13;
+
+/// AES-128 in CCM mode with 32-bit tags and 104 bit nonces, as used by Bluetooth LE.
+///
+/// Messages are limited to 2^16 - 1 bytes.
+pub const AES_128_CCM_BLUETOOTH: Algorithm = Algorithm {
+    init: init_aes_128_ccm_bluetooth,
+    key_len: AES_128_KEY_LEN,
+    tag_len: 4,
+    nonce_len: PROFILE_NONCE_LEN,
+    id: AlgorithmID::AES_128_CCM {
+        tag_len: 4,
+        nonce_len: PROFILE_NONCE_LEN,
+    },
+    max_input_len: max_input_len(PROFILE_NONCE_LEN),
+};
+
+/// AES-128 in CCM mode with 64-bit tags and 104 bit nonces, as used by Bluetooth Mesh.
+///
+/// Messages are limited to 2^16 - 1 bytes.
+pub const AES_128_CCM_BLUETOOTH_8: Algorithm = Algorithm {
+    init: init_aes_128_ccm_bluetooth_8,
+    key_len: AES_128_KEY_LEN,
+    tag_len: 8,
+    nonce_len: PROFILE_NONCE_LEN,
+    id: AlgorithmID::AES_128_CCM {
+        tag_len: 8,
+        nonce_len: PROFILE_NONCE_LEN,
+    },
+    max_input_len: max_input_len(PROFILE_NONCE_LEN),
+};
+
+/// AES-128 in CCM mode with 128-bit tags and 104 bit nonces, as used by Matter.
+///
+/// Messages are limited to 2^16 - 1 bytes.
+pub const AES_128_CCM_MATTER: Algorithm = Algorithm {
+    init: init_aes_128_ccm_matter,
+    key_len: AES_128_KEY_LEN,
+    tag_len: 16,
+    nonce_len: PROFILE_NONCE_LEN,
+    id: AlgorithmID::AES_128_CCM {
+        tag_len: 16,
+        nonce_len: PROFILE_NONCE_LEN,
+    },
+    max_input_len: max_input_len(PROFILE_NONCE_LEN),
+};
+
+/// Returns the AES-128-CCM algorithm with the given tag and nonce lengths.
+///
+/// `tag_len` must be 4, 8 or 16 bytes. `nonce_len` must be between 7 and 13
+/// bytes. A shorter nonce leaves more room for the message length in the
+/// counter block: messages are limited to `2^(8 * (15 - nonce_len)) - 1`
+/// bytes.
+///
+/// # Errors
+/// `error::Unspecified` if the combination of lengths is not supported.
+///
+/// # FIPS
+/// Only the 13 byte nonce length is supported when the `fips` feature is
+/// enabled.
+pub fn aes_128_ccm(tag_len: usize, nonce_len: usize) -> Result<&'static Algorithm, Unspecified> {
+    match (tag_len, nonce_len) {
+        (4, PROFILE_NONCE_LEN) => Ok(&AES_128_CCM_BLUETOOTH),
+        (8, PROFILE_NONCE_LEN) => Ok(&AES_128_CCM_BLUETOOTH_8),
+        (16, PROFILE_NONCE_LEN) => Ok(&AES_128_CCM_MATTER),
+        #[cfg(not(feature = "fips"))]
+        _ => cipher::lookup(tag_len, nonce_len),
+        #[cfg(feature = "fips")]
+        _ => Err(Unspecified),
+    }
+}
+
+// The message length is encoded in the `15 - nonce_len` bytes of the first
+// block that are not taken by the nonce.
+const fn max_input_len(nonce_len: usize) -> u64 {
+    let length_field_len = 15 - nonce_len;
+    if length_field_len >= 8 {
+        u64::MAX
+    } else {
+        (1 << (8 * length_field_len)) - 1
+    }
+}
+
+#[inline]
+fn init_aes_128_ccm_bluetooth(key: &[u8], tag_len: usize) -> Result<KeyCtx, Unspecified> {
+    AeadCtx::aes_128_ccm_bluetooth(key, tag_len).map(KeyCtx::from)
+}
+
+#[inline]
+fn init_aes_128_ccm_bluetooth_8(key: &[u8], tag_len: usize) -> Result<KeyCtx, Unspecified> {
+    AeadCtx::aes_128_ccm_bluetooth_8(key, tag_len).map(KeyCtx::from)
+}
+
+#[inline]
+fn init_aes_128_ccm_matter(key: &[u8], tag_len: usize) -> Result<KeyCtx, Unspecified> {
+    AeadCtx::aes_128_ccm_matter(key, tag_len).map(KeyCtx::from)
+}
+
+// AWS-LC only offers `EVP_AEAD` implementations for the 13 byte nonce profiles above. The other
+// nonce lengths are implemented with the CCM `EVP_CIPHER`, which is not available in the FIPS
+// build.
+#[cfg(not(feature = "fips"))]
+mod cipher {
+    use super::{max_input_len, AES_128_KEY_LEN};
+    use crate::aead::unbound_key::KeyCtx;
+    use crate::aead::{Algorithm, AlgorithmID};
+    use crate::cipher::block::BLOCK_LEN;
+    use crate::error::Unspecified;
+    use crate::ptr::LcPtr;
+    use aws_lc::{
+        EVP_CIPHER_CTX_ctrl, EVP_CIPHER_CTX_new, EVP_CipherInit_ex, EVP_DecryptUpdate,
+        EVP_EncryptFinal_ex, EVP_EncryptUpdate, EVP_aes_128_ccm, EVP_CIPHER_CTX,
+        EVP_CTRL_AEAD_GET_TAG, EVP_CTRL_AEAD_SET_IVLEN, EVP_CTRL_AEAD_SET_TAG,
+    };
+    use core::ptr::{null, null_mut};
+    use std::os::raw::c_int;
+    use zeroize::Zeroize;
+
+    const MIN_NONCE_LEN: usize = 7;
+
+    macro_rules! aes_128_ccm {
+        ($tag_len:literal, $nonce_len:literal) => {
+            Algorithm {
+                init: |key, tag_len| AesCcmKey::new(key, tag_len, $nonce_len).map(KeyCtx::AesCcm),
+                key_len: AES_128_KEY_LEN,
+                tag_len: $tag_len,
+                nonce_len: $nonce_len,
+                id: AlgorithmID::AES_128_CCM {
+                    tag_len: $tag_len,
+                    nonce_len: $nonce_len,
+                },
+                max_input_len: max_input_len($nonce_len),
+            }
+        };
+    }
+
+    // Indexed by tag length (4, 8, 16) and then by `nonce_len - MIN_NONCE_LEN`.
+    static AES_128_CCM: [[Algorithm; 6]; 3] = [
+        [
+            aes_128_ccm!(4, 7),
+            aes_128_ccm!(4, 8),
+            aes_128_ccm!(4, 9),
+            aes_128_ccm!(4, 10),
+            aes_128_ccm!(4, 11),
+            aes_128_ccm!(4, 12),
+        ],
+        [
+            aes_128_ccm!(8, 7),
+            aes_128_ccm!(8, 8),
+            aes_128_ccm!(8, 9),
+            aes_128_ccm!(8, 10),
+            aes_128_ccm!(8, 11),
+            aes_128_ccm!(8, 12),
+        ],
+        [
+            aes_128_ccm!(16, 7),
+            aes_128_ccm!(16, 8),
+            aes_128_ccm!(16, 9),
+            aes_128_ccm!(16, 10),
+            aes_128_ccm!(16, 11),
+            aes_128_ccm!(16, 12),
+        ],
+    ];
+
+    pub(super) fn lookup(
+        tag_len: usize,
+        nonce_len: usize,
+    ) -> Result<&'static Algorithm, Unspecified> {
+        let by_nonce_len = match tag_len {
+            4 => &AES_128_CCM[0],
+            8 => &AES_128_CCM[1],
+            16 => &AES_128_CCM[2],
+            _ => return Err(Unspecified),
+        };
+        nonce_len
+            .checked_sub(MIN_NONCE_LEN)
+            .and_then(|index| by_nonce_len.get(index))
+            .ok_or(Unspecified)
+    }
+
+    /// An AES-128-CCM key for the nonce lengths not covered by an `EVP_AEAD`.
+    ///
+    /// CCM needs the message length before it can process the additional data, so each operation
+    /// is performed with a freshly initialized `EVP_CIPHER_CTX`.
+    pub(crate) struct AesCcmKey {
+        key: [u8; AES_128_KEY_LEN],
+        algorithm: &'static Algorithm,
+    }
+
+    impl AesCcmKey {
+        pub(crate) fn new(
+            key_bytes: &[u8],
+            tag_len: usize,
+            nonce_len: usize,
+        ) -> Result<Self, Unspecified> {
+            let key = key_bytes.try_into().map_err(|_| Unspecified)?;
+            Ok(Self {
+                key,
+                algorithm: lookup(tag_len, nonce_len)?,
+            })
+        }
+
+        /// Encrypts `in_out` in place and writes the tag to `tag`.
+        pub(crate) fn seal_in_place(
+            &self,
+            nonce: &[u8],
+            aad: &[u8],
+            in_out: &mut [u8],
+            tag: &mut [u8],
+        ) -> Result<(), Unspecified> {
+            if tag.len() != self.algorithm.tag_len() {
+                return Err(Unspecified);
+            }
+            let mut ctx = self.cipher_ctx(nonce, None)?;
+            self.set_lengths_and_aad(&mut ctx, true, aad, in_out.len())?;
+
+            let in_out_len = c_int::try_from(in_out.len()).map_err(|_| Unspecified)?;
+            let mut out_len: c_int = 0;
+            if 1 != unsafe {
+                EVP_EncryptUpdate(
+                    *ctx.as_mut(),
+                    in_out.as_mut_ptr(),
+                    &mut out_len,
+                    in_out.as_ptr(),
+                    in_out_len,
+                )
+            } {
+                return Err(Unspecified);
+            }
+
+            let mut final_block = [0u8; BLOCK_LEN];
+            if 1 != unsafe {
+                EVP_EncryptFinal_ex(*ctx.as_mut(), final_block.as_mut_ptr(), &mut out_len)
+            } {
+                return Err(Unspecified);
+            }
+
+            if 1 != unsafe {
+                EVP_CIPHER_CTX_ctrl(
+                    *ctx.as_mut(),
+                    EVP_CTRL_AEAD_GET_TAG,
+                    c_int::try_from(tag.len()).map_err(|_| Unspecified)?,
+                    tag.as_mut_ptr().cast(),
+                )
+            } {
+                return Err(Unspecified);
+            }
+            Ok(())
+        }
+
+        /// Decrypts `in_out` in place, failing if `tag` does not authenticate it.
+        pub(crate) fn open_in_place(
+            &self,
+            nonce: &[u8],
+            aad: &[u8],
+            in_out: &mut [u8],
+            tag: &[u8],
+        ) -> Result<(), Unspecified> {
+            if tag.len() != self.algorithm.tag_len() {
+                return Err(Unspecified);
+            }
+            let mut ctx = self.cipher_ctx(nonce, Some(tag))?;
+            self.set_lengths_and_aad(&mut ctx, false, aad, in_out.len())?;
+
+            // The tag is verified by the final update, which zeroes the output on failure.
+            let in_out_len = c_int::try_from(in_out.len()).map_err(|_| Unspecified)?;
+            let mut out_len: c_int = 0;
+            if 1 != unsafe {
+                EVP_DecryptUpdate(
+                    *ctx.as_mut(),
+                    in_out.as_mut_ptr(),
+                    &mut out_len,
+                    in_out.as_ptr(),
+                    in_out_len,
+                )
+            } {
+                return Err(Unspecified);
+            }
+            Ok(())
+        }
+
+        fn cipher_ctx(
+            &self,
+            nonce: &[u8],
+            tag: Option<&[u8]>,
+        ) -> Result<LcPtr<EVP_CIPHER_CTX>, Unspecified> {
+            if nonce.len() != self.algorithm.nonce_len() {
+                return Err(Unspecified);
+            }
+            let enc = c_int::from(tag.is_none());
+            let mut ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+
+            // The nonce and tag lengths must be configured before the key and nonce are set.
+            if 1 != unsafe {
+                EVP_CipherInit_ex(
+                    *ctx.as_mut(),
+                    EVP_aes_128_ccm(),
+                    null_mut(),
+                    null(),
+                    null(),
+                    enc,
+                )
+            } {
+                return Err(Unspecified);
+            }
+
+            let nonce_len = c_int::try_from(nonce.len()).map_err(|_| Unspecified)?;
+            if 1 != unsafe {
+                EVP_CIPHER_CTX_ctrl(
+                    *ctx.as_mut(),
+                    EVP_CTRL_AEAD_SET_IVLEN,
+                    nonce_len,
+                    null_mut(),
+                )
+            } {
+                return Err(Unspecified);
+            }
+
+            // When sealing only the tag length is set, when opening the expected tag is also
+            // provided. AWS-LC copies the tag into the context.
+            let tag_len = c_int::try_from(self.algorithm.tag_len()).map_err(|_| Unspecified)?;
+            let tag_ptr = tag.map_or(null_mut(), |tag| tag.as_ptr() as *mut u8);
+            if 1 != unsafe {
+                EVP_CIPHER_CTX_ctrl(
+                    *ctx.as_mut(),
+                    EVP_CTRL_AEAD_SET_TAG,
+                    tag_len,
+                    tag_ptr.cast(),
+                )
+            } {
+                return Err(Unspecified);
+            }
+
+            if 1 != unsafe {
+                EVP_CipherInit_ex(
+                    *ctx.as_mut(),
+                    null(),
+                    null_mut(),
+                    self.key.as_ptr(),
+                    nonce.as_ptr(),
+                    enc,
+                )
+            } {
+                return Err(Unspecified);
+            }
+            Ok(ctx)
+        }
+
+        #[allow(clippy::unused_self)]
+        fn set_lengths_and_aad(
+            &self,
+            ctx: &mut LcPtr<EVP_CIPHER_CTX>,
+            encrypt: bool,
+            aad: &[u8],
+            message_len: usize,
+        ) -> Result<(), Unspecified> {
+            let update = if encrypt {
+                EVP_EncryptUpdate
+            } else {
+                EVP_DecryptUpdate
+            };
+            let mut out_len: c_int = 0;
+
+            // A null input and output sets the total message length.
+            let message_len = c_int::try_from(message_len).map_err(|_| Unspecified)?;
+            if 1 != unsafe { update(*ctx.as_mut(), null_mut(), &mut out_len, null(), message_len) }
+            {
+                return Err(Unspecified);
+            }
+
+            // A null output processes the additional data, which must be skipped when empty.
+            if !aad.is_empty() {
+                let aad_len = c_int::try_from(aad.len()).map_err(|_| Unspecified)?;
+                if 1 != unsafe {
+                    update(
+                        *ctx.as_mut(),
+                        null_mut(),
+                        &mut out_len,
+                        aad.as_ptr(),
+                        aad_len,
+                    )
+                } {
+                    return Err(Unspecified);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for AesCcmKey {
+        fn drop(&mut self) {
+            self.key.zeroize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aead::{
+        aes_128_ccm, AES_128_CCM_BLUETOOTH, AES_128_CCM_BLUETOOTH_8, AES_128_CCM_MATTER,
+    };
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(&AES_128_CCM_BLUETOOTH, aes_128_ccm(4, 13).unwrap());
+        assert_eq!(&AES_128_CCM_BLUETOOTH_8, aes_128_ccm(8, 13).unwrap());
+        assert_eq!(&AES_128_CCM_MATTER, aes_128_ccm(16, 13).unwrap());
+
+        for tag_len in [0, 2, 6, 12, 32] {
+            assert!(aes_128_ccm(tag_len, 13).is_err());
+        }
+        for nonce_len in [0, 6, 14, 16] {
+            assert!(aes_128_ccm(16, nonce_len).is_err());
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_lookup_lengths() {
+        for tag_len in [4, 8, 16] {
+            for nonce_len in 7..=13 {
+                let algorithm = aes_128_ccm(tag_len, nonce_len).unwrap();
+                assert_eq!(tag_len, algorithm.tag_len());
+                assert_eq!(nonce_len, algorithm.nonce_len());
+                assert_eq!(16, algorithm.key_len());
+            }
+        }
+        assert_eq!(u64::MAX, aes_128_ccm(16, 7).unwrap().max_input_len);
+        assert_eq!((1 << 24) - 1, aes_128_ccm(16, 12).unwrap().max_input_len);
+        assert_eq!(65535, aes_128_ccm(16, 13).unwrap().max_input_len);
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(
+            "AES_128_CCM { tag_len: 8, nonce_len: 13 }",
+            format!("{AES_128_CCM_BLUETOOTH_8:?}")
+        );
+    }
+}
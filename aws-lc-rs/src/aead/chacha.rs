@@ -5,7 +5,8 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::aead::aead_ctx::AeadCtx;
-use crate::aead::{Algorithm, AlgorithmID};
+use crate::aead::unbound_key::KeyCtx;
+use crate::aead::{Algorithm, AlgorithmID, EXTENDED_NONCE_LEN, NONCE_LEN, TAG_LEN};
 use crate::cipher::chacha::KEY_LEN;
 use crate::error;
 
@@ -17,11 +18,35 @@ use crate::error;
 pub const CHACHA20_POLY1305: Algorithm = Algorithm {
     init: init_chacha_aead,
     key_len: KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: NONCE_LEN,
     id: AlgorithmID::CHACHA20_POLY1305,
     max_input_len: u64::MAX,
 };
 
+/// XChaCha20-Poly1305 as described in [draft-irtf-cfrg-xchacha].
+///
+/// The keys are 256 bits long and the nonces are 192 bits long. The longer
+/// nonces are large enough to be chosen at random for every message sealed
+/// under a long-lived key. Use `ExtendedNonce::assume_unique_for_key` to
+/// construct them.
+///
+/// [draft-irtf-cfrg-xchacha]: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-xchacha-03
+pub const XCHACHA20_POLY1305: Algorithm = Algorithm {
+    init: init_xchacha_aead,
+    key_len: KEY_LEN,
+    tag_len: TAG_LEN,
+    nonce_len: EXTENDED_NONCE_LEN,
+    id: AlgorithmID::XCHACHA20_POLY1305,
+    max_input_len: u64::MAX,
+};
+
+#[inline]
+fn init_chacha_aead(key: &[u8], tag_len: usize) -> Result<KeyCtx, error::Unspecified> {
+    AeadCtx::chacha20(key, tag_len).map(KeyCtx::from)
+}
+
 #[inline]
-fn init_chacha_aead(key: &[u8], tag_len: usize) -> Result<AeadCtx, error::Unspecified> {
-    AeadCtx::chacha20(key, tag_len)
+fn init_xchacha_aead(key: &[u8], tag_len: usize) -> Result<KeyCtx, error::Unspecified> {
+    AeadCtx::xchacha20(key, tag_len).map(KeyCtx::from)
 }
@@ -26,7 +26,6 @@
 use super::{poly1305, Nonce, Tag};
 use crate::cipher::block::BLOCK_LEN;
 use crate::cipher::chacha::{self, ChaCha20Key};
-use crate::iv::FixedLength;
 use crate::{constant_time, endian::BigEndian, error};
 
 /// A key for sealing packets.
@@ -61,16 +60,16 @@ impl SealingKey {
         tag_out: &mut [u8; TAG_LEN],
     ) {
         let nonce = make_nonce(sequence_number);
-        let poly_key = derive_poly1305_key(&self.key.k_2, Nonce(FixedLength::from(nonce.as_ref())));
+        let poly_key = derive_poly1305_key(&self.key.k_2, &nonce);
 
         {
             let (len_in_out, data_and_padding_in_out) =
                 plaintext_in_ciphertext_out.split_at_mut(PACKET_LENGTH_LEN);
 
-            self.key.k_1.encrypt_in_place(nonce.as_ref(), len_in_out, 0);
+            self.key.k_1.encrypt_in_place(&nonce, len_in_out, 0);
             self.key
                 .k_2
-                .encrypt_in_place(nonce.as_ref(), data_and_padding_in_out, 1);
+                .encrypt_in_place(&nonce, data_and_padding_in_out, 1);
         }
 
         let Tag(tag, tag_len) = poly1305::sign(poly_key, plaintext_in_ciphertext_out);
@@ -109,9 +108,7 @@ impl OpeningKey {
     ) -> [u8; PACKET_LENGTH_LEN] {
         let mut packet_length = encrypted_packet_length;
         let nonce = make_nonce(sequence_number);
-        self.key
-            .k_1
-            .encrypt_in_place(nonce.as_ref(), &mut packet_length, 0);
+        self.key.k_1.encrypt_in_place(&nonce, &mut packet_length, 0);
         packet_length
     }
 
@@ -142,13 +139,13 @@ impl OpeningKey {
         // We must verify the tag before decrypting so that
         // `ciphertext_in_plaintext_out` is unmodified if verification fails.
         // This is beyond what we guarantee.
-        let poly_key = derive_poly1305_key(&self.key.k_2, Nonce(FixedLength::from(nonce.as_ref())));
+        let poly_key = derive_poly1305_key(&self.key.k_2, &nonce);
         verify(poly_key, ciphertext_in_plaintext_out, tag)?;
 
         let plaintext_in_ciphertext_out = &mut ciphertext_in_plaintext_out[PACKET_LENGTH_LEN..];
         self.key
             .k_2
-            .encrypt_in_place(nonce.as_ref(), plaintext_in_ciphertext_out, 1);
+            .encrypt_in_place(&nonce, plaintext_in_ciphertext_out, 1);
 
         Ok(plaintext_in_ciphertext_out)
     }
@@ -173,8 +170,8 @@ impl Key {
 }
 
 #[inline]
-fn make_nonce(sequence_number: u32) -> Nonce {
-    Nonce::from(BigEndian::from(sequence_number))
+fn make_nonce(sequence_number: u32) -> [u8; chacha::NONCE_LEN] {
+    *Nonce::from(BigEndian::from(sequence_number)).as_ref()
 }
 
 /// The length of key.
@@ -193,10 +190,12 @@ fn verify(key: poly1305::Key, msg: &[u8], tag: &[u8; TAG_LEN]) -> Result<(), err
 }
 
 #[inline]
-#[allow(clippy::needless_pass_by_value)]
-pub(super) fn derive_poly1305_key(chacha_key: &ChaCha20Key, nonce: Nonce) -> poly1305::Key {
+pub(super) fn derive_poly1305_key(
+    chacha_key: &ChaCha20Key,
+    nonce: &[u8; chacha::NONCE_LEN],
+) -> poly1305::Key {
     let mut key_bytes = [0u8; 2 * BLOCK_LEN];
-    chacha_key.encrypt_in_place(nonce.as_ref(), &mut key_bytes, 0);
+    chacha_key.encrypt_in_place(nonce, &mut key_bytes, 0);
     poly1305::Key::new(key_bytes)
 }
 
@@ -223,19 +222,19 @@ mod tests {
         let chacha_key = ChaCha20Key::from(chacha_key_bytes);
         {
             let iv = Nonce::from(&[45u32, 897, 4567]);
-            let poly1305_key = derive_poly1305_key(&chacha_key, iv);
+            let poly1305_key = derive_poly1305_key(&chacha_key, iv.as_ref());
             assert_eq!(&expected_poly1305_key, &poly1305_key.key_and_nonce);
         }
 
         {
             let iv = Nonce::from(&LittleEndian::<u32>::from_array(&[45u32, 897, 4567]));
-            let poly1305_key = derive_poly1305_key(&chacha_key, iv);
+            let poly1305_key = derive_poly1305_key(&chacha_key, iv.as_ref());
             assert_eq!(&expected_poly1305_key, &poly1305_key.key_and_nonce);
         }
 
         {
             let iv = Nonce::from(&BigEndian::<u32>::from_array(&[45u32, 897, 4567]));
-            let poly1305_key = derive_poly1305_key(&chacha_key, iv);
+            let poly1305_key = derive_poly1305_key(&chacha_key, iv.as_ref());
             assert_ne!(&expected_poly1305_key, &poly1305_key.key_and_nonce);
         }
     }
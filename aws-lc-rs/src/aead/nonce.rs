@@ -3,6 +3,7 @@
 // Modifications copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
+use crate::aead::Algorithm;
 use crate::endian::{ArrayEncoding, BigEndian, Encoding, FromArray, LittleEndian};
 use crate::error;
 use crate::iv::FixedLength;
@@ -50,6 +51,89 @@ impl From<&[u8; NONCE_LEN]> for Nonce {
     }
 }
 
+/// A nonce for a single AEAD opening or sealing operation, for the algorithms
+/// whose nonces are not `NONCE_LEN` bytes long.
+///
+/// `XCHACHA20_POLY1305` uses `EXTENDED_NONCE_LEN` byte nonces, and the AES-CCM
+/// algorithms use nonces of 7 to 13 bytes. Every key that accepts a `Nonce`
+/// also accepts an `ExtendedNonce`, and a `Nonce` converts into an
+/// `ExtendedNonce` with `From`.
+///
+/// The user must ensure, for a particular key, that each nonce is unique.
+///
+/// `ExtendedNonce` intentionally doesn't implement `Clone` to ensure that each
+/// one is consumed at most once.
+pub struct ExtendedNonce {
+    value: [u8; MAX_NONCE_LEN],
+    len: usize,
+}
+
+impl ExtendedNonce {
+    /// Constructs an `EXTENDED_NONCE_LEN` byte nonce with the given value,
+    /// assuming that the value is unique for the lifetime of the key it is
+    /// being used with.
+    ///
+    /// Fails if `value` isn't `EXTENDED_NONCE_LEN` bytes long.
+    /// # Errors
+    /// `error::Unspecified` when byte slice length is not `EXTENDED_NONCE_LEN`
+    #[inline]
+    pub fn try_assume_unique_for_key(value: &[u8]) -> Result<Self, error::Unspecified> {
+        let value: &[u8; EXTENDED_NONCE_LEN] = value.try_into()?;
+        Ok(Self::new(value))
+    }
+
+    /// Constructs an `EXTENDED_NONCE_LEN` byte nonce with the given value,
+    /// assuming that the value is unique for the lifetime of the key it is
+    /// being used with.
+    #[inline]
+    #[must_use]
+    pub fn assume_unique_for_key(value: [u8; EXTENDED_NONCE_LEN]) -> Self {
+        Self::new(&value)
+    }
+
+    /// Constructs a nonce for `algorithm` with the given value, assuming that
+    /// the value is unique for the lifetime of the key it is being used with.
+    ///
+    /// Fails if `value` isn't `algorithm.nonce_len()` bytes long.
+    /// # Errors
+    /// `error::Unspecified` when byte slice length is not `algorithm.nonce_len()`
+    #[inline]
+    pub fn try_assume_unique_for_algorithm(
+        algorithm: &Algorithm,
+        value: &[u8],
+    ) -> Result<Self, error::Unspecified> {
+        if value.len() != algorithm.nonce_len() {
+            return Err(error::Unspecified);
+        }
+        Ok(Self::new(value))
+    }
+
+    #[inline]
+    pub(crate) fn new(value: &[u8]) -> Self {
+        debug_assert!(value.len() <= MAX_NONCE_LEN);
+        let mut nonce = Self {
+            value: [0u8; MAX_NONCE_LEN],
+            len: value.len(),
+        };
+        nonce.value[..value.len()].copy_from_slice(value);
+        nonce
+    }
+}
+
+impl AsRef<[u8]> for ExtendedNonce {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.value[..self.len]
+    }
+}
+
+impl From<Nonce> for ExtendedNonce {
+    #[inline]
+    fn from(nonce: Nonce) -> Self {
+        Self::new(nonce.as_ref())
+    }
+}
+
 #[allow(useless_deprecated)] // https://github.com/rust-lang/rust/issues/39935
 #[deprecated]
 impl From<&[u32; NONCE_LEN / 4]> for Nonce {
@@ -90,9 +174,15 @@ impl From<&[u8; IV_LEN]> for Nonce {
     }
 }
 
-/// All the AEADs we support use 96-bit nonces.
+/// The length of the 96-bit nonces used by most of the AEADs we support.
 pub const NONCE_LEN: usize = 96 / 8;
 
+/// The length of the 192-bit extended nonces used by `XCHACHA20_POLY1305`.
+pub const EXTENDED_NONCE_LEN: usize = 192 / 8;
+
+/// The maximum length of a nonce for the algorithms in this module.
+pub(crate) const MAX_NONCE_LEN: usize = EXTENDED_NONCE_LEN;
+
 #[cfg(test)]
 mod tests {
 
@@ -105,4 +195,50 @@ mod tests {
 
         assert_eq!(&[1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12], nonce.as_ref());
     }
+
+    #[test]
+    fn test_nonce_extended() {
+        use crate::aead::{ExtendedNonce, Nonce, EXTENDED_NONCE_LEN, NONCE_LEN};
+        let bytes = [7u8; EXTENDED_NONCE_LEN * 2];
+
+        let nonce = ExtendedNonce::try_assume_unique_for_key(&bytes[..EXTENDED_NONCE_LEN]).unwrap();
+        assert_eq!(&bytes[..EXTENDED_NONCE_LEN], nonce.as_ref());
+        let nonce = ExtendedNonce::assume_unique_for_key([7u8; EXTENDED_NONCE_LEN]);
+        assert_eq!(&bytes[..EXTENDED_NONCE_LEN], nonce.as_ref());
+        let nonce = ExtendedNonce::from(Nonce::assume_unique_for_key([7u8; NONCE_LEN]));
+        assert_eq!(&bytes[..NONCE_LEN], nonce.as_ref());
+
+        assert!(ExtendedNonce::try_assume_unique_for_key(&bytes[..NONCE_LEN]).is_err());
+        assert!(
+            ExtendedNonce::try_assume_unique_for_key(&bytes[..EXTENDED_NONCE_LEN - 1]).is_err()
+        );
+        assert!(
+            ExtendedNonce::try_assume_unique_for_key(&bytes[..EXTENDED_NONCE_LEN + 1]).is_err()
+        );
+        assert!(Nonce::try_assume_unique_for_key(&bytes[..EXTENDED_NONCE_LEN]).is_err());
+    }
+
+    #[test]
+    fn test_nonce_for_algorithm() {
+        use crate::aead::{ExtendedNonce, AES_128_CCM_BLUETOOTH, AES_128_GCM, XCHACHA20_POLY1305};
+        let bytes = [7u8; 32];
+
+        for algorithm in [&AES_128_GCM, &XCHACHA20_POLY1305, &AES_128_CCM_BLUETOOTH] {
+            let nonce_len = algorithm.nonce_len();
+            let nonce =
+                ExtendedNonce::try_assume_unique_for_algorithm(algorithm, &bytes[..nonce_len])
+                    .unwrap();
+            assert_eq!(&bytes[..nonce_len], nonce.as_ref());
+            assert!(ExtendedNonce::try_assume_unique_for_algorithm(
+                algorithm,
+                &bytes[..nonce_len - 1]
+            )
+            .is_err());
+            assert!(ExtendedNonce::try_assume_unique_for_algorithm(
+                algorithm,
+                &bytes[..nonce_len + 1]
+            )
+            .is_err());
+        }
+    }
 }
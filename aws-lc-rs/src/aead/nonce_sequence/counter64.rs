@@ -3,7 +3,6 @@
 
 use crate::aead::{Nonce, NonceSequence, NONCE_LEN};
 use crate::error::Unspecified;
-use crate::iv::FixedLength;
 
 /// `Counter64` is an implementation of the `NonceSequence` trait.
 ///
@@ -118,7 +117,7 @@ impl NonceSequence for Counter64 {
         nonce_bytes[..4].copy_from_slice(&self.identifier);
         nonce_bytes[4..].copy_from_slice(&bytes);
         self.counter = self.counter.wrapping_add(1);
-        Ok(Nonce(FixedLength::from(nonce_bytes)))
+        Ok(Nonce::assume_unique_for_key(nonce_bytes))
     }
 }
 
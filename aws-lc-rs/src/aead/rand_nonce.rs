@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::error::Unspecified;
+use crate::rand;
 use core::fmt::Debug;
 
-use super::{aead_ctx::AeadCtx, Aad, Algorithm, AlgorithmID, Nonce, Tag, UnboundKey};
+use super::{
+    aead_ctx::AeadCtx, Aad, Algorithm, AlgorithmID, ExtendedNonce, Nonce, Tag, UnboundKey,
+    EXTENDED_NONCE_LEN, NONCE_LEN,
+};
 
 /// AEAD Cipher key using a randomized nonce.
 ///
@@ -13,6 +17,11 @@ use super::{aead_ctx::AeadCtx, Aad, Algorithm, AlgorithmID, Nonce, Tag, UnboundK
 /// The following algorithms are supported:
 /// * `AES_128_GCM`
 /// * `AES_256_GCM`
+/// * `XCHACHA20_POLY1305`
+///
+/// `XCHACHA20_POLY1305` uses `EXTENDED_NONCE_LEN` byte nonces, so keys for it
+/// must be used with the `_extended` sealing methods, which return an
+/// `ExtendedNonce`.
 ///
 /// Prefer this type in place of `LessSafeKey`, `OpeningKey`, `SealingKey`.
 pub struct RandomizedNonceKey {
@@ -35,9 +44,12 @@ impl RandomizedNonceKey {
                 algorithm.tag_len(),
                 algorithm.nonce_len(),
             ),
-            AlgorithmID::AES_128_GCM_SIV
+            AlgorithmID::XCHACHA20_POLY1305 => AeadCtx::xchacha20(key_bytes, algorithm.tag_len()),
+            AlgorithmID::AES_192_GCM
+            | AlgorithmID::AES_128_GCM_SIV
             | AlgorithmID::AES_256_GCM_SIV
-            | AlgorithmID::CHACHA20_POLY1305 => return Err(Unspecified),
+            | AlgorithmID::CHACHA20_POLY1305
+            | AlgorithmID::AES_128_CCM { .. } => return Err(Unspecified),
         }?;
         Ok(Self {
             key: UnboundKey::from(ctx),
@@ -56,16 +68,42 @@ impl RandomizedNonceKey {
     /// `error::Unspecified` when ciphertext is invalid.
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
-    pub fn open_in_place<'in_out, A>(
+    pub fn open_in_place<'in_out, N, A>(
         &self,
-        nonce: Nonce,
+        nonce: N,
         aad: Aad<A>,
         in_out: &'in_out mut [u8],
     ) -> Result<&'in_out mut [u8], Unspecified>
     where
+        N: Into<ExtendedNonce>,
+        A: AsRef<[u8]>,
+    {
+        self.key
+            .open_within(nonce.into(), aad.as_ref(), in_out, 0..)
+    }
+
+    /// Authenticates and decrypts (“opens”) a batch of independent messages in place.
+    ///
+    /// Each item is the `nonce`, `aad` and `in_out` of a single [`Self::open_in_place`]
+    /// operation, and the messages are opened in order. A message that fails to open does not
+    /// affect the others.
+    ///
+    /// Returns the plaintext, or an error, for each item, in order.
+    #[inline]
+    pub fn open_in_place_batch<'in_out, N, A, I>(
+        &self,
+        items: I,
+    ) -> Vec<Result<&'in_out mut [u8], Unspecified>>
+    where
+        N: Into<ExtendedNonce>,
         A: AsRef<[u8]>,
+        I: IntoIterator<Item = (N, Aad<A>, &'in_out mut [u8])>,
     {
-        self.key.open_within(nonce, aad.as_ref(), in_out, 0..)
+        self.key.open_in_place_batch(
+            items
+                .into_iter()
+                .map(|(nonce, aad, in_out)| (nonce.into(), aad, in_out)),
+        )
     }
 
     /// Encrypts and signs (“seals”) data in place, appending the tag to the
@@ -81,7 +119,9 @@ impl RandomizedNonceKey {
     /// The Nonce used for the operation is randomly generated, and returned to the caller.
     ///
     /// # Errors
-    /// `error::Unspecified` if encryption operation fails.
+    /// `error::Unspecified` if encryption operation fails, or if the key's
+    /// algorithm doesn't use `NONCE_LEN` byte nonces. Use
+    /// `seal_in_place_append_tag_extended` for those algorithms.
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
     pub fn seal_in_place_append_tag<'a, A, InOut>(
@@ -89,12 +129,33 @@ impl RandomizedNonceKey {
         aad: Aad<A>,
         in_out: &'a mut InOut,
     ) -> Result<Nonce, Unspecified>
+    where
+        A: AsRef<[u8]>,
+        InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
+    {
+        self.check_nonce_len()?;
+        let nonce = self.seal_in_place_append_tag_extended(aad, in_out)?;
+        Nonce::try_assume_unique_for_key(nonce.as_ref())
+    }
+
+    /// Like `seal_in_place_append_tag`, except it supports every algorithm
+    /// of `RandomizedNonceKey` and returns the nonce as an `ExtendedNonce`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if encryption operation fails.
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn seal_in_place_append_tag_extended<'a, A, InOut>(
+        &self,
+        aad: Aad<A>,
+        in_out: &'a mut InOut,
+    ) -> Result<ExtendedNonce, Unspecified>
     where
         A: AsRef<[u8]>,
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
         self.key
-            .seal_in_place_append_tag(None, aad.as_ref(), in_out)
+            .seal_in_place_append_tag(self.random_nonce()?, aad.as_ref(), in_out)
     }
 
     /// Encrypts and signs (“seals”) data in place.
@@ -112,7 +173,9 @@ impl RandomizedNonceKey {
     /// The Nonce used for the operation is randomly generated, and returned to the caller.
     ///
     /// # Errors
-    /// `error::Unspecified` if encryption operation fails.
+    /// `error::Unspecified` if encryption operation fails, or if the key's
+    /// algorithm doesn't use `NONCE_LEN` byte nonces. Use
+    /// `seal_in_place_separate_tag_extended` for those algorithms.
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
     pub fn seal_in_place_separate_tag<A>(
@@ -120,11 +183,67 @@ impl RandomizedNonceKey {
         aad: Aad<A>,
         in_out: &mut [u8],
     ) -> Result<(Nonce, Tag), Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        self.check_nonce_len()?;
+        let (nonce, tag) = self.seal_in_place_separate_tag_extended(aad, in_out)?;
+        Ok((Nonce::try_assume_unique_for_key(nonce.as_ref())?, tag))
+    }
+
+    /// Like `seal_in_place_separate_tag`, except it supports every algorithm
+    /// of `RandomizedNonceKey` and returns the nonce as an `ExtendedNonce`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if encryption operation fails.
+    #[inline]
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn seal_in_place_separate_tag_extended<A>(
+        &self,
+        aad: Aad<A>,
+        in_out: &mut [u8],
+    ) -> Result<(ExtendedNonce, Tag), Unspecified>
     where
         A: AsRef<[u8]>,
     {
         self.key
-            .seal_in_place_separate_tag(None, aad.as_ref(), in_out)
+            .seal_in_place_separate_tag(self.random_nonce()?, aad.as_ref(), in_out)
+    }
+
+    /// Encrypts and signs (“seals”) a batch of independent messages in place.
+    ///
+    /// Each item is the `aad` and `in_out` of a single [`Self::seal_in_place_separate_tag`]
+    /// operation, and the messages are sealed in order, each with its own random nonce. A
+    /// message that fails to seal does not affect the others.
+    ///
+    /// The key's `EVP_AEAD_CTX` is looked up once for the whole batch, but each message is still
+    /// sealed by its own call into AWS-LC.
+    ///
+    /// Returns the nonce and the tag, or an error, for each item, in order. Like
+    /// [`Self::seal_in_place_separate_tag`], every item fails if the algorithm's nonces are not
+    /// `NONCE_LEN` bytes long.
+    #[inline]
+    pub fn seal_in_place_separate_tag_batch<'in_out, A, I>(
+        &self,
+        items: I,
+    ) -> Vec<Result<(Nonce, Tag), Unspecified>>
+    where
+        A: AsRef<[u8]>,
+        I: IntoIterator<Item = (Aad<A>, &'in_out mut [u8])>,
+    {
+        let items = items.into_iter();
+        if self.check_nonce_len().is_err() {
+            return items.map(|_| Err(Unspecified)).collect();
+        }
+        // Only the AES-GCM keys remain, and AWS-LC generates their nonces while sealing.
+        self.key
+            .seal_in_place_separate_tag_batch(items.map(|(aad, in_out)| (None, aad, in_out)))
+            .into_iter()
+            .map(|result| {
+                let (nonce, tag) = result?;
+                Ok((Nonce::try_assume_unique_for_key(nonce.as_ref())?, tag))
+            })
+            .collect()
     }
 
     /// The key's AEAD algorithm.
@@ -133,6 +252,28 @@ impl RandomizedNonceKey {
     pub fn algorithm(&self) -> &'static Algorithm {
         self.algorithm
     }
+
+    // AWS-LC generates the nonces for the AES-GCM algorithms while sealing, which is signaled by
+    // `None`. Extended nonces are large enough to be drawn from the random number generator here.
+    fn random_nonce(&self) -> Result<Option<ExtendedNonce>, Unspecified> {
+        match self.algorithm.id {
+            AlgorithmID::XCHACHA20_POLY1305 => {
+                let mut nonce = [0u8; EXTENDED_NONCE_LEN];
+                rand::fill(&mut nonce)?;
+                Ok(Some(ExtendedNonce::assume_unique_for_key(nonce)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    // Checked before sealing so that no ciphertext is produced for a nonce that can't be
+    // returned as a `Nonce`.
+    fn check_nonce_len(&self) -> Result<(), Unspecified> {
+        if self.algorithm.nonce_len() != NONCE_LEN {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
 }
 
 #[allow(clippy::missing_fields_in_debug)]
@@ -148,7 +289,7 @@ impl Debug for RandomizedNonceKey {
 mod tests {
     use super::{Aad, RandomizedNonceKey};
     use crate::{
-        aead::{AES_128_GCM, AES_256_GCM, CHACHA20_POLY1305},
+        aead::{AES_128_GCM, AES_256_GCM, CHACHA20_POLY1305, NONCE_LEN, XCHACHA20_POLY1305},
         test::from_hex,
     };
     use paste::paste;
@@ -187,6 +328,49 @@ mod tests {
 
                     let mut in_out = Vec::from(plaintext.as_slice());
 
+                    let nonce = rand_nonce_key
+                        .seal_in_place_append_tag_extended(Aad::empty(), &mut in_out)
+                        .unwrap();
+
+                    assert_eq!(*$expect_nonce_len, nonce.as_ref().len());
+                    assert_ne!(plaintext, in_out[..plaintext.len()]);
+
+                    rand_nonce_key
+                        .open_in_place(nonce, Aad::empty(), &mut in_out)
+                        .unwrap();
+
+                    assert_eq!(plaintext, in_out[..plaintext.len()]);
+
+                    let mut in_out = Vec::from(plaintext.as_slice());
+
+                    let (nonce, tag) = rand_nonce_key
+                        .seal_in_place_separate_tag_extended(Aad::empty(), &mut in_out)
+                        .unwrap();
+
+                    assert_eq!(*$expect_nonce_len, nonce.as_ref().len());
+                    assert_ne!(plaintext, in_out[..plaintext.len()]);
+
+                    in_out.extend(tag.as_ref());
+
+                    rand_nonce_key
+                        .open_in_place(nonce, Aad::empty(), &mut in_out)
+                        .unwrap();
+
+                    assert_eq!(plaintext, in_out[..plaintext.len()]);
+
+                    let mut in_out = Vec::from(plaintext.as_slice());
+
+                    if *$expect_nonce_len != NONCE_LEN {
+                        assert!(rand_nonce_key
+                            .seal_in_place_append_tag(Aad::empty(), &mut in_out)
+                            .is_err());
+                        assert!(rand_nonce_key
+                            .seal_in_place_separate_tag(Aad::empty(), &mut in_out)
+                            .is_err());
+                        assert_eq!(plaintext, in_out);
+                        return;
+                    }
+
                     let nonce = rand_nonce_key
                         .seal_in_place_append_tag(Aad::empty(), &mut in_out)
                         .unwrap();
@@ -222,4 +406,11 @@ mod tests {
     test_randnonce!(aes_128_gcm, &AES_128_GCM, TEST_128_BIT_KEY, &16, &12);
     test_randnonce!(aes_256_gcm, &AES_256_GCM, TEST_256_BIT_KEY, &16, &12);
     test_randnonce!(chacha20_poly1305, &CHACHA20_POLY1305, TEST_256_BIT_KEY);
+    test_randnonce!(
+        xchacha20_poly1305,
+        &XCHACHA20_POLY1305,
+        TEST_256_BIT_KEY,
+        &16,
+        &24
+    );
 }
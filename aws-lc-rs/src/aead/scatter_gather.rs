@@ -0,0 +1,649 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::chacha20_poly1305_openssh::derive_poly1305_key;
+use super::{poly1305, Aad, Algorithm, AlgorithmID, Nonce, NONCE_LEN};
+use crate::cipher::block::BLOCK_LEN;
+use crate::cipher::chacha::{self, ChaCha20Key};
+use crate::constant_time;
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    EVP_CIPHER_CTX_copy, EVP_CIPHER_CTX_ctrl, EVP_CIPHER_CTX_new, EVP_CipherInit_ex,
+    EVP_DecryptFinal_ex, EVP_DecryptUpdate, EVP_EncryptFinal_ex, EVP_EncryptUpdate,
+    EVP_aes_128_gcm, EVP_aes_192_gcm, EVP_aes_256_gcm, EVP_CIPHER, EVP_CIPHER_CTX,
+    EVP_CTRL_AEAD_GET_TAG, EVP_CTRL_AEAD_SET_TAG,
+};
+use core::fmt::Debug;
+use core::ptr::{null, null_mut};
+use std::io::{IoSlice, IoSliceMut};
+use std::os::raw::c_int;
+use zeroize::Zeroize;
+
+/// An AEAD key for sealing and opening messages that are split across several buffers.
+///
+/// The input fragments are processed in order without first being copied into a contiguous
+/// buffer, and the output is written to a separate list of fragments. The boundaries of the
+/// input and output fragments do not need to line up, but their total lengths must be equal.
+/// The tag is written to (or read from) its own slice.
+///
+/// `EVP_AEAD` only processes contiguous messages, so AES-GCM uses the incremental GCM
+/// `EVP_CIPHER`, and ChaCha20-Poly1305 combines the ChaCha20 keystream with the incremental
+/// Poly1305 MAC as specified in [RFC 8439]. The results are identical to those of
+/// [`super::LessSafeKey`].
+///
+/// The following algorithms are supported:
+/// * `AES_128_GCM`
+/// * `AES_192_GCM`
+/// * `AES_256_GCM`
+/// * `CHACHA20_POLY1305`
+///
+/// [RFC 8439]: https://www.rfc-editor.org/rfc/rfc8439
+pub struct ScatterGatherKey {
+    inner: KeyInner,
+    algorithm: &'static Algorithm,
+}
+
+enum KeyInner {
+    AesGcm(LcPtr<EVP_CIPHER_CTX>),
+    ChaCha20Poly1305(ChaCha20Key),
+}
+
+impl ScatterGatherKey {
+    /// Constructs a `ScatterGatherKey`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the algorithm is not supported or the length of `key_bytes` does
+    /// not match the algorithm.
+    ///
+    // # FIPS
+    // This function must not be used.
+    pub fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if key_bytes.len() != algorithm.key_len() {
+            return Err(Unspecified);
+        }
+        let inner = match algorithm.id {
+            AlgorithmID::AES_128_GCM | AlgorithmID::AES_192_GCM | AlgorithmID::AES_256_GCM => {
+                KeyInner::AesGcm(aes_gcm_key(algorithm, key_bytes)?)
+            }
+            AlgorithmID::CHACHA20_POLY1305 => {
+                let key_bytes: [u8; chacha::KEY_LEN] = key_bytes.try_into()?;
+                KeyInner::ChaCha20Poly1305(ChaCha20Key::from(key_bytes))
+            }
+            _ => return Err(Unspecified),
+        };
+        Ok(Self { inner, algorithm })
+    }
+
+    /// Encrypts the concatenation of `in_fragments` into `out_fragments`, and writes the tag to
+    /// `tag_out`.
+    ///
+    /// The total length of `out_fragments` must equal the total length of `in_fragments`, and
+    /// `tag_out` must be exactly `self.algorithm().tag_len()` bytes long.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the lengths are invalid, or if sealing fails.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn seal_scatter_gather<A: AsRef<[u8]>>(
+        &self,
+        nonce: Nonce,
+        aad: Aad<A>,
+        in_fragments: &[IoSlice<'_>],
+        out_fragments: &mut [IoSliceMut<'_>],
+        tag_out: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        self.check_lengths(&nonce, in_fragments, out_fragments, tag_out)?;
+        match &self.inner {
+            KeyInner::AesGcm(ctx) => {
+                let mut ctx = message_ctx(ctx, &nonce, aad.as_ref(), true)?;
+                aes_gcm_seal(&mut ctx, in_fragments, out_fragments, tag_out)
+            }
+            KeyInner::ChaCha20Poly1305(key) => chacha20_poly1305_seal(
+                key,
+                nonce.as_ref(),
+                aad.as_ref(),
+                in_fragments,
+                out_fragments,
+                tag_out,
+            ),
+        }
+    }
+
+    /// Authenticates and decrypts the concatenation of `in_fragments` into `out_fragments`.
+    ///
+    /// The total length of `out_fragments` must equal the total length of `in_fragments`, and
+    /// `tag` must be exactly `self.algorithm().tag_len()` bytes long.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the lengths are invalid or the message fails to authenticate, in
+    /// which case `out_fragments` are overwritten with zeros.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn open_scatter_gather<A: AsRef<[u8]>>(
+        &self,
+        nonce: Nonce,
+        aad: Aad<A>,
+        in_fragments: &[IoSlice<'_>],
+        tag: &[u8],
+        out_fragments: &mut [IoSliceMut<'_>],
+    ) -> Result<(), Unspecified> {
+        self.check_lengths(&nonce, in_fragments, out_fragments, tag)?;
+        let result = match &self.inner {
+            KeyInner::AesGcm(ctx) => message_ctx(ctx, &nonce, aad.as_ref(), false)
+                .and_then(|mut ctx| aes_gcm_open(&mut ctx, in_fragments, tag, out_fragments)),
+            KeyInner::ChaCha20Poly1305(key) => chacha20_poly1305_open(
+                key,
+                nonce.as_ref(),
+                aad.as_ref(),
+                in_fragments,
+                tag,
+                out_fragments,
+            ),
+        };
+        if result.is_err() {
+            for fragment in out_fragments.iter_mut() {
+                fragment[..].zeroize();
+            }
+        }
+        result
+    }
+
+    /// The key's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
+    fn check_lengths(
+        &self,
+        nonce: &Nonce,
+        in_fragments: &[IoSlice<'_>],
+        out_fragments: &[IoSliceMut<'_>],
+        tag: &[u8],
+    ) -> Result<(), Unspecified> {
+        if nonce.as_ref().len() != self.algorithm.nonce_len()
+            || tag.len() != self.algorithm.tag_len()
+        {
+            return Err(Unspecified);
+        }
+        let in_len = total_len(in_fragments.iter().map(|fragment| fragment.len()))?;
+        let out_len = total_len(out_fragments.iter().map(|fragment| fragment.len()))?;
+        if in_len != out_len || in_len as u64 > self.algorithm.max_input_len {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl Debug for ScatterGatherKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScatterGatherKey")
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+// The keyed AES-GCM context is only read, by `EVP_CIPHER_CTX_copy`, after construction, and
+// the ChaCha20 key is only read.
+unsafe impl Send for ScatterGatherKey {}
+
+unsafe impl Sync for ScatterGatherKey {}
+
+fn total_len(mut lens: impl Iterator<Item = usize>) -> Result<usize, Unspecified> {
+    lens.try_fold(0usize, usize::checked_add).ok_or(Unspecified)
+}
+
+// Calls `f` with corresponding chunks of the input and output fragments, splitting the fragments
+// wherever either list has a boundary. The total lengths must already have been checked to be
+// equal.
+fn for_each_chunk(
+    in_fragments: &[IoSlice<'_>],
+    out_fragments: &mut [IoSliceMut<'_>],
+    mut f: impl FnMut(&[u8], &mut [u8]) -> Result<(), Unspecified>,
+) -> Result<(), Unspecified> {
+    let mut outputs = out_fragments.iter_mut().map(|fragment| &mut fragment[..]);
+    let mut output: &mut [u8] = &mut [];
+    for input in in_fragments {
+        let mut input: &[u8] = input;
+        while !input.is_empty() {
+            if output.is_empty() {
+                output = outputs.next().ok_or(Unspecified)?;
+                continue;
+            }
+            let len = input.len().min(output.len());
+            let (in_chunk, in_rest) = input.split_at(len);
+            let (out_chunk, out_rest) = core::mem::take(&mut output).split_at_mut(len);
+            f(in_chunk, out_chunk)?;
+            input = in_rest;
+            output = out_rest;
+        }
+    }
+    Ok(())
+}
+
+// Schedules the key once, so that each message only sets the nonce on a copy of the context.
+fn aes_gcm_key(
+    algorithm: &'static Algorithm,
+    key_bytes: &[u8],
+) -> Result<LcPtr<EVP_CIPHER_CTX>, Unspecified> {
+    let mut ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+    if 1 != unsafe {
+        EVP_CipherInit_ex(
+            *ctx.as_mut(),
+            aes_gcm_cipher(algorithm)?,
+            null_mut(),
+            key_bytes.as_ptr(),
+            null(),
+            1,
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(ctx)
+}
+
+// Copies the keyed context, sets the nonce and direction, and processes the additional data.
+// The default GCM nonce length of the `EVP_CIPHER` matches `NONCE_LEN`.
+fn message_ctx(
+    key_ctx: &LcPtr<EVP_CIPHER_CTX>,
+    nonce: &Nonce,
+    aad: &[u8],
+    encrypt: bool,
+) -> Result<LcPtr<EVP_CIPHER_CTX>, Unspecified> {
+    debug_assert_eq!(NONCE_LEN, nonce.as_ref().len());
+    let mut ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+    if 1 != unsafe { EVP_CIPHER_CTX_copy(*ctx.as_mut(), *key_ctx.as_const()) } {
+        return Err(Unspecified);
+    }
+    // A null cipher and key keep the ones the context was initialized with.
+    if 1 != unsafe {
+        EVP_CipherInit_ex(
+            *ctx.as_mut(),
+            null(),
+            null_mut(),
+            null(),
+            nonce.as_ref().as_ptr(),
+            c_int::from(encrypt),
+        )
+    } {
+        return Err(Unspecified);
+    }
+
+    // A null output processes the additional data.
+    if !aad.is_empty() {
+        aes_gcm_update(&mut ctx, encrypt, aad, None)?;
+    }
+    Ok(ctx)
+}
+
+fn aes_gcm_cipher(algorithm: &'static Algorithm) -> Result<*const EVP_CIPHER, Unspecified> {
+    match algorithm.id {
+        AlgorithmID::AES_128_GCM => Ok(unsafe { EVP_aes_128_gcm() }),
+        AlgorithmID::AES_192_GCM => Ok(unsafe { EVP_aes_192_gcm() }),
+        AlgorithmID::AES_256_GCM => Ok(unsafe { EVP_aes_256_gcm() }),
+        _ => Err(Unspecified),
+    }
+}
+
+fn aes_gcm_update(
+    ctx: &mut LcPtr<EVP_CIPHER_CTX>,
+    encrypt: bool,
+    input: &[u8],
+    output: Option<&mut [u8]>,
+) -> Result<(), Unspecified> {
+    let update = if encrypt {
+        EVP_EncryptUpdate
+    } else {
+        EVP_DecryptUpdate
+    };
+    let input_len = c_int::try_from(input.len()).map_err(|_| Unspecified)?;
+    let output_ptr = output.map_or(null_mut(), <[u8]>::as_mut_ptr);
+    let mut out_len: c_int = 0;
+    if 1 != unsafe {
+        update(
+            *ctx.as_mut(),
+            output_ptr,
+            &mut out_len,
+            input.as_ptr(),
+            input_len,
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+fn aes_gcm_seal(
+    ctx: &mut LcPtr<EVP_CIPHER_CTX>,
+    in_fragments: &[IoSlice<'_>],
+    out_fragments: &mut [IoSliceMut<'_>],
+    tag_out: &mut [u8],
+) -> Result<(), Unspecified> {
+    for_each_chunk(in_fragments, out_fragments, |input, output| {
+        aes_gcm_update(ctx, true, input, Some(output))
+    })?;
+
+    // GCM does not buffer any data, so nothing is written by the final call.
+    let mut final_block = [0u8; BLOCK_LEN];
+    let mut out_len: c_int = 0;
+    if 1 != unsafe { EVP_EncryptFinal_ex(*ctx.as_mut(), final_block.as_mut_ptr(), &mut out_len) } {
+        return Err(Unspecified);
+    }
+
+    if 1 != unsafe {
+        EVP_CIPHER_CTX_ctrl(
+            *ctx.as_mut(),
+            EVP_CTRL_AEAD_GET_TAG,
+            c_int::try_from(tag_out.len()).map_err(|_| Unspecified)?,
+            tag_out.as_mut_ptr().cast(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+fn aes_gcm_open(
+    ctx: &mut LcPtr<EVP_CIPHER_CTX>,
+    in_fragments: &[IoSlice<'_>],
+    tag: &[u8],
+    out_fragments: &mut [IoSliceMut<'_>],
+) -> Result<(), Unspecified> {
+    for_each_chunk(in_fragments, out_fragments, |input, output| {
+        aes_gcm_update(ctx, false, input, Some(output))
+    })?;
+
+    // AWS-LC copies the expected tag into the context, and the final call verifies it.
+    let tag_ptr = tag.as_ptr() as *mut u8;
+    if 1 != unsafe {
+        EVP_CIPHER_CTX_ctrl(
+            *ctx.as_mut(),
+            EVP_CTRL_AEAD_SET_TAG,
+            c_int::try_from(tag.len()).map_err(|_| Unspecified)?,
+            tag_ptr.cast(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    let mut final_block = [0u8; BLOCK_LEN];
+    let mut out_len: c_int = 0;
+    if 1 != unsafe { EVP_DecryptFinal_ex(*ctx.as_mut(), final_block.as_mut_ptr(), &mut out_len) } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+// Starts the RFC 8439 MAC of a message: the Poly1305 key is the first 32 bytes of the ChaCha20
+// keystream block 0, and the additional data is padded to a multiple of 16 bytes.
+fn chacha20_poly1305_mac(
+    key: &ChaCha20Key,
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+) -> poly1305::Context {
+    let mut mac = poly1305::Context::from_key(derive_poly1305_key(key, nonce));
+    mac.update(aad);
+    poly1305_pad(&mut mac, aad.len());
+    mac
+}
+
+fn poly1305_pad(mac: &mut poly1305::Context, len: usize) {
+    let pad_len = (BLOCK_LEN - len % BLOCK_LEN) % BLOCK_LEN;
+    mac.update(&[0u8; BLOCK_LEN][..pad_len]);
+}
+
+// Pads the ciphertext, and appends the little-endian lengths of the additional data and the
+// ciphertext.
+fn chacha20_poly1305_tag(
+    mut mac: poly1305::Context,
+    aad_len: usize,
+    ciphertext_len: usize,
+) -> super::Tag {
+    poly1305_pad(&mut mac, ciphertext_len);
+    mac.update(&(aad_len as u64).to_le_bytes());
+    mac.update(&(ciphertext_len as u64).to_le_bytes());
+    mac.finish()
+}
+
+fn chacha20_poly1305_seal(
+    key: &ChaCha20Key,
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    in_fragments: &[IoSlice<'_>],
+    out_fragments: &mut [IoSliceMut<'_>],
+    tag_out: &mut [u8],
+) -> Result<(), Unspecified> {
+    let mut mac = chacha20_poly1305_mac(key, nonce, aad);
+    let mut offset = 0u64;
+    for_each_chunk(in_fragments, out_fragments, |input, output| {
+        output.copy_from_slice(input);
+        // The message is encrypted starting with keystream block 1.
+        chacha::apply_keystream(key, nonce, 1, offset, output)?;
+        mac.update(output);
+        offset += output.len() as u64;
+        Ok(())
+    })?;
+    let tag = chacha20_poly1305_tag(mac, aad.len(), usize::try_from(offset)?);
+    tag_out.copy_from_slice(tag.as_ref());
+    Ok(())
+}
+
+fn chacha20_poly1305_open(
+    key: &ChaCha20Key,
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+    in_fragments: &[IoSlice<'_>],
+    tag: &[u8],
+    out_fragments: &mut [IoSliceMut<'_>],
+) -> Result<(), Unspecified> {
+    // The ciphertext is authenticated before any of it is decrypted.
+    let mut mac = chacha20_poly1305_mac(key, nonce, aad);
+    let mut ciphertext_len = 0usize;
+    for fragment in in_fragments {
+        mac.update(fragment);
+        ciphertext_len += fragment.len();
+    }
+    let expected_tag = chacha20_poly1305_tag(mac, aad.len(), ciphertext_len);
+    constant_time::verify_slices_are_equal(expected_tag.as_ref(), tag)?;
+
+    let mut offset = 0u64;
+    for_each_chunk(in_fragments, out_fragments, |input, output| {
+        output.copy_from_slice(input);
+        chacha::apply_keystream(key, nonce, 1, offset, output)?;
+        offset += output.len() as u64;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScatterGatherKey;
+    use crate::aead::{
+        Aad, Algorithm, LessSafeKey, Nonce, UnboundKey, AES_128_GCM, AES_192_GCM, AES_256_GCM,
+        AES_256_GCM_SIV, CHACHA20_POLY1305, XCHACHA20_POLY1305,
+    };
+    use std::io::{IoSlice, IoSliceMut};
+
+    const KEY: &[u8] = &[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+        0x1e, 0x1f,
+    ];
+
+    const NONCE: [u8; 12] = [
+        0xb0, 0xb1, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xbb,
+    ];
+
+    const AAD: &[u8] = b"scatter/gather";
+
+    // Splits `data` into fragments of the given lengths, repeating them as needed.
+    fn split<'a>(mut data: &'a [u8], lens: &[usize]) -> Vec<&'a [u8]> {
+        let mut fragments = Vec::new();
+        for len in lens.iter().cycle() {
+            if data.is_empty() {
+                return fragments;
+            }
+            let (fragment, rest) = data.split_at((*len).min(data.len()));
+            fragments.push(fragment);
+            data = rest;
+        }
+        unreachable!()
+    }
+
+    fn split_mut<'a>(mut data: &'a mut [u8], lens: &[usize]) -> Vec<IoSliceMut<'a>> {
+        let mut fragments = Vec::new();
+        for len in lens.iter().cycle() {
+            if data.is_empty() {
+                return fragments;
+            }
+            let (fragment, rest) = data.split_at_mut((*len).min(data.len()));
+            fragments.push(IoSliceMut::new(fragment));
+            data = rest;
+        }
+        unreachable!()
+    }
+
+    fn seal(
+        key: &ScatterGatherKey,
+        plaintext: &[u8],
+        in_lens: &[usize],
+        out_lens: &[usize],
+    ) -> (Vec<u8>, Vec<u8>) {
+        let in_fragments: Vec<IoSlice<'_>> = split(plaintext, in_lens)
+            .into_iter()
+            .map(IoSlice::new)
+            .collect();
+        let mut ciphertext = vec![0u8; plaintext.len()];
+        let mut tag = vec![0u8; key.algorithm().tag_len()];
+        {
+            let mut out_fragments = split_mut(&mut ciphertext, out_lens);
+            key.seal_scatter_gather(
+                Nonce::assume_unique_for_key(NONCE),
+                Aad::from(AAD),
+                &in_fragments,
+                &mut out_fragments,
+                &mut tag,
+            )
+            .unwrap();
+        }
+        (ciphertext, tag)
+    }
+
+    fn open(
+        key: &ScatterGatherKey,
+        ciphertext: &[u8],
+        tag: &[u8],
+        in_lens: &[usize],
+        out_lens: &[usize],
+    ) -> Result<Vec<u8>, Vec<u8>> {
+        let in_fragments: Vec<IoSlice<'_>> = split(ciphertext, in_lens)
+            .into_iter()
+            .map(IoSlice::new)
+            .collect();
+        let mut plaintext = vec![0xffu8; ciphertext.len()];
+        let result = key.open_scatter_gather(
+            Nonce::assume_unique_for_key(NONCE),
+            Aad::from(AAD),
+            &in_fragments,
+            tag,
+            &mut split_mut(&mut plaintext, out_lens),
+        );
+        match result {
+            Ok(()) => Ok(plaintext),
+            Err(_) => Err(plaintext),
+        }
+    }
+
+    #[test]
+    fn scatter_gather_matches_less_safe_key() {
+        let algorithms: [&'static Algorithm; 4] =
+            [&AES_128_GCM, &AES_192_GCM, &AES_256_GCM, &CHACHA20_POLY1305];
+        let fragment_lens: [&[usize]; 4] = [&[1], &[7, 16], &[64, 3, 0, 65], &[1000]];
+        for algorithm in algorithms {
+            let key_bytes = &KEY[..algorithm.key_len()];
+            let key = ScatterGatherKey::new(algorithm, key_bytes).unwrap();
+            let less_safe_key = LessSafeKey::new(UnboundKey::new(algorithm, key_bytes).unwrap());
+            for len in [0usize, 1, 15, 16, 17, 63, 64, 65, 200] {
+                let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+                let mut expected = plaintext.clone();
+                let expected_tag = less_safe_key
+                    .seal_in_place_separate_tag(
+                        Nonce::assume_unique_for_key(NONCE),
+                        Aad::from(AAD),
+                        &mut expected,
+                    )
+                    .unwrap();
+
+                for in_lens in fragment_lens {
+                    for out_lens in fragment_lens {
+                        let (ciphertext, tag) = seal(&key, &plaintext, in_lens, out_lens);
+                        assert_eq!(expected, ciphertext);
+                        assert_eq!(expected_tag.as_ref(), tag.as_slice());
+                        assert_eq!(
+                            plaintext,
+                            open(&key, &ciphertext, &tag, out_lens, in_lens).unwrap()
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn scatter_gather_detects_tampering() {
+        for algorithm in [&AES_128_GCM, &AES_256_GCM, &CHACHA20_POLY1305] {
+            let key = ScatterGatherKey::new(algorithm, &KEY[..algorithm.key_len()]).unwrap();
+            let plaintext = vec![0x5a; 100];
+            let (ciphertext, tag) = seal(&key, &plaintext, &[10, 30], &[50]);
+
+            let mut tampered = ciphertext.clone();
+            tampered[42] ^= 1;
+            let zeros = vec![0u8; 100];
+            assert_eq!(
+                Err(zeros.clone()),
+                open(&key, &tampered, &tag, &[20], &[33])
+            );
+
+            let mut tampered_tag = tag.clone();
+            tampered_tag[0] ^= 1;
+            assert_eq!(
+                Err(zeros),
+                open(&key, &ciphertext, &tampered_tag, &[20], &[33])
+            );
+        }
+    }
+
+    #[test]
+    fn scatter_gather_invalid() {
+        assert!(ScatterGatherKey::new(&AES_256_GCM_SIV, KEY).is_err());
+        assert!(ScatterGatherKey::new(&CHACHA20_POLY1305, &KEY[..16]).is_err());
+        assert!(ScatterGatherKey::new(&XCHACHA20_POLY1305, KEY).is_err());
+        assert!(ScatterGatherKey::new(&AES_128_GCM, KEY).is_err());
+
+        let key = ScatterGatherKey::new(&AES_128_GCM, &KEY[..16]).unwrap();
+        let plaintext = [0u8; 32];
+        let mut output = [0u8; 31];
+        let mut tag = [0u8; 16];
+        assert!(key
+            .seal_scatter_gather(
+                Nonce::assume_unique_for_key(NONCE),
+                Aad::empty(),
+                &[IoSlice::new(&plaintext)],
+                &mut [IoSliceMut::new(&mut output)],
+                &mut tag,
+            )
+            .is_err());
+
+        let mut output = [0u8; 32];
+        let mut short_tag = [0u8; 15];
+        assert!(key
+            .seal_scatter_gather(
+                Nonce::assume_unique_for_key(NONCE),
+                Aad::empty(),
+                &[IoSlice::new(&plaintext)],
+                &mut [IoSliceMut::new(&mut output)],
+                &mut short_tag,
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,402 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! AES-SIV deterministic authenticated encryption, as specified by [RFC 5297].
+//!
+//! AES-SIV derives a synthetic initialization vector (IV) from the key, the associated data
+//! and the plaintext using the S2V construction over AES-CMAC, and encrypts the plaintext
+//! with AES-CTR under that IV. The IV doubles as the authentication tag.
+//!
+//! Without a nonce, AES-SIV is deterministic: sealing the same plaintext with the same
+//! associated data always produces the same ciphertext, which reveals when messages repeat.
+//! This is useful for key wrapping and for encrypted values that must be searchable, such as
+//! database indexes. When that is undesirable, pass a nonce as the last component of the
+//! associated data.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::aead::siv::{Key, AES_SIV_CMAC_256};
+//!
+//! let key_bytes = [0x42u8; 32];
+//! let key = Key::new(&AES_SIV_CMAC_256, &key_bytes)?;
+//!
+//! let associated_data: [&[u8]; 2] = [b"table: users", b"column: email"];
+//!
+//! let mut in_out = Vec::from("alice@example.com");
+//! key.seal_in_place_prepend_tag(&associated_data, &mut in_out)?;
+//!
+//! // Sealing is deterministic.
+//! let mut in_out_2 = Vec::from("alice@example.com");
+//! key.seal_in_place_prepend_tag(&associated_data, &mut in_out_2)?;
+//! assert_eq!(in_out, in_out_2);
+//!
+//! let plaintext = key.open_in_place(&associated_data, &mut in_out)?;
+//! assert_eq!(b"alice@example.com", plaintext);
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! [RFC 5297]: https://www.rfc-editor.org/rfc/rfc5297
+
+use crate::aead::{Tag, MAX_TAG_LEN};
+use crate::cipher::{
+    EncryptingKey, EncryptionContext, UnboundCipherKey, AES_128, AES_192, AES_256,
+};
+use crate::error::Unspecified;
+use crate::hkdf::KeyType;
+use crate::iv::FixedLength;
+use crate::{cmac, constant_time, derive_debug_via_id, hkdf};
+use zeroize::Zeroize;
+
+/// The length of the synthetic IV, which is also the authentication tag.
+pub const TAG_LEN: usize = 16;
+
+/// The maximum number of associated data components, as limited by RFC 5297 Section 7.
+pub const MAX_ASSOCIATED_DATA_COMPONENTS: usize = 126;
+
+const BLOCK_LEN: usize = 16;
+
+const MAX_KEY_LEN: usize = 64;
+
+/// An AES-SIV algorithm.
+pub struct Algorithm {
+    key_len: usize,
+    id: AlgorithmID,
+}
+
+impl Algorithm {
+    /// The length of the key, which is twice the length of the underlying AES key.
+    #[inline]
+    #[must_use]
+    pub fn key_len(&self) -> usize {
+        self.key_len
+    }
+
+    /// The length of the synthetic IV.
+    #[inline]
+    #[must_use]
+    pub fn tag_len(&self) -> usize {
+        TAG_LEN
+    }
+
+    fn cmac_algorithm(&self) -> cmac::Algorithm {
+        match self.id {
+            AlgorithmID::AES_SIV_CMAC_256 => cmac::CMAC_AES_128,
+            AlgorithmID::AES_SIV_CMAC_384 => cmac::CMAC_AES_192,
+            AlgorithmID::AES_SIV_CMAC_512 => cmac::CMAC_AES_256,
+        }
+    }
+
+    fn cipher_algorithm(&self) -> &'static crate::cipher::Algorithm {
+        match self.id {
+            AlgorithmID::AES_SIV_CMAC_256 => &AES_128,
+            AlgorithmID::AES_SIV_CMAC_384 => &AES_192,
+            AlgorithmID::AES_SIV_CMAC_512 => &AES_256,
+        }
+    }
+}
+
+impl KeyType for &'static Algorithm {
+    #[inline]
+    fn len(&self) -> usize {
+        self.key_len()
+    }
+}
+
+derive_debug_via_id!(Algorithm);
+
+#[derive(Debug, Eq, PartialEq)]
+#[allow(non_camel_case_types)]
+enum AlgorithmID {
+    AES_SIV_CMAC_256,
+    AES_SIV_CMAC_384,
+    AES_SIV_CMAC_512,
+}
+
+impl PartialEq for Algorithm {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Algorithm {}
+
+/// AES-SIV using AES-128 for both S2V and CTR, with a 256-bit key.
+pub static AES_SIV_CMAC_256: Algorithm = Algorithm {
+    key_len: 32,
+    id: AlgorithmID::AES_SIV_CMAC_256,
+};
+
+/// AES-SIV using AES-192 for both S2V and CTR, with a 384-bit key.
+pub static AES_SIV_CMAC_384: Algorithm = Algorithm {
+    key_len: 48,
+    id: AlgorithmID::AES_SIV_CMAC_384,
+};
+
+/// AES-SIV using AES-256 for both S2V and CTR, with a 512-bit key.
+pub static AES_SIV_CMAC_512: Algorithm = Algorithm {
+    key_len: 64,
+    id: AlgorithmID::AES_SIV_CMAC_512,
+};
+
+/// An AES-SIV key, used for both sealing and opening.
+pub struct Key {
+    algorithm: &'static Algorithm,
+    mac_key: cmac::Key,
+    ctr_key: EncryptingKey,
+}
+
+impl From<hkdf::Okm<'_, &'static Algorithm>> for Key {
+    fn from(okm: hkdf::Okm<&'static Algorithm>) -> Self {
+        let mut key_bytes = [0; MAX_KEY_LEN];
+        let algorithm = *okm.len();
+        let key_bytes = &mut key_bytes[..algorithm.key_len()];
+        okm.fill(key_bytes).unwrap();
+        let key = Self::new(algorithm, key_bytes).unwrap();
+        key_bytes.zeroize();
+        key
+    }
+}
+
+impl Key {
+    /// Constructs an AES-SIV key. The first half of `key_bytes` is the S2V (AES-CMAC) key and
+    /// the second half is the AES-CTR key, as specified by RFC 5297.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// `error::Unspecified` when `key_bytes` length is not `algorithm.key_len()`.
+    pub fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if key_bytes.len() != algorithm.key_len() {
+            return Err(Unspecified);
+        }
+        let (mac_key_bytes, ctr_key_bytes) = key_bytes.split_at(algorithm.key_len() / 2);
+        let mac_key = cmac::Key::new(algorithm.cmac_algorithm(), mac_key_bytes)?;
+        let ctr_key = EncryptingKey::ctr(UnboundCipherKey::new(
+            algorithm.cipher_algorithm(),
+            ctr_key_bytes,
+        )?)?;
+        Ok(Self {
+            algorithm,
+            mac_key,
+            ctr_key,
+        })
+    }
+
+    /// The key's algorithm.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
+    /// Encrypts and authenticates `in_out` in place, returning the synthetic IV.
+    ///
+    /// `associated_data` is the vector of associated data components. A nonce, if used, is
+    /// passed as the last component.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when there are more than [`MAX_ASSOCIATED_DATA_COMPONENTS`]
+    /// associated data components, or on an internal failure.
+    pub fn seal_in_place_separate_tag(
+        &self,
+        associated_data: &[&[u8]],
+        in_out: &mut [u8],
+    ) -> Result<Tag, Unspecified> {
+        let iv = self.s2v(associated_data, in_out)?;
+        self.ctr(&iv, in_out)?;
+        let mut tag = [0u8; MAX_TAG_LEN];
+        tag[..TAG_LEN].copy_from_slice(&iv);
+        Ok(Tag(tag, TAG_LEN))
+    }
+
+    /// Encrypts and authenticates `in_out` in place, prepending the synthetic IV so that
+    /// `in_out` holds the RFC 5297 ciphertext `V || C`.
+    ///
+    /// `associated_data` is the vector of associated data components. A nonce, if used, is
+    /// passed as the last component.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when there are more than [`MAX_ASSOCIATED_DATA_COMPONENTS`]
+    /// associated data components, or on an internal failure.
+    pub fn seal_in_place_prepend_tag(
+        &self,
+        associated_data: &[&[u8]],
+        in_out: &mut Vec<u8>,
+    ) -> Result<(), Unspecified> {
+        let tag = self.seal_in_place_separate_tag(associated_data, in_out)?;
+        in_out.splice(0..0, tag.as_ref().iter().copied());
+        Ok(())
+    }
+
+    /// Authenticates and decrypts the RFC 5297 ciphertext `V || C` in `in_out`, returning the
+    /// plaintext, which is the portion of `in_out` after the synthetic IV.
+    ///
+    /// When authentication fails the decrypted data is zeroed.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when `in_out` is shorter than [`TAG_LEN`], when there are more
+    /// than [`MAX_ASSOCIATED_DATA_COMPONENTS`] associated data components, or when the
+    /// ciphertext is not authentic.
+    pub fn open_in_place<'in_out>(
+        &self,
+        associated_data: &[&[u8]],
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        if in_out.len() < TAG_LEN {
+            return Err(Unspecified);
+        }
+        let (tag, ciphertext) = in_out.split_at_mut(TAG_LEN);
+        self.open_in_place_separate_tag(associated_data, tag, ciphertext)?;
+        Ok(ciphertext)
+    }
+
+    /// Authenticates and decrypts `in_out` in place using the synthetic IV `tag`, returning
+    /// the plaintext.
+    ///
+    /// When authentication fails the decrypted data is zeroed.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when `tag` is not [`TAG_LEN`] bytes long, when there are more
+    /// than [`MAX_ASSOCIATED_DATA_COMPONENTS`] associated data components, or when the
+    /// ciphertext is not authentic.
+    pub fn open_in_place_separate_tag<'in_out>(
+        &self,
+        associated_data: &[&[u8]],
+        tag: &[u8],
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        let iv = <&[u8; TAG_LEN]>::try_from(tag)?;
+        if associated_data.len() > MAX_ASSOCIATED_DATA_COMPONENTS {
+            return Err(Unspecified);
+        }
+        self.ctr(iv, in_out)?;
+        let expected = self.s2v(associated_data, in_out)?;
+        if constant_time::verify_slices_are_equal(&expected, iv).is_err() {
+            // Don't release unauthenticated plaintext.
+            in_out.zeroize();
+            return Err(Unspecified);
+        }
+        Ok(in_out)
+    }
+
+    /// The S2V construction of RFC 5297 Section 2.4, over the associated data components
+    /// followed by the plaintext.
+    fn s2v(
+        &self,
+        associated_data: &[&[u8]],
+        plaintext: &[u8],
+    ) -> Result<[u8; BLOCK_LEN], Unspecified> {
+        if associated_data.len() > MAX_ASSOCIATED_DATA_COMPONENTS {
+            return Err(Unspecified);
+        }
+        let mut d = self.cmac(&[0u8; BLOCK_LEN]);
+        for component in associated_data {
+            d = dbl(d);
+            xor_in_place(&mut d, &self.cmac(component));
+        }
+
+        let mut ctx = cmac::Context::with_key(&self.mac_key);
+        if plaintext.len() >= BLOCK_LEN {
+            // T = S_n xorend D
+            let (head, last) = plaintext.split_at(plaintext.len() - BLOCK_LEN);
+            ctx.update(head);
+            xor_in_place(&mut d, last);
+        } else {
+            // T = dbl(D) xor pad(S_n)
+            d = dbl(d);
+            xor_in_place(&mut d, plaintext);
+            d[plaintext.len()] ^= 0x80;
+        }
+        ctx.update(&d);
+        d.zeroize();
+
+        let mut v = [0u8; BLOCK_LEN];
+        v.copy_from_slice(ctx.sign().as_ref());
+        Ok(v)
+    }
+
+    fn cmac(&self, data: &[u8]) -> [u8; BLOCK_LEN] {
+        let mut output = [0u8; BLOCK_LEN];
+        output.copy_from_slice(cmac::sign(&self.mac_key, data).as_ref());
+        output
+    }
+
+    /// Applies AES-CTR with the counter derived from the synthetic IV `iv`, whose 31st and 63rd
+    /// bits (counting from the right) are cleared.
+    fn ctr(&self, iv: &[u8; BLOCK_LEN], in_out: &mut [u8]) -> Result<(), Unspecified> {
+        let mut counter = *iv;
+        counter[8] &= 0x7f;
+        counter[12] &= 0x7f;
+        self.ctr_key
+            .less_safe_encrypt(in_out, EncryptionContext::Iv128(FixedLength::from(counter)))?;
+        Ok(())
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl core::fmt::Debug for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Key")
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+/// Multiplication by x in GF(2^128), as specified by RFC 5297 Section 2.3.
+fn dbl(block: [u8; BLOCK_LEN]) -> [u8; BLOCK_LEN] {
+    let value = u128::from_be_bytes(block);
+    let reduction = (value >> 127).wrapping_neg() & 0x87;
+    ((value << 1) ^ reduction).to_be_bytes()
+}
+
+fn xor_in_place(target: &mut [u8], other: &[u8]) {
+    for (t, o) in target.iter_mut().zip(other) {
+        *t ^= o;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dbl, Key, AES_SIV_CMAC_256, AES_SIV_CMAC_384, AES_SIV_CMAC_512};
+    use crate::test::{self, from_hex};
+
+    #[test]
+    fn test_types() {
+        test::compile_time_assert_send::<Key>();
+        test::compile_time_assert_sync::<Key>();
+    }
+
+    #[test]
+    fn dbl_test() {
+        // RFC 5297 Appendix A.1
+        let d = from_hex("0e04dfafc1efbf040140582859bf073a").unwrap();
+        assert_eq!(
+            from_hex("1c09bf5f83df7e080280b050b37e0e74").unwrap(),
+            dbl(d.as_slice().try_into().unwrap())
+        );
+        let d = from_hex("edf09de876c642ee4d78bce4ceedfc4f").unwrap();
+        assert_eq!(
+            from_hex("dbe13bd0ed8c85dc9af179c99ddbf819").unwrap(),
+            dbl(d.as_slice().try_into().unwrap())
+        );
+    }
+
+    #[test]
+    fn key_len_test() {
+        assert!(Key::new(&AES_SIV_CMAC_256, &[0u8; 31]).is_err());
+        assert!(Key::new(&AES_SIV_CMAC_256, &[0u8; 32]).is_ok());
+        assert!(Key::new(&AES_SIV_CMAC_384, &[0u8; 48]).is_ok());
+        assert!(Key::new(&AES_SIV_CMAC_512, &[0u8; 64]).is_ok());
+        assert!(Key::new(&AES_SIV_CMAC_512, &[0u8; 32]).is_err());
+        assert_eq!(
+            "Key { algorithm: AES_SIV_CMAC_384 }",
+            format!("{:?}", Key::new(&AES_SIV_CMAC_384, &[0u8; 48]).unwrap())
+        );
+    }
+}
@@ -0,0 +1,521 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use super::{Aad, Algorithm, AlgorithmID, Nonce, UnboundKey, NONCE_LEN};
+use crate::error::Unspecified;
+use crate::{hkdf, rand};
+use core::fmt::Debug;
+
+/// The length of the random salt from which the key of each stream is derived.
+pub const STREAM_SALT_LEN: usize = 32;
+
+/// The length of the random nonce prefix shared by the segments of a stream.
+pub const STREAM_NONCE_PREFIX_LEN: usize = 7;
+
+/// The length of a stream header: the salt followed by the nonce prefix.
+pub const STREAM_HEADER_LEN: usize = STREAM_SALT_LEN + STREAM_NONCE_PREFIX_LEN;
+
+// The nonce of each segment is the nonce prefix, followed by the segment number as a 32-bit
+// big-endian integer, followed by a byte that is 1 for the last segment and 0 otherwise.
+const COUNTER_OFFSET: usize = STREAM_NONCE_PREFIX_LEN;
+const LAST_SEGMENT_FLAG_OFFSET: usize = NONCE_LEN - 1;
+
+fn check_algorithm(algorithm: &'static Algorithm, segment_len: usize) -> Result<(), Unspecified> {
+    match algorithm.id {
+        AlgorithmID::AES_128_GCM
+        | AlgorithmID::AES_192_GCM
+        | AlgorithmID::AES_256_GCM
+        | AlgorithmID::CHACHA20_POLY1305 => {}
+        AlgorithmID::AES_128_GCM_SIV
+        | AlgorithmID::AES_256_GCM_SIV
+        | AlgorithmID::XCHACHA20_POLY1305
+        | AlgorithmID::AES_128_CCM { .. } => return Err(Unspecified),
+    }
+    if segment_len == 0 {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+// Each stream is sealed with its own key, derived from the caller's key and the random salt in
+// the stream header using HKDF-SHA256, as in Tink's streaming AEAD. A random 7-byte nonce prefix
+// alone would be expected to repeat after about 2^28 streams under the same key.
+fn derive_stream_key(
+    algorithm: &'static Algorithm,
+    key_bytes: &[u8],
+    header: &[u8; STREAM_HEADER_LEN],
+    segment_len: usize,
+) -> Result<(UnboundKey, SegmentNonces), Unspecified> {
+    check_algorithm(algorithm, segment_len)?;
+    if key_bytes.len() != algorithm.key_len() {
+        return Err(Unspecified);
+    }
+    let (salt, nonce_prefix) = header.split_at(STREAM_SALT_LEN);
+    let prk = hkdf::Salt::new(hkdf::HKDF_SHA256, salt).extract(key_bytes);
+    let okm = prk.expand(&[], algorithm)?;
+    let mut nonces = SegmentNonces {
+        nonce_prefix: [0u8; STREAM_NONCE_PREFIX_LEN],
+        segment_number: 0,
+    };
+    nonces.nonce_prefix.copy_from_slice(nonce_prefix);
+    Ok((UnboundKey::from(okm), nonces))
+}
+
+/// Tracks the nonce prefix and position within a stream.
+struct SegmentNonces {
+    nonce_prefix: [u8; STREAM_NONCE_PREFIX_LEN],
+    segment_number: u32,
+}
+
+impl SegmentNonces {
+    // Returns the nonce for the current segment and advances to the next segment. A stream cannot
+    // continue past `u32::MAX` segments, but its last segment may have that number.
+    fn advance(&mut self, last_segment: bool) -> Result<Nonce, Unspecified> {
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[..COUNTER_OFFSET].copy_from_slice(&self.nonce_prefix);
+        nonce[COUNTER_OFFSET..LAST_SEGMENT_FLAG_OFFSET]
+            .copy_from_slice(&self.segment_number.to_be_bytes());
+        nonce[LAST_SEGMENT_FLAG_OFFSET] = u8::from(last_segment);
+        if !last_segment {
+            self.segment_number = self.segment_number.checked_add(1).ok_or(Unspecified)?;
+        }
+        Ok(Nonce::assume_unique_for_key(nonce))
+    }
+}
+
+/// An AEAD key for sealing a stream of data in fixed-size segments.
+///
+/// Each stream is sealed with a key derived from the given key and a random salt, so a key may
+/// be used for any number of streams. The plaintext is split into segments of `segment_len`
+/// bytes, except for the last segment, which may be shorter (or empty). Each segment is sealed
+/// with a nonce that encodes a random per-stream prefix, the position of the segment in the stream, and whether it is the last
+/// segment, following the STREAM construction of Hoang, Reyhanitabar, Rogaway and Vizár. This
+/// allows the opening side to detect segments that have been reordered, dropped, or truncated
+/// from the end of the stream.
+///
+/// The salt and nonce prefix, returned by [`Self::header`], must be transmitted with the stream
+/// so that it can be opened with a [`StreamOpeningKey`].
+///
+/// The following algorithms are supported:
+/// * `AES_128_GCM`
+/// * `AES_192_GCM`
+/// * `AES_256_GCM`
+/// * `CHACHA20_POLY1305`
+pub struct StreamSealingKey {
+    key: UnboundKey,
+    nonces: SegmentNonces,
+    header: [u8; STREAM_HEADER_LEN],
+    segment_len: usize,
+}
+
+impl StreamSealingKey {
+    /// Constructs a key for sealing a new stream with a randomly generated header.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the algorithm is not supported, the length of `key_bytes` does not
+    /// match the algorithm, `segment_len` is zero, or the header cannot be generated.
+    pub fn new(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+        segment_len: usize,
+    ) -> Result<Self, Unspecified> {
+        let mut header = [0u8; STREAM_HEADER_LEN];
+        rand::fill(&mut header)?;
+        Self::less_safe_new(algorithm, key_bytes, header, segment_len)
+    }
+
+    /// Constructs a key for sealing a new stream with the given header.
+    ///
+    /// This is considered less safe because the caller could reuse a header for different
+    /// streams under the same key, which reuses nonces.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the algorithm is not supported, the length of `key_bytes` does not
+    /// match the algorithm, or `segment_len` is zero.
+    pub fn less_safe_new(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+        header: [u8; STREAM_HEADER_LEN],
+        segment_len: usize,
+    ) -> Result<Self, Unspecified> {
+        let (key, nonces) = derive_stream_key(algorithm, key_bytes, &header, segment_len)?;
+        Ok(Self {
+            key,
+            nonces,
+            header,
+            segment_len,
+        })
+    }
+
+    /// Seals the next segment in place, appending the tag.
+    ///
+    /// `in_out` must be exactly `segment_len` bytes of plaintext. The last segment of the stream
+    /// must instead be sealed with [`Self::seal_last_segment`].
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `in_out` is not `segment_len` bytes long, if the stream has reached
+    /// the maximum number of segments, or if sealing fails.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn seal_segment<A, InOut>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &mut InOut,
+    ) -> Result<(), Unspecified>
+    where
+        A: AsRef<[u8]>,
+        InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
+    {
+        if in_out.as_mut().len() != self.segment_len {
+            return Err(Unspecified);
+        }
+        let nonce = self.nonces.advance(false)?;
+        self.key
+            .seal_in_place_append_tag(Some(nonce.into()), aad.as_ref(), in_out)
+            .map(|_| ())
+    }
+
+    /// Seals the last segment of the stream in place, appending the tag.
+    ///
+    /// `in_out` may be up to `segment_len` bytes of plaintext, and may be empty.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `in_out` is longer than `segment_len` bytes, or if sealing fails.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn seal_last_segment<A, InOut>(
+        mut self,
+        aad: Aad<A>,
+        in_out: &mut InOut,
+    ) -> Result<(), Unspecified>
+    where
+        A: AsRef<[u8]>,
+        InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
+    {
+        if in_out.as_mut().len() > self.segment_len {
+            return Err(Unspecified);
+        }
+        let nonce = self.nonces.advance(true)?;
+        self.key
+            .seal_in_place_append_tag(Some(nonce.into()), aad.as_ref(), in_out)
+            .map(|_| ())
+    }
+
+    /// The header of the stream, which is needed to open it.
+    #[inline]
+    #[must_use]
+    pub fn header(&self) -> &[u8; STREAM_HEADER_LEN] {
+        &self.header
+    }
+
+    /// The length of each plaintext segment, other than the last.
+    #[inline]
+    #[must_use]
+    pub fn segment_len(&self) -> usize {
+        self.segment_len
+    }
+
+    /// The key's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.key.algorithm()
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl Debug for StreamSealingKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StreamSealingKey")
+            .field("algorithm", &self.algorithm())
+            .field("segment_len", &self.segment_len)
+            .finish()
+    }
+}
+
+/// An AEAD key for opening a stream sealed by a [`StreamSealingKey`].
+///
+/// Each sealed segment, other than the last, is `segment_len` bytes of ciphertext followed by
+/// the tag. Segments must be opened in order, and the last segment must be opened with
+/// [`Self::open_last_segment`]; a stream that has been truncated at a segment boundary is
+/// detected because its final segment was not sealed as the last one. Since the last segment
+/// may be a full segment, the caller must read ahead to determine whether a segment is the last.
+pub struct StreamOpeningKey {
+    key: UnboundKey,
+    nonces: SegmentNonces,
+    segment_len: usize,
+}
+
+impl StreamOpeningKey {
+    /// Constructs a key for opening the stream that was sealed with `header`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the algorithm is not supported, the length of `key_bytes` does not
+    /// match the algorithm, or `segment_len` is zero.
+    pub fn new(
+        algorithm: &'static Algorithm,
+        key_bytes: &[u8],
+        header: &[u8; STREAM_HEADER_LEN],
+        segment_len: usize,
+    ) -> Result<Self, Unspecified> {
+        let (key, nonces) = derive_stream_key(algorithm, key_bytes, header, segment_len)?;
+        Ok(Self {
+            key,
+            nonces,
+            segment_len,
+        })
+    }
+
+    /// Authenticates and decrypts the next segment in place.
+    ///
+    /// `in_out` must be the `segment_len` bytes of ciphertext followed by the tag. On success,
+    /// returns the plaintext, which is at the start of `in_out`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `in_out` has the wrong length or the segment is not authentic,
+    /// including when it is out of order or was sealed as the last segment. The position in the
+    /// stream does not advance on failure.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn open_segment<'in_out, A>(
+        &mut self,
+        aad: Aad<A>,
+        in_out: &'in_out mut [u8],
+    ) -> Result<&'in_out mut [u8], Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        if in_out.len() != self.ciphertext_segment_len() {
+            return Err(Unspecified);
+        }
+        let segment_number = self.nonces.segment_number;
+        let nonce = self.nonces.advance(false)?;
+        self.key
+            .open_within(nonce.into(), aad.as_ref(), in_out, 0..)
+            .map_err(|e| {
+                self.nonces.segment_number = segment_number;
+                e
+            })
+    }
+
+    /// Authenticates and decrypts the last segment of the stream in place.
+    ///
+    /// `in_out` must be up to `segment_len` bytes of ciphertext followed by the tag. On success,
+    /// returns the plaintext, which is at the start of `in_out`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `in_out` has an invalid length or the segment is not authentic,
+    /// including when it was not sealed as the last segment, i.e. the stream was truncated.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn open_last_segment<A>(
+        mut self,
+        aad: Aad<A>,
+        in_out: &mut [u8],
+    ) -> Result<&mut [u8], Unspecified>
+    where
+        A: AsRef<[u8]>,
+    {
+        if in_out.len() > self.ciphertext_segment_len() {
+            return Err(Unspecified);
+        }
+        let nonce = self.nonces.advance(true)?;
+        self.key
+            .open_within(nonce.into(), aad.as_ref(), in_out, 0..)
+    }
+
+    /// The length of each plaintext segment, other than the last.
+    #[inline]
+    #[must_use]
+    pub fn segment_len(&self) -> usize {
+        self.segment_len
+    }
+
+    /// The length of each sealed segment, other than the last: `segment_len` plus the tag length.
+    #[inline]
+    #[must_use]
+    pub fn ciphertext_segment_len(&self) -> usize {
+        self.segment_len.saturating_add(self.algorithm().tag_len())
+    }
+
+    /// The key's AEAD algorithm.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.key.algorithm()
+    }
+}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl Debug for StreamOpeningKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("StreamOpeningKey")
+            .field("algorithm", &self.algorithm())
+            .field("segment_len", &self.segment_len)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StreamOpeningKey, StreamSealingKey, STREAM_HEADER_LEN};
+    use crate::aead::{
+        Aad, Algorithm, AES_128_GCM, AES_256_GCM, AES_256_GCM_SIV, CHACHA20_POLY1305,
+        XCHACHA20_POLY1305,
+    };
+    use crate::test::from_hex;
+
+    const KEY: &[u8] = &[
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d,
+        0x1e, 0x1f,
+    ];
+
+    // A salt of 0x40..0x60 followed by a nonce prefix of 0xa0..0xa7.
+    const HEADER: [u8; STREAM_HEADER_LEN] = [
+        0x40, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0x4a, 0x4b, 0x4c, 0x4d, 0x4e,
+        0x4f, 0x50, 0x51, 0x52, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x5b, 0x5c, 0x5d,
+        0x5e, 0x5f, 0xa0, 0xa1, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6,
+    ];
+
+    fn seal_stream(key: StreamSealingKey, plaintext: &[u8]) -> Vec<Vec<u8>> {
+        let mut key = key;
+        let mut segments: Vec<Vec<u8>> = Vec::new();
+        let mut chunks = plaintext.chunks(key.segment_len()).peekable();
+        loop {
+            let mut segment = chunks.next().map(Vec::from).unwrap_or_default();
+            if chunks.peek().is_none() {
+                key.seal_last_segment(Aad::empty(), &mut segment).unwrap();
+                segments.push(segment);
+                return segments;
+            }
+            key.seal_segment(Aad::empty(), &mut segment).unwrap();
+            segments.push(segment);
+        }
+    }
+
+    fn open_stream(key: StreamOpeningKey, segments: &[Vec<u8>]) -> Result<Vec<u8>, ()> {
+        let mut key = key;
+        let mut plaintext = Vec::new();
+        let (last, rest) = segments.split_last().unwrap();
+        for segment in rest {
+            let mut segment = segment.clone();
+            plaintext.extend_from_slice(
+                key.open_segment(Aad::empty(), &mut segment)
+                    .map_err(|_| ())?,
+            );
+        }
+        let mut last = last.clone();
+        plaintext.extend_from_slice(
+            key.open_last_segment(Aad::empty(), &mut last)
+                .map_err(|_| ())?,
+        );
+        Ok(plaintext)
+    }
+
+    // The expected ciphertext was computed independently with pyca/cryptography: the stream key
+    // is HKDF-SHA256 of the key with the salt and empty info (c22ca504ad8b1881693c4d50ca8e1388),
+    // and each segment is sealed with AES-128-GCM under that key.
+    #[test]
+    fn stream_known_answer() {
+        let plaintext: Vec<u8> = (0u8..40).collect();
+        let key = StreamSealingKey::less_safe_new(&AES_128_GCM, &KEY[..16], HEADER, 16).unwrap();
+        let segments = seal_stream(key, &plaintext);
+        assert_eq!(
+            from_hex(
+                "c1b4ea2800dcca950f3dc105994c3a10ab82aa62333529f5172bf2ed66b90909\
+                 17273f330c5e2b1e3013a0ef9339ed537c11607b4d1a4d723728d2171d221ada\
+                 fa6cad9671f71f8b649279ca0c04448d4d81743d7c123f06"
+            )
+            .unwrap(),
+            segments.concat()
+        );
+
+        let key = StreamOpeningKey::new(&AES_128_GCM, &KEY[..16], &HEADER, 16).unwrap();
+        assert_eq!(plaintext, open_stream(key, &segments).unwrap());
+    }
+
+    #[test]
+    fn stream_round_trip() {
+        let algorithms: [&'static Algorithm; 2] = [&AES_256_GCM, &CHACHA20_POLY1305];
+        for algorithm in algorithms {
+            for len in [0usize, 1, 63, 64, 65, 128, 200] {
+                let plaintext = vec![0x5a; len];
+                let key = StreamSealingKey::new(algorithm, KEY, 64).unwrap();
+                let header = *key.header();
+                let segments = seal_stream(key, &plaintext);
+                assert_eq!(len / 64 + 1, segments.len());
+
+                let key = StreamOpeningKey::new(algorithm, KEY, &header, 64).unwrap();
+                assert_eq!(plaintext, open_stream(key, &segments).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn stream_detects_tampering() {
+        let plaintext = vec![0x5a; 200];
+        let key = StreamSealingKey::less_safe_new(&AES_256_GCM, KEY, HEADER, 64).unwrap();
+        let segments = seal_stream(key, &plaintext);
+        assert_eq!(4, segments.len());
+        let opening_key = || StreamOpeningKey::new(&AES_256_GCM, KEY, &HEADER, 64).unwrap();
+
+        // Truncation at a segment boundary.
+        assert!(open_stream(opening_key(), &segments[..3]).is_err());
+        // Reordering.
+        let mut reordered = segments.clone();
+        reordered.swap(0, 1);
+        assert!(open_stream(opening_key(), &reordered).is_err());
+        // Dropping a segment.
+        let dropped = [&segments[..1], &segments[2..]].concat();
+        assert!(open_stream(opening_key(), &dropped).is_err());
+        // Extension with a segment from another position.
+        let extended = [&segments[..], &segments[3..]].concat();
+        assert!(open_stream(opening_key(), &extended).is_err());
+        // A different salt or nonce prefix.
+        for i in [0, STREAM_HEADER_LEN - 1] {
+            let mut header = HEADER;
+            header[i] ^= 1;
+            let key = StreamOpeningKey::new(&AES_256_GCM, KEY, &header, 64).unwrap();
+            assert!(open_stream(key, &segments).is_err());
+        }
+
+        // A failed segment does not advance the position in the stream.
+        let mut key = opening_key();
+        let mut segment = segments[1].clone();
+        assert!(key.open_segment(Aad::empty(), &mut segment).is_err());
+        let mut segment = segments[0].clone();
+        assert!(key.open_segment(Aad::empty(), &mut segment).is_ok());
+    }
+
+    #[test]
+    fn stream_invalid_parameters() {
+        assert!(StreamSealingKey::new(&AES_256_GCM_SIV, KEY, 64).is_err());
+        assert!(StreamSealingKey::new(&XCHACHA20_POLY1305, KEY, 64).is_err());
+        assert!(StreamSealingKey::new(&AES_256_GCM, KEY, 0).is_err());
+        assert!(StreamSealingKey::new(&AES_256_GCM, &KEY[..16], 64).is_err());
+        assert!(StreamOpeningKey::new(&AES_256_GCM_SIV, KEY, &HEADER, 64).is_err());
+
+        let mut key = StreamSealingKey::new(&AES_256_GCM, KEY, 64).unwrap();
+        assert!(key.seal_segment(Aad::empty(), &mut vec![0u8; 63]).is_err());
+        assert!(key.seal_segment(Aad::empty(), &mut vec![0u8; 65]).is_err());
+        assert!(key
+            .seal_last_segment(Aad::empty(), &mut vec![0u8; 65])
+            .is_err());
+
+        let mut key = StreamOpeningKey::new(&AES_256_GCM, KEY, &HEADER, 64).unwrap();
+        assert_eq!(80, key.ciphertext_segment_len());
+        assert!(key.open_segment(Aad::empty(), &mut [0u8; 79]).is_err());
+        assert!(key.open_last_segment(Aad::empty(), &mut [0u8; 81]).is_err());
+    }
+
+    #[test]
+    fn stream_debug() {
+        let key = StreamSealingKey::new(&AES_256_GCM, KEY, 64).unwrap();
+        assert_eq!(
+            "StreamSealingKey { algorithm: AES_256_GCM, segment_len: 64 }",
+            format!("{key:?}")
+        );
+        let key = StreamOpeningKey::new(&AES_256_GCM, KEY, &HEADER, 64).unwrap();
+        assert_eq!(
+            "StreamOpeningKey { algorithm: AES_256_GCM, segment_len: 64 }",
+            format!("{key:?}")
+        );
+    }
+}
@@ -74,9 +74,12 @@ impl TlsRecordSealingKey {
                 aead_ctx::AeadDirection::Seal,
             ),
             (
-                AlgorithmID::AES_128_GCM_SIV
+                AlgorithmID::AES_192_GCM
+                | AlgorithmID::AES_128_GCM_SIV
                 | AlgorithmID::AES_256_GCM_SIV
-                | AlgorithmID::CHACHA20_POLY1305,
+                | AlgorithmID::CHACHA20_POLY1305
+                | AlgorithmID::XCHACHA20_POLY1305
+                | AlgorithmID::AES_128_CCM { .. },
                 _,
             ) => Err(Unspecified),
         }?;
@@ -107,7 +110,7 @@ impl TlsRecordSealingKey {
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
         self.key
-            .seal_in_place_append_tag(Some(nonce), aad.as_ref(), in_out)
+            .seal_in_place_append_tag(Some(nonce.into()), aad.as_ref(), in_out)
             .map(|_| ())
     }
 
@@ -139,10 +142,41 @@ impl TlsRecordSealingKey {
         A: AsRef<[u8]>,
     {
         self.key
-            .seal_in_place_separate_tag(Some(nonce), aad.as_ref(), in_out)
+            .seal_in_place_separate_tag(Some(nonce.into()), aad.as_ref(), in_out)
             .map(|(_, tag)| tag)
     }
 
+    /// Encrypts and signs (“seals”) a batch of TLS records in place.
+    ///
+    /// Each item is the `nonce`, `aad` and `in_out` of a single
+    /// [`Self::seal_in_place_separate_tag`] operation, and the records are sealed in order. The
+    /// nonces must be unique and increasing across the batch, otherwise an error is returned
+    /// for the offending record. The other records are unaffected.
+    ///
+    /// The key's `EVP_AEAD_CTX` is looked up once for the whole batch, but each record is still
+    /// sealed by its own call into AWS-LC.
+    ///
+    /// Returns the tag, or an error, for each record, in order.
+    #[inline]
+    pub fn seal_in_place_separate_tag_batch<'in_out, A, I>(
+        &mut self,
+        items: I,
+    ) -> Vec<Result<Tag, Unspecified>>
+    where
+        A: AsRef<[u8]>,
+        I: IntoIterator<Item = (Nonce, Aad<A>, &'in_out mut [u8])>,
+    {
+        self.key
+            .seal_in_place_separate_tag_batch(
+                items
+                    .into_iter()
+                    .map(|(nonce, aad, in_out)| (Some(nonce.into()), aad, in_out)),
+            )
+            .into_iter()
+            .map(|result| result.map(|(_, tag)| tag))
+            .collect()
+    }
+
     /// The key's AEAD algorithm.
     #[inline]
     #[must_use]
@@ -219,9 +253,12 @@ impl TlsRecordOpeningKey {
                 aead_ctx::AeadDirection::Open,
             ),
             (
-                AlgorithmID::AES_128_GCM_SIV
+                AlgorithmID::AES_192_GCM
+                | AlgorithmID::AES_128_GCM_SIV
                 | AlgorithmID::AES_256_GCM_SIV
-                | AlgorithmID::CHACHA20_POLY1305,
+                | AlgorithmID::CHACHA20_POLY1305
+                | AlgorithmID::XCHACHA20_POLY1305
+                | AlgorithmID::AES_128_CCM { .. },
                 _,
             ) => Err(Unspecified),
         }?;
@@ -246,7 +283,8 @@ impl TlsRecordOpeningKey {
     where
         A: AsRef<[u8]>,
     {
-        self.key.open_within(nonce, aad.as_ref(), in_out, 0..)
+        self.key
+            .open_within(nonce.into(), aad.as_ref(), in_out, 0..)
     }
 
     /// See [`super::OpeningKey::open_within()`] for details.
@@ -266,7 +304,7 @@ impl TlsRecordOpeningKey {
         A: AsRef<[u8]>,
     {
         self.key
-            .open_within(nonce, aad.as_ref(), in_out, ciphertext_and_tag)
+            .open_within(nonce.into(), aad.as_ref(), in_out, ciphertext_and_tag)
     }
 
     /// The key's AEAD algorithm.
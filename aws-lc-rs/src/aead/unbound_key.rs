@@ -1,28 +1,47 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-use super::{aead_ctx::AeadCtx, Algorithm, Nonce, MAX_KEY_LEN, MAX_TAG_LEN, NONCE_LEN};
-use super::{Tag, AES_128_GCM, AES_128_GCM_SIV, AES_256_GCM, AES_256_GCM_SIV, CHACHA20_POLY1305};
-use crate::iv::FixedLength;
-use crate::{error::Unspecified, fips::indicator_check, hkdf};
+#[cfg(not(feature = "fips"))]
+use super::aes_ccm::AesCcmKey;
+use super::{
+    aead_ctx::AeadCtx, Algorithm, AlgorithmID, ExtendedNonce, MAX_KEY_LEN, MAX_TAG_LEN, NONCE_LEN,
+};
+use super::{
+    Tag, AES_128_CCM_BLUETOOTH, AES_128_CCM_BLUETOOTH_8, AES_128_CCM_MATTER, AES_128_GCM,
+    AES_128_GCM_SIV, AES_192_GCM, AES_256_GCM, AES_256_GCM_SIV, CHACHA20_POLY1305,
+    XCHACHA20_POLY1305,
+};
+use crate::{error::Unspecified, fips::indicator_check, hkdf, ptr::LcPtr};
 use aws_lc::{
     EVP_AEAD_CTX_open, EVP_AEAD_CTX_open_gather, EVP_AEAD_CTX_seal, EVP_AEAD_CTX_seal_scatter,
+    EVP_AEAD_CTX,
 };
 use core::fmt::Debug;
 use core::{mem::MaybeUninit, ops::RangeFrom, ptr::null};
+use zeroize::Zeroizing;
 
-/// The maximum length of a nonce returned by our AEAD API.
-const MAX_NONCE_LEN: usize = NONCE_LEN;
+/// The maximum length of a nonce generated by AWS-LC.
+const MAX_RANDNONCE_LEN: usize = NONCE_LEN;
 
 /// The maximum required tag buffer needed if using AWS-LC generated nonce construction
-const MAX_TAG_NONCE_BUFFER_LEN: usize = MAX_TAG_LEN + MAX_NONCE_LEN;
+const MAX_TAG_NONCE_BUFFER_LEN: usize = MAX_TAG_LEN + MAX_RANDNONCE_LEN;
 
 /// An AEAD key without a designated role or nonce sequence.
 pub struct UnboundKey {
-    ctx: AeadCtx,
+    ctx: KeyCtx,
     algorithm: &'static Algorithm,
 }
 
+/// The keyed state behind an `UnboundKey`.
+///
+/// AWS-LC only offers `EVP_AEAD` implementations of AES-CCM for 13 byte nonces, so the other
+/// nonce lengths are implemented by `AesCcmKey` on the CCM `EVP_CIPHER` instead.
+pub(crate) enum KeyCtx {
+    Aead(AeadCtx),
+    #[cfg(not(feature = "fips"))]
+    AesCcm(AesCcmKey),
+}
+
 #[allow(clippy::missing_fields_in_debug)]
 impl Debug for UnboundKey {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
@@ -46,7 +65,7 @@ impl UnboundKey {
     #[inline]
     pub(crate) fn open_within<'in_out>(
         &self,
-        nonce: Nonce,
+        nonce: ExtendedNonce,
         aad: &[u8],
         in_out: &'in_out mut [u8],
         ciphertext_and_tag: RangeFrom<usize>,
@@ -57,9 +76,10 @@ impl UnboundKey {
             .checked_sub(self.algorithm().tag_len())
             .ok_or(Unspecified)?;
         self.check_per_nonce_max_bytes(ciphertext_len)?;
+        self.check_nonce_len(&nonce)?;
 
         match self.ctx {
-            AeadCtx::AES_128_GCM_RANDNONCE(_) | AeadCtx::AES_256_GCM_RANDNONCE(_) => {
+            KeyCtx::Aead(AeadCtx::AES_128_GCM_RANDNONCE(_) | AeadCtx::AES_256_GCM_RANDNONCE(_)) => {
                 self.open_combined_randnonce(nonce, aad, &mut in_out[in_prefix_len..])
             }
             _ => self.open_combined(nonce, aad.as_ref(), &mut in_out[in_prefix_len..]),
@@ -75,13 +95,14 @@ impl UnboundKey {
     #[inline]
     pub(crate) fn open_separate_gather(
         &self,
-        nonce: &Nonce,
+        nonce: &ExtendedNonce,
         aad: &[u8],
         in_ciphertext: &[u8],
         in_tag: &[u8],
         out_plaintext: &mut [u8],
     ) -> Result<(), Unspecified> {
         self.check_per_nonce_max_bytes(in_ciphertext.len())?;
+        self.check_nonce_len(nonce)?;
 
         // ensure that the lengths match
         {
@@ -93,8 +114,14 @@ impl UnboundKey {
             }
         }
 
+        #[cfg(not(feature = "fips"))]
+        if let KeyCtx::AesCcm(key) = &self.ctx {
+            out_plaintext.copy_from_slice(in_ciphertext);
+            return key.open_in_place(nonce.as_ref(), aad, out_plaintext, in_tag);
+        }
+
         unsafe {
-            let aead_ctx = self.ctx.as_ref();
+            let aead_ctx = self.aead_ctx()?;
             let nonce = nonce.as_ref();
 
             if 1 != EVP_AEAD_CTX_open_gather(
@@ -118,16 +145,19 @@ impl UnboundKey {
     #[inline]
     pub(crate) fn seal_in_place_append_tag<'a, InOut>(
         &self,
-        nonce: Option<Nonce>,
+        nonce: Option<ExtendedNonce>,
         aad: &[u8],
         in_out: &'a mut InOut,
-    ) -> Result<Nonce, Unspecified>
+    ) -> Result<ExtendedNonce, Unspecified>
     where
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
         self.check_per_nonce_max_bytes(in_out.as_mut().len())?;
         match nonce {
-            Some(nonce) => self.seal_combined(nonce, aad, in_out),
+            Some(nonce) => {
+                self.check_nonce_len(&nonce)?;
+                self.seal_combined(nonce, aad, in_out)
+            }
             None => self.seal_combined_randnonce(aad, in_out),
         }
     }
@@ -135,28 +165,79 @@ impl UnboundKey {
     #[inline]
     pub(crate) fn seal_in_place_separate_tag(
         &self,
-        nonce: Option<Nonce>,
+        nonce: Option<ExtendedNonce>,
         aad: &[u8],
         in_out: &mut [u8],
-    ) -> Result<(Nonce, Tag), Unspecified> {
+    ) -> Result<(ExtendedNonce, Tag), Unspecified> {
         self.check_per_nonce_max_bytes(in_out.len())?;
         match nonce {
-            Some(nonce) => self.seal_separate(nonce, aad, in_out),
-            None => self.seal_separate_randnonce(aad, in_out),
+            Some(nonce) => {
+                self.check_nonce_len(&nonce)?;
+                self.seal_separate(nonce, aad, in_out)
+            }
+            None => self.seal_separate_aead(self.aead_ctx()?, None, aad, in_out),
         }
     }
 
+    // The key's context is resolved once for the whole batch, and the messages are then sealed
+    // one after another with it. The AES-CCM keys don't have an `EVP_AEAD_CTX`, so they seal
+    // each message on their own.
+    pub(crate) fn seal_in_place_separate_tag_batch<'in_out, A, I>(
+        &self,
+        items: I,
+    ) -> Vec<Result<(ExtendedNonce, Tag), Unspecified>>
+    where
+        A: AsRef<[u8]>,
+        I: IntoIterator<Item = (Option<ExtendedNonce>, A, &'in_out mut [u8])>,
+    {
+        let items = items.into_iter();
+        let aead_ctx = match self.aead_ctx() {
+            Ok(aead_ctx) => aead_ctx,
+            Err(_) => {
+                return items
+                    .map(|(nonce, aad, in_out)| {
+                        self.seal_in_place_separate_tag(nonce, aad.as_ref(), in_out)
+                    })
+                    .collect()
+            }
+        };
+        items
+            .map(|(nonce, aad, in_out)| {
+                self.check_per_nonce_max_bytes(in_out.len())?;
+                if let Some(nonce) = &nonce {
+                    self.check_nonce_len(nonce)?;
+                }
+                self.seal_separate_aead(aead_ctx, nonce, aad.as_ref(), in_out)
+            })
+            .collect()
+    }
+
+    pub(crate) fn open_in_place_batch<'in_out, A, I>(
+        &self,
+        items: I,
+    ) -> Vec<Result<&'in_out mut [u8], Unspecified>>
+    where
+        A: AsRef<[u8]>,
+        I: IntoIterator<Item = (ExtendedNonce, A, &'in_out mut [u8])>,
+    {
+        items
+            .into_iter()
+            .map(|(nonce, aad, in_out)| self.open_within(nonce, aad.as_ref(), in_out, 0..))
+            .collect()
+    }
+
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
     pub(crate) fn seal_in_place_separate_scatter(
         &self,
-        nonce: Nonce,
+        nonce: ExtendedNonce,
         aad: &[u8],
         in_out: &mut [u8],
         extra_in: &[u8],
         extra_out_and_tag: &mut [u8],
     ) -> Result<(), Unspecified> {
         self.check_per_nonce_max_bytes(in_out.len())?;
+        self.check_nonce_len(&nonce)?;
         // ensure that the extra lengths match
         {
             let actual = extra_in.len() + self.algorithm().tag_len();
@@ -167,12 +248,22 @@ impl UnboundKey {
             }
         }
 
+        if let AlgorithmID::AES_128_CCM { .. } = self.algorithm().id {
+            return self.seal_separate_scatter_combined(
+                nonce,
+                aad,
+                in_out,
+                extra_in,
+                extra_out_and_tag,
+            );
+        }
+
         let nonce = nonce.as_ref();
         let mut out_tag_len = extra_out_and_tag.len();
 
         if 1 != unsafe {
             EVP_AEAD_CTX_seal_scatter(
-                *self.ctx.as_ref().as_const(),
+                *self.aead_ctx()?.as_const(),
                 in_out.as_mut_ptr(),
                 extra_out_and_tag.as_mut_ptr(),
                 &mut out_tag_len,
@@ -207,11 +298,30 @@ impl UnboundKey {
         Ok(())
     }
 
+    // The AES-CCM keys are dispatched to `AesCcmKey` before an `EVP_AEAD_CTX` is required, and
+    // never use the nonces generated by AWS-LC.
+    #[inline]
+    fn aead_ctx(&self) -> Result<&LcPtr<EVP_AEAD_CTX>, Unspecified> {
+        match &self.ctx {
+            KeyCtx::Aead(ctx) => Ok(ctx.as_ref()),
+            #[cfg(not(feature = "fips"))]
+            KeyCtx::AesCcm(_) => Err(Unspecified),
+        }
+    }
+
+    #[inline]
+    fn check_nonce_len(&self, nonce: &ExtendedNonce) -> Result<(), Unspecified> {
+        if nonce.as_ref().len() != self.algorithm().nonce_len() {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
     #[inline]
     #[allow(clippy::needless_pass_by_value)]
     fn open_combined(
         &self,
-        nonce: Nonce,
+        nonce: ExtendedNonce,
         aad: &[u8],
         in_out: &mut [u8],
     ) -> Result<(), Unspecified> {
@@ -221,10 +331,16 @@ impl UnboundKey {
 
         let plaintext_len = in_out.len() - self.algorithm().tag_len();
 
+        #[cfg(not(feature = "fips"))]
+        if let KeyCtx::AesCcm(key) = &self.ctx {
+            let (ciphertext, tag) = in_out.split_at_mut(plaintext_len);
+            return key.open_in_place(nonce, aad, ciphertext, tag);
+        }
+
         let mut out_len = MaybeUninit::<usize>::uninit();
         if 1 != indicator_check!(unsafe {
             EVP_AEAD_CTX_open(
-                *self.ctx.as_ref().as_const(),
+                *self.aead_ctx()?.as_const(),
                 in_out.as_mut_ptr(),
                 out_len.as_mut_ptr(),
                 plaintext_len,
@@ -246,7 +362,7 @@ impl UnboundKey {
     #[allow(clippy::needless_pass_by_value)]
     fn open_combined_randnonce(
         &self,
-        nonce: Nonce,
+        nonce: ExtendedNonce,
         aad: &[u8],
         in_out: &mut [u8],
     ) -> Result<(), Unspecified> {
@@ -270,7 +386,7 @@ impl UnboundKey {
 
         if 1 != indicator_check!(unsafe {
             EVP_AEAD_CTX_open_gather(
-                *self.ctx.as_ref().as_const(),
+                *self.aead_ctx()?.as_const(),
                 in_out.as_mut_ptr(),
                 null(),
                 0,
@@ -291,10 +407,10 @@ impl UnboundKey {
     #[inline]
     fn seal_combined<InOut>(
         &self,
-        nonce: Nonce,
+        nonce: ExtendedNonce,
         aad: &[u8],
         in_out: &mut InOut,
-    ) -> Result<Nonce, Unspecified>
+    ) -> Result<ExtendedNonce, Unspecified>
     where
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
@@ -311,6 +427,13 @@ impl UnboundKey {
         let mut out_len = MaybeUninit::<usize>::uninit();
         let mut_in_out = in_out.as_mut();
 
+        #[cfg(not(feature = "fips"))]
+        if let KeyCtx::AesCcm(key) = &self.ctx {
+            let (plaintext, tag) = mut_in_out.split_at_mut(plaintext_len);
+            key.seal_in_place(nonce.as_ref(), aad, plaintext, tag)?;
+            return Ok(nonce);
+        }
+
         {
             let nonce = nonce.as_ref();
 
@@ -318,7 +441,7 @@ impl UnboundKey {
 
             if 1 != indicator_check!(unsafe {
                 EVP_AEAD_CTX_seal(
-                    *self.ctx.as_ref().as_const(),
+                    *self.aead_ctx()?.as_const(),
                     mut_in_out.as_mut_ptr(),
                     out_len.as_mut_ptr(),
                     plaintext_len + alg_tag_len,
@@ -342,7 +465,7 @@ impl UnboundKey {
         &self,
         aad: &[u8],
         in_out: &mut InOut,
-    ) -> Result<Nonce, Unspecified>
+    ) -> Result<ExtendedNonce, Unspecified>
     where
         InOut: AsMut<[u8]> + for<'in_out> Extend<&'in_out u8>,
     {
@@ -356,7 +479,7 @@ impl UnboundKey {
 
             if 1 != indicator_check!(unsafe {
                 EVP_AEAD_CTX_seal_scatter(
-                    *self.ctx.as_ref().as_const(),
+                    *self.aead_ctx()?.as_const(),
                     in_out.as_mut_ptr(),
                     tag_buffer.as_mut_ptr(),
                     out_tag_len.as_mut_ptr(),
@@ -378,9 +501,7 @@ impl UnboundKey {
         let tag_len = self.algorithm().tag_len();
         let nonce_len = self.algorithm().nonce_len();
 
-        let nonce = Nonce(FixedLength::<NONCE_LEN>::try_from(
-            &tag_buffer[tag_len..tag_len + nonce_len],
-        )?);
+        let nonce = ExtendedNonce::new(&tag_buffer[tag_len..tag_len + nonce_len]);
 
         in_out.extend(&tag_buffer[..tag_len]);
 
@@ -390,63 +511,82 @@ impl UnboundKey {
     #[inline]
     fn seal_separate(
         &self,
-        nonce: Nonce,
+        nonce: ExtendedNonce,
         aad: &[u8],
         in_out: &mut [u8],
-    ) -> Result<(Nonce, Tag), Unspecified> {
-        let mut tag = [0u8; MAX_TAG_LEN];
-        let mut out_tag_len = MaybeUninit::<usize>::uninit();
-        {
-            let nonce = nonce.as_ref();
+    ) -> Result<(ExtendedNonce, Tag), Unspecified> {
+        #[cfg(not(feature = "fips"))]
+        if let KeyCtx::AesCcm(key) = &self.ctx {
+            let mut tag = [0u8; MAX_TAG_LEN];
+            let tag_len = self.algorithm().tag_len();
+            key.seal_in_place(nonce.as_ref(), aad, in_out, &mut tag[..tag_len])?;
+            return Ok((nonce, Tag(tag, tag_len)));
+        }
 
-            debug_assert_eq!(nonce.len(), self.algorithm().nonce_len());
+        self.seal_separate_aead(self.aead_ctx()?, Some(nonce), aad, in_out)
+    }
 
-            if 1 != indicator_check!(unsafe {
-                EVP_AEAD_CTX_seal_scatter(
-                    *self.ctx.as_ref().as_const(),
-                    in_out.as_mut_ptr(),
-                    tag.as_mut_ptr(),
-                    out_tag_len.as_mut_ptr(),
-                    tag.len(),
-                    nonce.as_ptr(),
-                    nonce.len(),
-                    in_out.as_ptr(),
-                    in_out.len(),
-                    null(),
-                    0usize,
-                    aad.as_ptr(),
-                    aad.len(),
-                )
-            }) {
-                return Err(Unspecified);
-            }
-        }
-        Ok((nonce, Tag(tag, unsafe { out_tag_len.assume_init() })))
+    // AES-CCM must process the whole message in a single operation, so the extra input is sealed
+    // together with `in_out` in a temporary buffer that is zeroized when dropped.
+    fn seal_separate_scatter_combined(
+        &self,
+        nonce: ExtendedNonce,
+        aad: &[u8],
+        in_out: &mut [u8],
+        extra_in: &[u8],
+        extra_out_and_tag: &mut [u8],
+    ) -> Result<(), Unspecified> {
+        let mut message = Zeroizing::new(Vec::with_capacity(in_out.len() + extra_in.len()));
+        message.extend_from_slice(in_out);
+        message.extend_from_slice(extra_in);
+        self.check_per_nonce_max_bytes(message.len())?;
+
+        let (_, tag) = self.seal_separate(nonce, aad, &mut message)?;
+
+        let (ciphertext, extra_ciphertext) = message.split_at(in_out.len());
+        let (extra_out, tag_out) = extra_out_and_tag.split_at_mut(extra_in.len());
+        in_out.copy_from_slice(ciphertext);
+        extra_out.copy_from_slice(extra_ciphertext);
+        tag_out.copy_from_slice(tag.as_ref());
+        Ok(())
     }
 
+    // Seals `in_out` with an `EVP_AEAD_CTX` of the key. Without a `nonce`, AWS-LC generates one
+    // and writes it after the tag.
     #[inline]
-    fn seal_separate_randnonce(
+    fn seal_separate_aead(
         &self,
+        aead_ctx: &LcPtr<EVP_AEAD_CTX>,
+        nonce: Option<ExtendedNonce>,
         aad: &[u8],
         in_out: &mut [u8],
-    ) -> Result<(Nonce, Tag), Unspecified> {
+    ) -> Result<(ExtendedNonce, Tag), Unspecified> {
         let mut tag_buffer = [0u8; MAX_TAG_NONCE_BUFFER_LEN];
 
-        debug_assert!(
-            self.algorithm().tag_len() + self.algorithm().nonce_len() <= tag_buffer.len()
-        );
+        let tag_len = self.algorithm().tag_len();
+        let nonce_len = self.algorithm().nonce_len();
+
+        debug_assert!(tag_len + nonce_len <= tag_buffer.len());
+
+        let (nonce_ptr, nonce_ptr_len) = match &nonce {
+            Some(nonce) => {
+                debug_assert_eq!(nonce.as_ref().len(), nonce_len);
+                (nonce.as_ref().as_ptr(), nonce.as_ref().len())
+            }
+            None => (null(), 0),
+        };
 
         let mut out_tag_len = MaybeUninit::<usize>::uninit();
 
         if 1 != indicator_check!(unsafe {
             EVP_AEAD_CTX_seal_scatter(
-                *self.ctx.as_ref().as_const(),
+                *aead_ctx.as_const(),
                 in_out.as_mut_ptr(),
                 tag_buffer.as_mut_ptr(),
                 out_tag_len.as_mut_ptr(),
                 tag_buffer.len(),
-                null(),
-                0,
+                nonce_ptr,
+                nonce_ptr_len,
                 in_out.as_ptr(),
                 in_out.len(),
                 null(),
@@ -458,20 +598,24 @@ impl UnboundKey {
             return Err(Unspecified);
         }
 
-        let tag_len = self.algorithm().tag_len();
-        let nonce_len = self.algorithm().nonce_len();
-
-        let nonce = Nonce(FixedLength::<NONCE_LEN>::try_from(
-            &tag_buffer[tag_len..tag_len + nonce_len],
-        )?);
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => ExtendedNonce::new(&tag_buffer[tag_len..tag_len + nonce_len]),
+        };
 
         let mut tag = [0u8; MAX_TAG_LEN];
-        tag.copy_from_slice(&tag_buffer[..tag_len]);
+        tag[..tag_len].copy_from_slice(&tag_buffer[..tag_len]);
 
         Ok((nonce, Tag(tag, tag_len)))
     }
 }
 
+impl From<AeadCtx> for KeyCtx {
+    fn from(value: AeadCtx) -> Self {
+        KeyCtx::Aead(value)
+    }
+}
+
 impl From<AeadCtx> for UnboundKey {
     fn from(value: AeadCtx) -> Self {
         let algorithm = match value {
@@ -480,15 +624,20 @@ impl From<AeadCtx> for UnboundKey {
             | AeadCtx::AES_128_GCM_TLS13(_)
             | AeadCtx::AES_128_GCM_RANDNONCE(_) => &AES_128_GCM,
             AeadCtx::AES_128_GCM_SIV(_) => &AES_128_GCM_SIV,
+            AeadCtx::AES_192_GCM(_) => &AES_192_GCM,
             AeadCtx::AES_256_GCM(_)
             | AeadCtx::AES_256_GCM_RANDNONCE(_)
             | AeadCtx::AES_256_GCM_TLS12(_)
             | AeadCtx::AES_256_GCM_TLS13(_) => &AES_256_GCM,
             AeadCtx::AES_256_GCM_SIV(_) => &AES_256_GCM_SIV,
             AeadCtx::CHACHA20_POLY1305(_) => &CHACHA20_POLY1305,
+            AeadCtx::XCHACHA20_POLY1305(_) => &XCHACHA20_POLY1305,
+            AeadCtx::AES_128_CCM_BLUETOOTH(_) => &AES_128_CCM_BLUETOOTH,
+            AeadCtx::AES_128_CCM_BLUETOOTH_8(_) => &AES_128_CCM_BLUETOOTH_8,
+            AeadCtx::AES_128_CCM_MATTER(_) => &AES_128_CCM_MATTER,
         };
         Self {
-            ctx: value,
+            ctx: KeyCtx::Aead(value),
             algorithm,
         }
     }
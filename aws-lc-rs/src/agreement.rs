@@ -3,7 +3,7 @@
 // Modifications copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-//! Key Agreement: ECDH, including X25519.
+//! Key Agreement: ECDH, including X25519, and finite field Diffie-Hellman.
 //!
 //! # Example
 //!
@@ -50,6 +50,10 @@
 //! # Ok::<(), aws_lc_rs::error::Unspecified>(())
 //! ```
 mod ephemeral;
+mod ffdhe;
+#[cfg(not(feature = "fips"))]
+pub mod hybrid;
+pub mod spake2;
 
 pub use ephemeral::{agree_ephemeral, EphemeralPrivateKey};
 
@@ -57,6 +61,7 @@ use crate::cbb::LcCBB;
 use crate::ec::{ec_group_from_nid, evp_key_generate};
 use crate::error::{KeyRejected, Unspecified};
 use crate::fips::indicator_check;
+use crate::pkcs8::Version;
 use crate::ptr::{ConstPointer, LcPtr};
 use crate::{ec, hex};
 use aws_lc::{
@@ -64,26 +69,35 @@ use aws_lc::{
     EVP_PKEY_derive_set_peer, EVP_PKEY_get0_EC_KEY, EVP_PKEY_get_raw_private_key,
     EVP_PKEY_get_raw_public_key, EVP_PKEY_id, EVP_PKEY_keygen, EVP_PKEY_keygen_init,
     EVP_PKEY_new_raw_private_key, EVP_PKEY_new_raw_public_key, EVP_marshal_public_key,
-    EVP_parse_public_key, NID_X9_62_prime256v1, NID_secp384r1, NID_secp521r1, BIGNUM, CBS,
-    EVP_PKEY, EVP_PKEY_X25519, NID_X25519,
+    EVP_parse_public_key, NID_X9_62_prime256v1, NID_dhKeyAgreement, NID_secp224r1, NID_secp256k1,
+    NID_secp384r1, NID_secp521r1, BIGNUM, CBS, DH, EVP_PKEY, EVP_PKEY_EC, EVP_PKEY_X25519,
+    NID_X25519,
 };
 
 use crate::encoding::{
     AsBigEndian, AsDer, Curve25519SeedBin, EcPrivateKeyBin, EcPrivateKeyRfc5915Der,
-    EcPublicKeyCompressedBin, EcPublicKeyUncompressedBin, PublicKeyX509Der,
+    EcPublicKeyCompressedBin, EcPublicKeyUncompressedBin, Pkcs8V1Der, PublicKeyX509Der,
 };
 use core::fmt;
 use core::fmt::{Debug, Formatter};
 use core::ptr::null_mut;
 use std::mem::MaybeUninit;
+use zeroize::Zeroizing;
 
 #[allow(non_camel_case_types)]
 #[derive(PartialEq, Eq)]
 enum AlgorithmID {
+    ECDH_P224,
     ECDH_P256,
     ECDH_P384,
     ECDH_P521,
+    ECDH_P256K1,
     X25519,
+    FFDHE2048,
+    FFDHE3072,
+    FFDHE4096,
+    FFDHE6144,
+    FFDHE8192,
 }
 
 impl AlgorithmID {
@@ -93,18 +107,34 @@ impl AlgorithmID {
             AlgorithmID::ECDH_P256 => NID_X9_62_prime256v1,
             AlgorithmID::ECDH_P384 => NID_secp384r1,
             AlgorithmID::ECDH_P521 => NID_secp521r1,
+            AlgorithmID::ECDH_P224 => NID_secp224r1,
+            AlgorithmID::ECDH_P256K1 => NID_secp256k1,
             AlgorithmID::X25519 => NID_X25519,
+            AlgorithmID::FFDHE2048
+            | AlgorithmID::FFDHE3072
+            | AlgorithmID::FFDHE4096
+            | AlgorithmID::FFDHE6144
+            | AlgorithmID::FFDHE8192 => NID_dhKeyAgreement,
         }
     }
 
-    // Uncompressed public key length in bytes
+    // Uncompressed public key length in bytes. Finite field public values have the length of
+    // the prime.
     #[inline]
     const fn pub_key_len(&self) -> usize {
         match self {
-            AlgorithmID::ECDH_P256 => ec::uncompressed_public_key_size_bytes(256),
+            AlgorithmID::ECDH_P224 => ec::uncompressed_public_key_size_bytes(224),
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P256K1 => {
+                ec::uncompressed_public_key_size_bytes(256)
+            }
             AlgorithmID::ECDH_P384 => ec::uncompressed_public_key_size_bytes(384),
             AlgorithmID::ECDH_P521 => ec::uncompressed_public_key_size_bytes(521),
             AlgorithmID::X25519 => 32,
+            AlgorithmID::FFDHE2048 => 2048 / 8,
+            AlgorithmID::FFDHE3072 => 3072 / 8,
+            AlgorithmID::FFDHE4096 => 4096 / 8,
+            AlgorithmID::FFDHE6144 => 6144 / 8,
+            AlgorithmID::FFDHE8192 => 8192 / 8,
         }
     }
 
@@ -112,19 +142,33 @@ impl AlgorithmID {
     #[inline]
     const fn compressed_pub_key_len(&self) -> usize {
         match self {
-            AlgorithmID::ECDH_P256 => ec::compressed_public_key_size_bytes(256),
+            AlgorithmID::ECDH_P224 => ec::compressed_public_key_size_bytes(224),
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P256K1 => {
+                ec::compressed_public_key_size_bytes(256)
+            }
             AlgorithmID::ECDH_P384 => ec::compressed_public_key_size_bytes(384),
             AlgorithmID::ECDH_P521 => ec::compressed_public_key_size_bytes(521),
             AlgorithmID::X25519 => 32,
+            AlgorithmID::FFDHE2048
+            | AlgorithmID::FFDHE3072
+            | AlgorithmID::FFDHE4096
+            | AlgorithmID::FFDHE6144
+            | AlgorithmID::FFDHE8192 => self.pub_key_len(),
         }
     }
 
     #[inline]
     const fn private_key_len(&self) -> usize {
         match self {
-            AlgorithmID::ECDH_P256 | AlgorithmID::X25519 => 32,
+            AlgorithmID::ECDH_P224 => 28,
+            AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P256K1 | AlgorithmID::X25519 => 32,
             AlgorithmID::ECDH_P384 => 48,
             AlgorithmID::ECDH_P521 => 66,
+            AlgorithmID::FFDHE2048
+            | AlgorithmID::FFDHE3072
+            | AlgorithmID::FFDHE4096
+            | AlgorithmID::FFDHE6144
+            | AlgorithmID::FFDHE8192 => self.pub_key_len(),
         }
     }
 }
@@ -135,7 +179,14 @@ impl Debug for AlgorithmID {
             AlgorithmID::ECDH_P256 => "curve: P256",
             AlgorithmID::ECDH_P384 => "curve: P384",
             AlgorithmID::ECDH_P521 => "curve: P521",
+            AlgorithmID::ECDH_P224 => "curve: P224",
+            AlgorithmID::ECDH_P256K1 => "curve: secp256k1",
             AlgorithmID::X25519 => "curve: Curve25519",
+            AlgorithmID::FFDHE2048 => "group: ffdhe2048",
+            AlgorithmID::FFDHE3072 => "group: ffdhe3072",
+            AlgorithmID::FFDHE4096 => "group: ffdhe4096",
+            AlgorithmID::FFDHE6144 => "group: ffdhe6144",
+            AlgorithmID::FFDHE8192 => "group: ffdhe8192",
         };
         f.write_str(output)
     }
@@ -153,6 +204,15 @@ impl Debug for Algorithm {
     }
 }
 
+/// ECDH using the P-224 (secp224r1) curve.
+///
+/// # ⚠️ Warning
+/// P-224 provides a security strength of only 112 bits. This algorithm should only be used for
+/// backwards compatibility purposes.
+pub const ECDH_P224: Algorithm = Algorithm {
+    id: AlgorithmID::ECDH_P224,
+};
+
 /// ECDH using the NSA Suite B P-256 (secp256r1) curve.
 pub const ECDH_P256: Algorithm = Algorithm {
     id: AlgorithmID::ECDH_P256,
@@ -168,6 +228,11 @@ pub const ECDH_P521: Algorithm = Algorithm {
     id: AlgorithmID::ECDH_P521,
 };
 
+/// ECDH using the secp256k1 curve.
+pub const ECDH_P256K1: Algorithm = Algorithm {
+    id: AlgorithmID::ECDH_P256K1,
+};
+
 /// X25519 (ECDH using Curve25519) as described in [RFC 7748].
 ///
 /// Everything is as described in RFC 7748. Key agreement will fail if the
@@ -180,12 +245,62 @@ pub const X25519: Algorithm = Algorithm {
     id: AlgorithmID::X25519,
 };
 
-#[allow(non_camel_case_types)]
+/// Finite field Diffie-Hellman using the 2048-bit `ffdhe2048` group of [RFC 7919].
+///
+/// Public values and shared secrets are encoded as big-endian integers, left-padded with
+/// zeros to the length of the prime, as required by TLS. Key agreement will fail if the peer's
+/// public value is not in the range `[2, p - 2]` or not in the prime order subgroup.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+pub const FFDHE2048: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE2048,
+};
+
+/// Finite field Diffie-Hellman using the 3072-bit `ffdhe3072` group of [RFC 7919].
+///
+/// See [`FFDHE2048`] for the encoding of public values and shared secrets.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+pub const FFDHE3072: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE3072,
+};
+
+/// Finite field Diffie-Hellman using the 4096-bit `ffdhe4096` group of [RFC 7919].
+///
+/// See [`FFDHE2048`] for the encoding of public values and shared secrets.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+pub const FFDHE4096: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE4096,
+};
+
+/// Finite field Diffie-Hellman using the 6144-bit `ffdhe6144` group of [RFC 7919].
+///
+/// See [`FFDHE2048`] for the encoding of public values and shared secrets.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+pub const FFDHE6144: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE6144,
+};
+
+/// Finite field Diffie-Hellman using the 8192-bit `ffdhe8192` group of [RFC 7919].
+///
+/// See [`FFDHE2048`] for the encoding of public values and shared secrets.
+///
+/// [RFC 7919]: https://tools.ietf.org/html/rfc7919
+pub const FFDHE8192: Algorithm = Algorithm {
+    id: AlgorithmID::FFDHE8192,
+};
+
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 enum KeyInner {
+    ECDH_P224(LcPtr<EVP_PKEY>),
     ECDH_P256(LcPtr<EVP_PKEY>),
     ECDH_P384(LcPtr<EVP_PKEY>),
     ECDH_P521(LcPtr<EVP_PKEY>),
+    ECDH_P256K1(LcPtr<EVP_PKEY>),
     X25519(LcPtr<EVP_PKEY>),
+    FFDHE(&'static Algorithm, LcPtr<DH>),
 }
 
 impl Clone for KeyInner {
@@ -194,7 +309,10 @@ impl Clone for KeyInner {
             KeyInner::ECDH_P256(evp_pkey) => KeyInner::ECDH_P256(evp_pkey.clone()),
             KeyInner::ECDH_P384(evp_pkey) => KeyInner::ECDH_P384(evp_pkey.clone()),
             KeyInner::ECDH_P521(evp_pkey) => KeyInner::ECDH_P521(evp_pkey.clone()),
+            KeyInner::ECDH_P224(evp_pkey) => KeyInner::ECDH_P224(evp_pkey.clone()),
+            KeyInner::ECDH_P256K1(evp_pkey) => KeyInner::ECDH_P256K1(evp_pkey.clone()),
             KeyInner::X25519(evp_pkey) => KeyInner::X25519(evp_pkey.clone()),
+            KeyInner::FFDHE(alg, dh) => KeyInner::FFDHE(alg, dh.clone()),
         }
     }
 }
@@ -213,16 +331,22 @@ impl KeyInner {
             KeyInner::ECDH_P256(..) => &ECDH_P256,
             KeyInner::ECDH_P384(..) => &ECDH_P384,
             KeyInner::ECDH_P521(..) => &ECDH_P521,
+            KeyInner::ECDH_P224(..) => &ECDH_P224,
+            KeyInner::ECDH_P256K1(..) => &ECDH_P256K1,
             KeyInner::X25519(..) => &X25519,
+            KeyInner::FFDHE(alg, ..) => alg,
         }
     }
 
-    fn get_evp_pkey(&self) -> &LcPtr<EVP_PKEY> {
+    fn get_evp_pkey(&self) -> Result<&LcPtr<EVP_PKEY>, Unspecified> {
         match self {
             KeyInner::ECDH_P256(evp_pkey)
             | KeyInner::ECDH_P384(evp_pkey)
             | KeyInner::ECDH_P521(evp_pkey)
-            | KeyInner::X25519(evp_pkey) => evp_pkey,
+            | KeyInner::ECDH_P224(evp_pkey)
+            | KeyInner::ECDH_P256K1(evp_pkey)
+            | KeyInner::X25519(evp_pkey) => Ok(evp_pkey),
+            KeyInner::FFDHE(..) => Err(Unspecified),
         }
     }
 }
@@ -247,20 +371,37 @@ impl Debug for PrivateKey {
 }
 
 impl PrivateKey {
-    fn new(alg: &'static Algorithm, evp_pkey: LcPtr<EVP_PKEY>) -> Self {
+    fn new(alg: &'static Algorithm, evp_pkey: LcPtr<EVP_PKEY>) -> Result<Self, Unspecified> {
         match alg.id {
-            AlgorithmID::X25519 => Self {
+            AlgorithmID::X25519 => Ok(Self {
                 inner_key: KeyInner::X25519(evp_pkey),
-            },
-            AlgorithmID::ECDH_P256 => Self {
+            }),
+            AlgorithmID::ECDH_P256 => Ok(Self {
                 inner_key: KeyInner::ECDH_P256(evp_pkey),
-            },
-            AlgorithmID::ECDH_P384 => Self {
+            }),
+            AlgorithmID::ECDH_P384 => Ok(Self {
                 inner_key: KeyInner::ECDH_P384(evp_pkey),
-            },
-            AlgorithmID::ECDH_P521 => Self {
+            }),
+            AlgorithmID::ECDH_P521 => Ok(Self {
                 inner_key: KeyInner::ECDH_P521(evp_pkey),
-            },
+            }),
+            AlgorithmID::ECDH_P224 => Ok(Self {
+                inner_key: KeyInner::ECDH_P224(evp_pkey),
+            }),
+            AlgorithmID::ECDH_P256K1 => Ok(Self {
+                inner_key: KeyInner::ECDH_P256K1(evp_pkey),
+            }),
+            AlgorithmID::FFDHE2048
+            | AlgorithmID::FFDHE3072
+            | AlgorithmID::FFDHE4096
+            | AlgorithmID::FFDHE6144
+            | AlgorithmID::FFDHE8192 => Err(Unspecified),
+        }
+    }
+
+    fn new_ffdhe(alg: &'static Algorithm, dh: LcPtr<DH>) -> Self {
+        Self {
+            inner_key: KeyInner::FFDHE(alg, dh),
         }
     }
 
@@ -268,6 +409,7 @@ impl PrivateKey {
     /// Generate a new private key for the given algorithm.
     // # FIPS
     // Use this function with one of the following algorithms:
+    // * `ECDH_P224`
     // * `ECDH_P256`
     // * `ECDH_P384`
     // * `ECDH_P521`
@@ -275,11 +417,14 @@ impl PrivateKey {
     /// # Errors
     /// `error::Unspecified` when operation fails due to internal error.
     pub fn generate(alg: &'static Algorithm) -> Result<Self, Unspecified> {
+        if let Some(p) = ffdhe::group_prime(&alg.id) {
+            return Ok(Self::new_ffdhe(alg, ffdhe::generate(p)?));
+        }
         let evp_pkey = match alg.id {
             AlgorithmID::X25519 => generate_x25519()?,
             _ => evp_key_generate(alg.id.nid())?,
         };
-        Ok(Self::new(alg, evp_pkey))
+        Self::new(alg, evp_pkey)
     }
 
     /// Deserializes a DER-encoded private key structure to produce a `agreement::PrivateKey`.
@@ -288,7 +433,7 @@ impl PrivateKey {
     /// attempt to automatically detect other key formats. This function supports unencrypted
     /// PKCS#8 `PrivateKeyInfo` structures as well as key type specific formats.
     ///
-    /// X25519 keys are not supported. See `PrivateKey::as_der`.
+    /// X25519 and finite field Diffie-Hellman keys are not supported. See `PrivateKey::as_der`.
     ///
     /// # Errors
     /// `error::KeyRejected` if parsing failed or key otherwise unacceptable.
@@ -298,18 +443,43 @@ impl PrivateKey {
         alg: &'static Algorithm,
         key_bytes: &[u8],
     ) -> Result<Self, KeyRejected> {
-        if AlgorithmID::X25519 == alg.id {
+        if AlgorithmID::X25519 == alg.id || ffdhe::group_prime(&alg.id).is_some() {
             return Err(KeyRejected::invalid_encoding());
         }
         let evp_pkey = ec::unmarshal_der_to_private_key(key_bytes, alg.id.nid())?;
-        Ok(Self::new(alg, evp_pkey))
+        Ok(Self::new(alg, evp_pkey)?)
+    }
+
+    /// Deserializes an unencrypted PKCS#8 v1 or v2 document as a private key for the
+    /// given algorithm. The public key, if present in a v2 document, is not used.
+    ///
+    /// Finite field Diffie-Hellman keys are not supported.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if parsing failed, the key is not for `alg`, or the key is
+    /// otherwise unacceptable.
+    pub fn from_pkcs8(alg: &'static Algorithm, pkcs8: &[u8]) -> Result<Self, KeyRejected> {
+        if ffdhe::group_prime(&alg.id).is_some() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        // Includes a call to `EC_KEY_check_key` for EC keys
+        let evp_pkey = LcPtr::<EVP_PKEY>::try_from(pkcs8)?;
+        validate_evp_pkey_type(alg, &evp_pkey)?;
+        if AlgorithmID::X25519 != alg.id {
+            #[cfg(not(feature = "fips"))]
+            ec::verify_evp_key_nid(&evp_pkey.as_const(), alg.id.nid())?;
+            #[cfg(feature = "fips")]
+            ec::validate_evp_key(&evp_pkey.as_const(), alg.id.nid())?;
+        }
+        Ok(Self::new(alg, evp_pkey)?)
     }
 
     /// Constructs an ECDH key from private key bytes
     ///
     /// The private key must encoded as a big-endian fixed-length integer. For
     /// example, a P-256 private key must be 32 bytes prefixed with leading
-    /// zeros as needed.
+    /// zeros as needed. Finite field Diffie-Hellman private keys have the
+    /// length of the group's prime.
     ///
     /// # Errors
     /// `error::KeyRejected` if parsing failed or key otherwise unacceptable.
@@ -320,6 +490,9 @@ impl PrivateKey {
         if key_bytes.len() != alg.id.private_key_len() {
             return Err(KeyRejected::wrong_algorithm());
         }
+        if let Some(p) = ffdhe::group_prime(&alg.id) {
+            return Ok(Self::new_ffdhe(alg, ffdhe::from_private_key(p, key_bytes)?));
+        }
         let evp_pkey = if AlgorithmID::X25519 == alg.id {
             LcPtr::new(unsafe {
                 EVP_PKEY_new_raw_private_key(
@@ -336,7 +509,7 @@ impl PrivateKey {
             ec::evp_pkey_from_private(&ec_group.as_const(), &private_bn.as_const())
                 .map_err(|_| KeyRejected::invalid_encoding())?
         };
-        Ok(Self::new(alg, evp_pkey))
+        Ok(Self::new(alg, evp_pkey)?)
     }
 
     #[cfg(test)]
@@ -366,6 +539,16 @@ impl PrivateKey {
                 rng.fill(&mut priv_key)?;
                 Self::from_p521_private_key(&priv_key)
             }
+            AlgorithmID::ECDH_P224 | AlgorithmID::ECDH_P256K1 => {
+                let mut priv_key = vec![0u8; alg.id.private_key_len()];
+                rng.fill(&mut priv_key)?;
+                Self::new(alg, from_ec_private_key(&priv_key, alg.id.nid())?)
+            }
+            AlgorithmID::FFDHE2048
+            | AlgorithmID::FFDHE3072
+            | AlgorithmID::FFDHE4096
+            | AlgorithmID::FFDHE6144
+            | AlgorithmID::FFDHE8192 => Self::generate(alg),
         }
     }
 
@@ -416,39 +599,7 @@ impl PrivateKey {
     /// # Errors
     /// `error::Unspecified` when operation fails due to internal error.
     pub fn compute_public_key(&self) -> Result<PublicKey, Unspecified> {
-        match &self.inner_key {
-            KeyInner::ECDH_P256(evp_pkey)
-            | KeyInner::ECDH_P384(evp_pkey)
-            | KeyInner::ECDH_P521(evp_pkey) => {
-                let mut buffer = [0u8; MAX_PUBLIC_KEY_LEN];
-                let key_len = ec::marshal_public_key_to_buffer(&mut buffer, evp_pkey, false)?;
-                Ok(PublicKey {
-                    inner_key: self.inner_key.clone(),
-                    public_key: buffer,
-                    len: key_len,
-                })
-            }
-            KeyInner::X25519(priv_key) => {
-                let mut buffer = [0u8; MAX_PUBLIC_KEY_LEN];
-                let mut out_len = buffer.len();
-
-                if 1 != unsafe {
-                    EVP_PKEY_get_raw_public_key(
-                        *priv_key.as_const(),
-                        buffer.as_mut_ptr(),
-                        &mut out_len,
-                    )
-                } {
-                    return Err(Unspecified);
-                }
-
-                Ok(PublicKey {
-                    inner_key: self.inner_key.clone(),
-                    public_key: buffer,
-                    len: out_len,
-                })
-            }
-        }
+        PublicKey::from_inner_key(self.inner_key.clone())
     }
 
     /// The algorithm for the private key.
@@ -459,6 +610,23 @@ impl PrivateKey {
     }
 }
 
+impl AsDer<Pkcs8V1Der<'static>> for PrivateKey {
+    /// Serializes the key as a PKCS#8 v1 document.
+    ///
+    /// Finite field Diffie-Hellman is not supported.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if serialization failed.
+    fn as_der(&self) -> Result<Pkcs8V1Der<'static>, Unspecified> {
+        Ok(Pkcs8V1Der::new(
+            self.inner_key
+                .get_evp_pkey()?
+                .marshall_private_key(Version::V1)?
+                .into_vec(),
+        ))
+    }
+}
+
 impl AsDer<EcPrivateKeyRfc5915Der<'static>> for PrivateKey {
     /// Serializes the key as a DER-encoded `ECPrivateKey` (RFC 5915) structure.
     ///
@@ -474,7 +642,7 @@ impl AsDer<EcPrivateKeyRfc5915Der<'static>> for PrivateKey {
         let mut outp = null_mut::<u8>();
         let ec_key = {
             ConstPointer::new(unsafe {
-                EVP_PKEY_get0_EC_KEY(*self.inner_key.get_evp_pkey().as_const())
+                EVP_PKEY_get0_EC_KEY(*self.inner_key.get_evp_pkey()?.as_const())
             })?
         };
         let length = usize::try_from(unsafe { aws_lc::i2d_ECPrivateKey(*ec_key, &mut outp) })
@@ -499,7 +667,7 @@ impl AsBigEndian<EcPrivateKeyBin<'static>> for PrivateKey {
         }
         let buffer = ec::marshal_private_key_to_buffer(
             self.inner_key.algorithm().id.private_key_len(),
-            &self.inner_key.get_evp_pkey().as_const(),
+            &self.inner_key.get_evp_pkey()?.as_const(),
         )?;
         Ok(EcPrivateKeyBin::new(buffer))
     }
@@ -516,7 +684,7 @@ impl AsBigEndian<Curve25519SeedBin<'static>> for PrivateKey {
         if AlgorithmID::X25519 != self.inner_key.algorithm().id {
             return Err(Unspecified);
         }
-        let evp_pkey = self.inner_key.get_evp_pkey().as_const();
+        let evp_pkey = self.inner_key.get_evp_pkey()?.as_const();
         let mut buffer = [0u8; AlgorithmID::X25519.private_key_len()];
         let mut out_len = AlgorithmID::X25519.private_key_len();
         if 1 != unsafe {
@@ -529,6 +697,23 @@ impl AsBigEndian<Curve25519SeedBin<'static>> for PrivateKey {
     }
 }
 
+// Verifies that `evp_pkey` is an X25519 or EC key, as appropriate for `alg`. The curve of EC keys
+// is verified separately.
+fn validate_evp_pkey_type(
+    alg: &'static Algorithm,
+    evp_pkey: &LcPtr<EVP_PKEY>,
+) -> Result<(), KeyRejected> {
+    let expected_id = if AlgorithmID::X25519 == alg.id {
+        EVP_PKEY_X25519
+    } else {
+        EVP_PKEY_EC
+    };
+    if expected_id != evp_pkey.id() {
+        return Err(KeyRejected::wrong_algorithm());
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 fn from_ec_private_key(priv_key: &[u8], nid: i32) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
     let ec_group = ec_group_from_nid(nid)?;
@@ -562,11 +747,121 @@ const MAX_PUBLIC_KEY_LEN: usize = ec::PUBLIC_KEY_MAX_LEN;
 /// A public key for key agreement.
 pub struct PublicKey {
     inner_key: KeyInner,
-    public_key: [u8; MAX_PUBLIC_KEY_LEN],
-    len: usize,
+    public_key: PublicKeyBytes,
+}
+
+// Finite field public values, which are up to 1024 bytes long, are kept on the heap so that they
+// do not enlarge the public keys of the other algorithms.
+#[derive(Clone)]
+enum PublicKeyBytes {
+    Inline([u8; MAX_PUBLIC_KEY_LEN], usize),
+    Boxed(Box<[u8]>),
+}
+
+impl PublicKeyBytes {
+    fn as_slice(&self) -> &[u8] {
+        match self {
+            PublicKeyBytes::Inline(buffer, len) => &buffer[..*len],
+            PublicKeyBytes::Boxed(buffer) => buffer,
+        }
+    }
 }
 
 impl PublicKey {
+    fn from_inner_key(inner_key: KeyInner) -> Result<Self, Unspecified> {
+        let mut buffer = [0u8; MAX_PUBLIC_KEY_LEN];
+        let public_key = match &inner_key {
+            KeyInner::ECDH_P256(evp_pkey)
+            | KeyInner::ECDH_P384(evp_pkey)
+            | KeyInner::ECDH_P521(evp_pkey)
+            | KeyInner::ECDH_P224(evp_pkey)
+            | KeyInner::ECDH_P256K1(evp_pkey) => {
+                let len = ec::marshal_public_key_to_buffer(&mut buffer, evp_pkey, false)?;
+                PublicKeyBytes::Inline(buffer, len)
+            }
+            KeyInner::X25519(evp_pkey) => {
+                let mut out_len = buffer.len();
+                if 1 != unsafe {
+                    EVP_PKEY_get_raw_public_key(
+                        *evp_pkey.as_const(),
+                        buffer.as_mut_ptr(),
+                        &mut out_len,
+                    )
+                } {
+                    return Err(Unspecified);
+                }
+                PublicKeyBytes::Inline(buffer, out_len)
+            }
+            KeyInner::FFDHE(alg, dh) => {
+                let mut buffer = vec![0u8; alg.id.pub_key_len()].into_boxed_slice();
+                ffdhe::public_key(dh, &mut buffer)?;
+                PublicKeyBytes::Boxed(buffer)
+            }
+        };
+        Ok(PublicKey {
+            inner_key,
+            public_key,
+        })
+    }
+
+    /// Parses and validates a DER-encoded (X.509) `SubjectPublicKeyInfo` structure as a public
+    /// key for the given algorithm.
+    ///
+    /// Finite field Diffie-Hellman is not supported.
+    ///
+    /// The parsed key can be used for any number of key agreements by passing it to
+    /// [`agree_with_public_key`], which does not parse it again.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if parsing failed, the key is not for `alg`, or the key is
+    /// otherwise unacceptable.
+    pub fn from_der(alg: &'static Algorithm, der: &[u8]) -> Result<Self, KeyRejected> {
+        if ffdhe::group_prime(&alg.id).is_some() {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        let evp_pkey = ec::try_parse_subject_public_key_info_bytes(der)
+            .map_err(|_| KeyRejected::invalid_encoding())?;
+        validate_evp_pkey_type(alg, &evp_pkey)?;
+        if AlgorithmID::X25519 != alg.id {
+            ec::validate_evp_key(&evp_pkey.as_const(), alg.id.nid())?;
+        }
+        Ok(Self::from_inner_key(
+            PrivateKey::new(alg, evp_pkey)?.inner_key,
+        )?)
+    }
+
+    /// Parses and validates a public key for the given algorithm from its raw encoding.
+    ///
+    /// * ECDH public keys are elliptic curve points, either uncompressed or compressed, encoded
+    ///   using the Octet-String-to-Elliptic-Curve-Point algorithm in
+    ///   [SEC 1: Elliptic Curve Cryptography, Version 2.0].
+    /// * X25519 public keys are 32 bytes.
+    /// * Finite field Diffie-Hellman public values are big-endian integers with the length of
+    ///   the group's prime.
+    ///
+    /// The parsed key can be used for any number of key agreements by passing it to
+    /// [`agree_with_public_key`], which does not parse it again.
+    ///
+    /// [SEC 1: Elliptic Curve Cryptography, Version 2.0]: https://www.secg.org/sec1-v2.pdf
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if parsing failed or the key is otherwise unacceptable.
+    pub fn from_raw(alg: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, KeyRejected> {
+        if let Some(p) = ffdhe::group_prime(&alg.id) {
+            let dh = ffdhe::from_public_key(p, key_bytes)?;
+            return Ok(Self::from_inner_key(KeyInner::FFDHE(alg, dh))?);
+        }
+        let evp_pkey = if AlgorithmID::X25519 == alg.id {
+            try_parse_x25519_public_key_raw_bytes(key_bytes)
+        } else {
+            ec::try_parse_public_key_raw_bytes(key_bytes, alg.id.nid())
+        }
+        .map_err(|_| KeyRejected::invalid_encoding())?;
+        Ok(Self::from_inner_key(
+            PrivateKey::new(alg, evp_pkey)?.inner_key,
+        )?)
+    }
+
     /// The algorithm for the public key.
     #[must_use]
     pub fn algorithm(&self) -> &'static Algorithm {
@@ -582,7 +877,7 @@ impl Debug for PublicKey {
         f.write_str(&format!(
             "PublicKey {{ algorithm: {:?}, bytes: \"{}\" }}",
             self.inner_key.algorithm(),
-            hex::encode(&self.public_key[0..self.len])
+            hex::encode(self.public_key.as_slice())
         ))
     }
 }
@@ -592,7 +887,7 @@ impl AsRef<[u8]> for PublicKey {
     /// Octet-String-to-Elliptic-Curve-Point algorithm in
     /// [SEC 1: Elliptic Curve Cryptography, Version 2.0].
     fn as_ref(&self) -> &[u8] {
-        &self.public_key[0..self.len]
+        self.public_key.as_slice()
     }
 }
 
@@ -600,14 +895,16 @@ impl Clone for PublicKey {
     fn clone(&self) -> Self {
         PublicKey {
             inner_key: self.inner_key.clone(),
-            public_key: self.public_key,
-            len: self.len,
+            public_key: self.public_key.clone(),
         }
     }
 }
 
 impl AsDer<PublicKeyX509Der<'static>> for PublicKey {
     /// Provides the public key as a DER-encoded (X.509) `SubjectPublicKeyInfo` structure.
+    ///
+    /// Finite field Diffie-Hellman is not supported.
+    ///
     /// # Errors
     /// Returns an error if the public key fails to marshal to X.509.
     fn as_der(&self) -> Result<PublicKeyX509Der<'static>, crate::error::Unspecified> {
@@ -615,6 +912,8 @@ impl AsDer<PublicKeyX509Der<'static>> for PublicKey {
             KeyInner::ECDH_P256(evp_pkey)
             | KeyInner::ECDH_P384(evp_pkey)
             | KeyInner::ECDH_P521(evp_pkey)
+            | KeyInner::ECDH_P224(evp_pkey)
+            | KeyInner::ECDH_P256K1(evp_pkey)
             | KeyInner::X25519(evp_pkey) => {
                 let key_size_bytes =
                     TryInto::<usize>::try_into(unsafe { EVP_PKEY_bits(*evp_pkey.as_const()) })
@@ -626,6 +925,7 @@ impl AsDer<PublicKeyX509Der<'static>> for PublicKey {
                 };
                 Ok(PublicKeyX509Der::from(der.into_buffer()?))
             }
+            KeyInner::FFDHE(..) => Err(Unspecified),
         }
     }
 }
@@ -638,8 +938,10 @@ impl AsBigEndian<EcPublicKeyCompressedBin<'static>> for PublicKey {
         let evp_pkey = match &self.inner_key {
             KeyInner::ECDH_P256(evp_pkey)
             | KeyInner::ECDH_P384(evp_pkey)
-            | KeyInner::ECDH_P521(evp_pkey) => evp_pkey,
-            KeyInner::X25519(_) => return Err(Unspecified),
+            | KeyInner::ECDH_P521(evp_pkey)
+            | KeyInner::ECDH_P224(evp_pkey)
+            | KeyInner::ECDH_P256K1(evp_pkey) => evp_pkey,
+            KeyInner::X25519(_) | KeyInner::FFDHE(..) => return Err(Unspecified),
         };
         let ec_key = ConstPointer::new(unsafe { EVP_PKEY_get0_EC_KEY(*evp_pkey.as_const()) })?;
 
@@ -665,14 +967,18 @@ impl AsBigEndian<EcPublicKeyUncompressedBin<'static>> for PublicKey {
     fn as_be_bytes(
         &self,
     ) -> Result<EcPublicKeyUncompressedBin<'static>, crate::error::Unspecified> {
-        if self.algorithm().id == AlgorithmID::X25519 {
-            return Err(Unspecified);
+        match self.inner_key {
+            KeyInner::ECDH_P224(..)
+            | KeyInner::ECDH_P256(..)
+            | KeyInner::ECDH_P384(..)
+            | KeyInner::ECDH_P521(..)
+            | KeyInner::ECDH_P256K1(..) => {}
+            KeyInner::X25519(..) | KeyInner::FFDHE(..) => return Err(Unspecified),
         }
 
-        let mut buffer = vec![0u8; self.len];
-        buffer.copy_from_slice(&self.public_key[0..self.len]);
-
-        Ok(EcPublicKeyUncompressedBin::new(buffer))
+        Ok(EcPublicKeyUncompressedBin::new(
+            self.public_key.as_slice().to_vec(),
+        ))
     }
 }
 
@@ -736,6 +1042,7 @@ impl<B: AsRef<[u8]>> UnparsedPublicKey<B> {
 /// returns.
 // # FIPS
 // Use this function with one of the following key algorithms:
+// * `ECDH_P224`
 // * `ECDH_P256`
 // * `ECDH_P384`
 // * `ECDH_P521`
@@ -770,9 +1077,75 @@ where
         }
         KeyInner::ECDH_P256(priv_key)
         | KeyInner::ECDH_P384(priv_key)
-        | KeyInner::ECDH_P521(priv_key) => {
+        | KeyInner::ECDH_P521(priv_key)
+        | KeyInner::ECDH_P224(priv_key)
+        | KeyInner::ECDH_P256K1(priv_key) => {
             ec_key_ecdh(&mut buffer, priv_key, peer_pub_bytes, expected_nid).or(Err(error_value))?
         }
+        KeyInner::FFDHE(alg, dh) => {
+            // Finite field secrets, which are up to 1024 bytes long, use a heap buffer.
+            let p_len = alg.id.pub_key_len();
+            let mut buffer = Zeroizing::new(vec![0u8; p_len]);
+            let secret = ffdhe::diffie_hellman(&mut buffer, dh, p_len, peer_pub_bytes)
+                .or(Err(error_value))?;
+            return kdf(secret);
+        }
+    };
+    kdf(secret)
+}
+
+/// Performs a key agreement with a private key and a peer's public key that has already been
+/// parsed and validated, e.g. by [`PublicKey::from_der`] or [`PublicKey::from_raw`].
+///
+/// Unlike [`agree`], the peer's public key is not parsed again, so the same `PublicKey` can be
+/// used for any number of key agreements.
+///
+/// `error_value` is returned if `peer_public_key` does not match `my_private_key's`
+/// algorithm/curve, or if the key agreement fails. Otherwise, `kdf` is called with the raw key
+/// material and its result is returned.
+// # FIPS
+// Use this function with one of the following key algorithms:
+// * `ECDH_P224`
+// * `ECDH_P256`
+// * `ECDH_P384`
+// * `ECDH_P521`
+//
+/// # Errors
+/// `error_value` on internal failure.
+#[inline]
+pub fn agree_with_public_key<F, R, E>(
+    my_private_key: &PrivateKey,
+    peer_public_key: &PublicKey,
+    error_value: E,
+    kdf: F,
+) -> Result<R, E>
+where
+    F: FnOnce(&[u8]) -> Result<R, E>,
+{
+    if peer_public_key.algorithm() != my_private_key.algorithm() {
+        return Err(error_value);
+    }
+
+    let mut buffer = [0u8; MAX_AGREEMENT_SECRET_LEN];
+
+    let secret: &[u8] = match (&my_private_key.inner_key, &peer_public_key.inner_key) {
+        (KeyInner::X25519(priv_key), KeyInner::X25519(peer_key))
+        | (KeyInner::ECDH_P256(priv_key), KeyInner::ECDH_P256(peer_key))
+        | (KeyInner::ECDH_P384(priv_key), KeyInner::ECDH_P384(peer_key))
+        | (KeyInner::ECDH_P521(priv_key), KeyInner::ECDH_P521(peer_key))
+        | (KeyInner::ECDH_P224(priv_key), KeyInner::ECDH_P224(peer_key))
+        | (KeyInner::ECDH_P256K1(priv_key), KeyInner::ECDH_P256K1(peer_key)) => {
+            evp_pkey_derive(&mut buffer, priv_key, peer_key).or(Err(error_value))?
+        }
+        (KeyInner::FFDHE(alg, dh), KeyInner::FFDHE(_, peer_dh)) => {
+            // Finite field secrets, which are up to 1024 bytes long, use a heap buffer.
+            let p_len = alg.id.pub_key_len();
+            let mut buffer = Zeroizing::new(vec![0u8; p_len]);
+            let secret = ffdhe::diffie_hellman_with_key(&mut buffer, dh, p_len, peer_dh)
+                .or(Err(error_value))?;
+            return kdf(secret);
+        }
+        _ => return Err(error_value),
     };
     kdf(secret)
 }
@@ -788,15 +1161,25 @@ fn ec_key_ecdh<'a>(
     peer_pub_key_bytes: &[u8],
     nid: i32,
 ) -> Result<&'a [u8], ()> {
-    let mut pub_key = ec::try_parse_public_key_bytes(peer_pub_key_bytes, nid)?;
+    let pub_key = ec::try_parse_public_key_bytes(peer_pub_key_bytes, nid)?;
+    evp_pkey_derive(buffer, priv_key, &pub_key)
+}
 
+// Derives the shared secret of `priv_key` and an already parsed and validated `peer_key`.
+#[inline]
+fn evp_pkey_derive<'a>(
+    buffer: &'a mut [u8; MAX_AGREEMENT_SECRET_LEN],
+    priv_key: &LcPtr<EVP_PKEY>,
+    peer_key: &LcPtr<EVP_PKEY>,
+) -> Result<&'a [u8], ()> {
     let mut pkey_ctx = priv_key.create_EVP_PKEY_CTX()?;
 
     if 1 != unsafe { EVP_PKEY_derive_init(*pkey_ctx.as_mut()) } {
         return Err(());
     };
 
-    if 1 != unsafe { EVP_PKEY_derive_set_peer(*pkey_ctx.as_mut(), *pub_key.as_mut()) } {
+    // `EVP_PKEY_derive_set_peer` only takes a reference to `peer_key`.
+    if 1 != unsafe { EVP_PKEY_derive_set_peer(*pkey_ctx.as_mut(), *peer_key.as_mut_unsafe()) } {
         return Err(());
     }
 
@@ -821,29 +1204,12 @@ fn x25519_diffie_hellman<'a>(
     priv_key: &LcPtr<EVP_PKEY>,
     peer_pub_key: &[u8],
 ) -> Result<&'a [u8], ()> {
-    let mut pkey_ctx = priv_key.create_EVP_PKEY_CTX()?;
-
-    if 1 != unsafe { EVP_PKEY_derive_init(*pkey_ctx.as_mut()) } {
-        return Err(());
-    };
-
-    let mut pub_key = try_parse_x25519_public_key_bytes(peer_pub_key)?;
-
-    if 1 != unsafe { EVP_PKEY_derive_set_peer(*pkey_ctx.as_mut(), *pub_key.as_mut()) } {
-        return Err(());
-    }
-
-    let mut out_key_len = buffer.len();
+    let pub_key = try_parse_x25519_public_key_bytes(peer_pub_key)?;
+    let secret = evp_pkey_derive(buffer, priv_key, &pub_key)?;
 
-    if 1 != indicator_check!(unsafe {
-        EVP_PKEY_derive(*pkey_ctx.as_mut(), buffer.as_mut_ptr(), &mut out_key_len)
-    }) {
-        return Err(());
-    }
-
-    debug_assert!(out_key_len == AlgorithmID::X25519.pub_key_len());
+    debug_assert!(secret.len() == AlgorithmID::X25519.pub_key_len());
 
-    Ok(&buffer[0..AlgorithmID::X25519.pub_key_len()])
+    Ok(secret)
 }
 
 pub(crate) fn try_parse_x25519_public_key_bytes(
@@ -890,14 +1256,15 @@ fn try_parse_x25519_subject_public_key_info_bytes(
 #[cfg(test)]
 mod tests {
     use crate::agreement::{
-        agree, Algorithm, PrivateKey, PublicKey, UnparsedPublicKey, ECDH_P256, ECDH_P384,
-        ECDH_P521, X25519,
+        agree, agree_with_public_key, ffdhe, Algorithm, PrivateKey, PublicKey, UnparsedPublicKey,
+        ECDH_P224, ECDH_P256, ECDH_P256K1, ECDH_P384, ECDH_P521, FFDHE2048, FFDHE3072, FFDHE4096,
+        FFDHE6144, FFDHE8192, X25519,
     };
     use crate::encoding::{
         AsBigEndian, AsDer, Curve25519SeedBin, EcPrivateKeyBin, EcPrivateKeyRfc5915Der,
-        EcPublicKeyCompressedBin, EcPublicKeyUncompressedBin, PublicKeyX509Der,
+        EcPublicKeyCompressedBin, EcPublicKeyUncompressedBin, Pkcs8V1Der, PublicKeyX509Der,
     };
-    use crate::{rand, test};
+    use crate::{rand, test, test_file};
 
     #[test]
     fn test_agreement_x25519() {
@@ -1284,7 +1651,15 @@ mod tests {
 
     #[test]
     fn test_agreement_random() {
-        let test_algorithms = [&ECDH_P256, &ECDH_P384, &ECDH_P521, &X25519];
+        let test_algorithms = [
+            &ECDH_P224,
+            &ECDH_P256,
+            &ECDH_P256K1,
+            &ECDH_P384,
+            &ECDH_P521,
+            &X25519,
+            &FFDHE2048,
+        ];
 
         for alg in test_algorithms {
             test_agreement_random_helper(alg);
@@ -1321,10 +1696,17 @@ mod tests {
         }
 
         let key_types_tested = match alg.id {
-            crate::agreement::AlgorithmID::ECDH_P256
+            crate::agreement::AlgorithmID::ECDH_P224
+            | crate::agreement::AlgorithmID::ECDH_P256
             | crate::agreement::AlgorithmID::ECDH_P384
-            | crate::agreement::AlgorithmID::ECDH_P521 => 4,
+            | crate::agreement::AlgorithmID::ECDH_P521
+            | crate::agreement::AlgorithmID::ECDH_P256K1 => 4,
             crate::agreement::AlgorithmID::X25519 => 2,
+            crate::agreement::AlgorithmID::FFDHE2048
+            | crate::agreement::AlgorithmID::FFDHE3072
+            | crate::agreement::AlgorithmID::FFDHE4096
+            | crate::agreement::AlgorithmID::FFDHE6144
+            | crate::agreement::AlgorithmID::FFDHE8192 => 1,
         };
 
         assert_eq!(results.len(), key_types_tested * 2); // Multiplied by two because we tested the other direction
@@ -1335,9 +1717,11 @@ mod tests {
     fn public_key_formats_helper(public_key: &PublicKey) -> Vec<Vec<u8>> {
         let verify_ec_raw_traits = matches!(
             public_key.algorithm().id,
-            crate::agreement::AlgorithmID::ECDH_P256
+            crate::agreement::AlgorithmID::ECDH_P224
+                | crate::agreement::AlgorithmID::ECDH_P256
                 | crate::agreement::AlgorithmID::ECDH_P384
                 | crate::agreement::AlgorithmID::ECDH_P521
+                | crate::agreement::AlgorithmID::ECDH_P256K1
         );
 
         let mut public_keys = Vec::<Vec<u8>>::new();
@@ -1350,12 +1734,144 @@ mod tests {
             public_keys.push(raw.as_ref().into());
         }
 
-        let peer_x509 = AsDer::<PublicKeyX509Der>::as_der(public_key).unwrap();
-        public_keys.push(peer_x509.as_ref().into());
+        let peer_x509 = AsDer::<PublicKeyX509Der>::as_der(public_key);
+        if ffdhe::group_prime(&public_key.algorithm().id).is_some() {
+            assert!(peer_x509.is_err());
+        } else {
+            public_keys.push(peer_x509.unwrap().as_ref().into());
+        }
 
         public_keys
     }
 
+    #[test]
+    fn test_ffdhe_from_private_key() {
+        for alg in [&FFDHE2048, &FFDHE3072, &FFDHE4096, &FFDHE6144, &FFDHE8192] {
+            let len = alg.id.private_key_len();
+            let mut key_bytes = vec![0u8; len];
+
+            // With a private key of 1 the public value is the generator.
+            key_bytes[len - 1] = 1;
+            let one = PrivateKey::from_private_key(alg, &key_bytes).unwrap();
+            let public_key = one.compute_public_key().unwrap();
+            assert_eq!(len, public_key.as_ref().len());
+            assert!(public_key.as_ref()[..len - 1].iter().all(|b| *b == 0));
+            assert_eq!(2, public_key.as_ref()[len - 1]);
+
+            key_bytes[len - 1] = 2;
+            let two = PrivateKey::from_private_key(alg, &key_bytes).unwrap();
+            let public_key = two.compute_public_key().unwrap();
+
+            // 2^(1 * 2) = 4, left-padded to the length of the prime.
+            let mut expected = vec![0u8; len];
+            expected[len - 1] = 4;
+            agree(
+                &one,
+                &UnparsedPublicKey::new(alg, public_key.as_ref()),
+                (),
+                |secret| {
+                    assert_eq!(expected.as_slice(), secret);
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+            // Private keys must be in [1, q - 1] and have the length of the prime.
+            key_bytes[len - 1] = 0;
+            assert!(PrivateKey::from_private_key(alg, &key_bytes).is_err());
+            assert!(PrivateKey::from_private_key(alg, &[0xff; 32]).is_err());
+            let p = ffdhe::group_prime(&alg.id).unwrap();
+            assert!(PrivateKey::from_private_key(alg, p).is_err());
+            assert!(PrivateKey::from_private_key_der(alg, &key_bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn test_ffdhe_known_answer() {
+        test::run(
+            test_file!("agreement/data/ffdhe_tests.txt"),
+            |section, test_case| {
+                assert_eq!(section, "");
+                let alg = match test_case.consume_string("Group").as_str() {
+                    "ffdhe2048" => &FFDHE2048,
+                    "ffdhe3072" => &FFDHE3072,
+                    "ffdhe4096" => &FFDHE4096,
+                    "ffdhe6144" => &FFDHE6144,
+                    "ffdhe8192" => &FFDHE8192,
+                    group => panic!("Unsupported group: {group}"),
+                };
+                let private_key =
+                    PrivateKey::from_private_key(alg, &test_case.consume_bytes("D")).unwrap();
+                let my_public = test_case.consume_bytes("MyQ");
+                let peer_public = UnparsedPublicKey::new(alg, test_case.consume_bytes("PeerQ"));
+                let output = test_case.consume_bytes("Output");
+
+                assert_eq!(
+                    my_public,
+                    private_key.compute_public_key().unwrap().as_ref()
+                );
+                agree(&private_key, &peer_public, (), |secret| {
+                    assert_eq!(output.as_slice(), secret);
+                    Ok(())
+                })
+                .unwrap();
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn test_ffdhe2048_prime_matches_aws_lc() {
+        use crate::ptr::{ConstPointer, LcPtr};
+        use aws_lc::{BN_cmp, DH_get0_p, DH_get_rfc7919_2048, BIGNUM};
+
+        let dh = LcPtr::new(unsafe { DH_get_rfc7919_2048() }).unwrap();
+        let expected = ConstPointer::new(unsafe { DH_get0_p(*dh.as_const()) }).unwrap();
+        let p = LcPtr::<BIGNUM>::try_from(ffdhe::group_prime(&FFDHE2048.id).unwrap()).unwrap();
+        assert_eq!(0, unsafe { BN_cmp(*p.as_const(), *expected) });
+    }
+
+    #[test]
+    fn test_ffdhe_invalid_peer_public_key() {
+        let alg = &FFDHE2048;
+        let private_key = PrivateKey::generate(alg).unwrap();
+        let len = alg.id.pub_key_len();
+        let p = ffdhe::group_prime(&alg.id).unwrap();
+
+        let mut p_minus_one = p.to_vec();
+        p_minus_one[len - 1] -= 1;
+        let mut one = vec![0u8; len];
+        one[len - 1] = 1;
+        let mut two_short = vec![0u8; len - 1];
+        two_short[len - 2] = 2;
+
+        for peer in [
+            vec![0u8; len],
+            one,
+            p_minus_one,
+            p.to_vec(),
+            vec![0xff; len],
+            two_short,
+        ] {
+            let peer = UnparsedPublicKey::new(alg, peer);
+            assert!(agree(&private_key, &peer, (), |_| Ok(())).is_err());
+        }
+
+        let other = PrivateKey::generate(&FFDHE3072).unwrap();
+        let peer = other.compute_public_key().unwrap();
+        let peer = UnparsedPublicKey::new(&FFDHE3072, peer.as_ref());
+        assert!(agree(&private_key, &peer, (), |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_ffdhe_debug() {
+        let private_key = PrivateKey::generate(&FFDHE2048).unwrap();
+        assert_eq!(
+            "PrivateKey { algorithm: Algorithm { group: ffdhe2048 } }",
+            format!("{private_key:?}")
+        );
+    }
+
     #[test]
     fn private_key_drop() {
         let private_key = PrivateKey::generate(&ECDH_P256).unwrap();
@@ -1366,4 +1882,175 @@ mod tests {
         let _ = AsBigEndian::<EcPublicKeyUncompressedBin>::as_be_bytes(&public_key).unwrap();
         let _ = AsDer::<PublicKeyX509Der>::as_der(&public_key).unwrap();
     }
+
+    #[test]
+    fn test_pkcs8_round_trip() {
+        for alg in [&ECDH_P256, &ECDH_P384, &ECDH_P521, &X25519] {
+            let private_key = PrivateKey::generate(alg).unwrap();
+            let pkcs8 = AsDer::<Pkcs8V1Der>::as_der(&private_key).unwrap();
+            let parsed = PrivateKey::from_pkcs8(alg, pkcs8.as_ref()).unwrap();
+            assert_eq!(alg, parsed.algorithm());
+            assert_eq!(
+                private_key.compute_public_key().unwrap().as_ref(),
+                parsed.compute_public_key().unwrap().as_ref()
+            );
+
+            // Keys for other curves are rejected.
+            for other_alg in [&ECDH_P256, &ECDH_P384, &ECDH_P521, &X25519] {
+                if other_alg != alg {
+                    assert!(PrivateKey::from_pkcs8(other_alg, pkcs8.as_ref()).is_err());
+                }
+            }
+            assert!(PrivateKey::from_pkcs8(alg, &pkcs8.as_ref()[1..]).is_err());
+        }
+
+        let ffdhe_key = PrivateKey::generate(&FFDHE2048).unwrap();
+        assert!(AsDer::<Pkcs8V1Der>::as_der(&ffdhe_key).is_err());
+        let pkcs8 = AsDer::<Pkcs8V1Der>::as_der(&PrivateKey::generate(&X25519).unwrap()).unwrap();
+        assert!(PrivateKey::from_pkcs8(&FFDHE2048, pkcs8.as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_pkcs8_x25519() {
+        // The private key from RFC 8410 Section 10.3, with the X25519 algorithm identifier.
+        let pkcs8 = test::from_dirty_hex(
+            "302e020100300506032b656e04220420d4ee72dbf913584ad5b6d8f1f769f8ad3afe7c28cbf1d4fbe097a88f44755842",
+        );
+        let private_key = PrivateKey::from_pkcs8(&X25519, &pkcs8).unwrap();
+        assert_eq!(
+            test::from_dirty_hex(
+                "226ad8f8f62b7b2637247e6b21ffb5723d6330c4d5dd5a8d11e7e7de65c1c501"
+            ),
+            private_key.compute_public_key().unwrap().as_ref()
+        );
+        assert_eq!(
+            pkcs8,
+            AsDer::<Pkcs8V1Der>::as_der(&private_key).unwrap().as_ref()
+        );
+
+        // The Ed25519 key from RFC 8410 Section 10.3 is rejected.
+        let ed25519_pkcs8 = test::from_dirty_hex(
+            "302e020100300506032b657004220420d4ee72dbf913584ad5b6d8f1f769f8ad3afe7c28cbf1d4fbe097a88f44755842",
+        );
+        assert!(PrivateKey::from_pkcs8(&X25519, &ed25519_pkcs8).is_err());
+    }
+
+    #[test]
+    fn test_public_key_from_der_and_raw() {
+        for alg in [&ECDH_P256, &ECDH_P384, &ECDH_P521, &X25519] {
+            let my_private_key = PrivateKey::generate(alg).unwrap();
+            let peer_private_key = PrivateKey::generate(alg).unwrap();
+            let peer_public_key = peer_private_key.compute_public_key().unwrap();
+
+            let der = AsDer::<PublicKeyX509Der>::as_der(&peer_public_key).unwrap();
+            let from_der = PublicKey::from_der(alg, der.as_ref()).unwrap();
+            let from_raw = PublicKey::from_raw(alg, peer_public_key.as_ref()).unwrap();
+            assert_eq!(peer_public_key.as_ref(), from_der.as_ref());
+            assert_eq!(peer_public_key.as_ref(), from_raw.as_ref());
+            assert_eq!(alg, from_der.algorithm());
+
+            let expected = agree(
+                &my_private_key,
+                &UnparsedPublicKey::new(alg, &peer_public_key),
+                (),
+                |secret| Ok(secret.to_vec()),
+            )
+            .unwrap();
+            for parsed in [&from_der, &from_raw, &peer_public_key] {
+                let secret =
+                    agree_with_public_key(
+                        &my_private_key,
+                        parsed,
+                        (),
+                        |secret| Ok(secret.to_vec()),
+                    )
+                    .unwrap();
+                assert_eq!(expected, secret);
+            }
+
+            // DER and raw encodings are not interchangeable, and keys for other curves are
+            // rejected.
+            assert!(PublicKey::from_der(alg, peer_public_key.as_ref()).is_err());
+            assert!(PublicKey::from_raw(alg, der.as_ref()).is_err());
+            for other_alg in [&ECDH_P256, &ECDH_P384, &ECDH_P521, &X25519] {
+                if other_alg != alg {
+                    assert!(PublicKey::from_der(other_alg, der.as_ref()).is_err());
+                    assert!(PublicKey::from_raw(other_alg, peer_public_key.as_ref()).is_err());
+                }
+            }
+        }
+
+        // A compressed point is accepted, and is provided uncompressed.
+        let private_key = PrivateKey::generate(&ECDH_P256).unwrap();
+        let public_key = private_key.compute_public_key().unwrap();
+        let compressed = AsBigEndian::<EcPublicKeyCompressedBin>::as_be_bytes(&public_key).unwrap();
+        let parsed = PublicKey::from_raw(&ECDH_P256, compressed.as_ref()).unwrap();
+        assert_eq!(public_key.as_ref(), parsed.as_ref());
+
+        // A point that is not on the curve is rejected.
+        let mut invalid = public_key.as_ref().to_vec();
+        invalid[64] ^= 1;
+        assert!(PublicKey::from_raw(&ECDH_P256, &invalid).is_err());
+
+        // The X25519 public key from RFC 8410 Section 10.1.
+        let der = test::from_dirty_hex(
+            "302a300506032b656e03210019bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1",
+        );
+        let public_key = PublicKey::from_der(&X25519, &der).unwrap();
+        assert_eq!(&der[12..], public_key.as_ref());
+        assert_eq!(
+            der,
+            AsDer::<PublicKeyX509Der>::as_der(&public_key)
+                .unwrap()
+                .as_ref()
+        );
+    }
+
+    #[test]
+    fn test_public_key_from_raw_ffdhe() {
+        let alg = &FFDHE2048;
+        let my_private_key = PrivateKey::generate(alg).unwrap();
+        let peer_public_key = PrivateKey::generate(alg)
+            .unwrap()
+            .compute_public_key()
+            .unwrap();
+
+        let parsed = PublicKey::from_raw(alg, peer_public_key.as_ref()).unwrap();
+        assert_eq!(peer_public_key.as_ref(), parsed.as_ref());
+        let expected = agree(
+            &my_private_key,
+            &UnparsedPublicKey::new(alg, &parsed),
+            (),
+            |secret| Ok(secret.to_vec()),
+        )
+        .unwrap();
+        for peer in [&parsed, &peer_public_key] {
+            let secret =
+                agree_with_public_key(&my_private_key, peer, (), |secret| Ok(secret.to_vec()))
+                    .unwrap();
+            assert_eq!(expected, secret);
+        }
+
+        // The peer's public key must be for the same group.
+        let other = PrivateKey::generate(&FFDHE3072)
+            .unwrap()
+            .compute_public_key()
+            .unwrap();
+        assert!(agree_with_public_key(&my_private_key, &other, (), |_| Ok(())).is_err());
+
+        let len = alg.id.pub_key_len();
+        let mut one = vec![0u8; len];
+        one[len - 1] = 1;
+        let p = ffdhe::group_prime(&alg.id).unwrap();
+        for invalid in [
+            vec![0u8; len],
+            one,
+            p.to_vec(),
+            peer_public_key.as_ref()[1..].to_vec(),
+        ] {
+            assert!(PublicKey::from_raw(alg, &invalid).is_err());
+        }
+        assert!(PublicKey::from_raw(&FFDHE3072, peer_public_key.as_ref()).is_err());
+        assert!(PublicKey::from_der(alg, peer_public_key.as_ref()).is_err());
+    }
 }
@@ -30,6 +30,7 @@ impl EphemeralPrivateKey {
     ///  Our implementation ignores the `SecureRandom` parameter.
     // # FIPS
     // Use this function with one of the following algorithms:
+    // * `ECDH_P224`
     // * `ECDH_P256`
     // * `ECDH_P384`
     // * `ECDH_P521`
@@ -88,6 +89,7 @@ impl EphemeralPrivateKey {
 /// returns.
 // # FIPS
 // Use this function with one of the following key algorithms:
+// * `ECDH_P224`
 // * `ECDH_P256`
 // * `ECDH_P384`
 // * `ECDH_P521`
@@ -368,7 +370,11 @@ mod tests {
                 );
             }
             (
-                AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521,
+                AlgorithmID::ECDH_P224
+                | AlgorithmID::ECDH_P256
+                | AlgorithmID::ECDH_P256K1
+                | AlgorithmID::ECDH_P384
+                | AlgorithmID::ECDH_P521,
                 "COMPRESSED",
             ) => {
                 let bin = AsBigEndian::<EcPublicKeyCompressedBin>::as_be_bytes(computed_public)
@@ -376,7 +382,11 @@ mod tests {
                 assert_eq!(expected_public_key_bytes, bin.as_ref());
             }
             (
-                AlgorithmID::ECDH_P256 | AlgorithmID::ECDH_P384 | AlgorithmID::ECDH_P521,
+                AlgorithmID::ECDH_P224
+                | AlgorithmID::ECDH_P256
+                | AlgorithmID::ECDH_P256K1
+                | AlgorithmID::ECDH_P384
+                | AlgorithmID::ECDH_P521,
                 "UNCOMPRESSED" | "",
             ) => {
                 let bin = AsBigEndian::<EcPublicKeyUncompressedBin>::as_be_bytes(computed_public)
@@ -473,8 +483,12 @@ mod tests {
     }
 
     fn alg_from_curve_name(curve_name: &str) -> &'static agreement::Algorithm {
-        if curve_name == "P-256" {
+        if curve_name == "P-224" {
+            &agreement::ECDH_P224
+        } else if curve_name == "P-256" {
             &agreement::ECDH_P256
+        } else if curve_name == "secp256k1" {
+            &agreement::ECDH_P256K1
         } else if curve_name == "P-384" {
             &agreement::ECDH_P384
         } else if curve_name == "P-521" {
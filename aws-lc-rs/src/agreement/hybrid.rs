@@ -0,0 +1,404 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Hybrid key agreement combining ECDH with ML-KEM, for use as TLS 1.3 key shares.
+//!
+//! The hybrid groups are specified by [draft-kwiatkowski-tls-ecdhe-mlkem]. Each key share and
+//! shared secret is the concatenation of the values produced by the component algorithms, as
+//! described by [draft-ietf-tls-hybrid-design].
+//!
+//! The client generates an [`EphemeralPrivateKey`] and sends its key share to the server. The
+//! server calls [`respond`] with the client's key share, which produces the server's key share and
+//! the shared secret. Finally, the client completes the key agreement by calling
+//! [`EphemeralPrivateKey::agree`] with the server's key share.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::agreement::hybrid::{self, EphemeralPrivateKey, X25519_MLKEM768};
+//!
+//! // The client generates their ephemeral private key, and sends the key share to the server.
+//! let client_private_key = EphemeralPrivateKey::generate(&X25519_MLKEM768)?;
+//! let client_key_share = client_private_key.compute_key_share()?;
+//!
+//! // The server computes their key share and the shared secret from the client's key share.
+//! let (server_key_share, server_secret) =
+//!     hybrid::respond(&X25519_MLKEM768, client_key_share.as_ref())?;
+//!
+//! // The client computes the shared secret from the server's key share.
+//! let client_secret = client_private_key.agree(server_key_share.as_ref())?;
+//!
+//! // The client and server have now arrived to the same secret
+//! assert_eq!(client_secret.as_ref(), server_secret.as_ref());
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//!
+//! [draft-kwiatkowski-tls-ecdhe-mlkem]: https://datatracker.ietf.org/doc/html/draft-kwiatkowski-tls-ecdhe-mlkem-02
+//! [draft-ietf-tls-hybrid-design]: https://datatracker.ietf.org/doc/html/draft-ietf-tls-hybrid-design-10
+
+use crate::agreement::{self, agree, UnparsedPublicKey, ECDH_P256, X25519};
+use crate::error::Unspecified;
+use crate::hex;
+use crate::kem::{self, Ciphertext, DecapsulationKey, EncapsulationKey, SharedSecret, ML_KEM_768};
+use core::fmt;
+use core::fmt::{Debug, Formatter};
+use zeroize::Zeroize;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AlgorithmID {
+    X25519_MLKEM768,
+    SECP256R1_MLKEM768,
+}
+
+/// A hybrid key agreement algorithm.
+pub struct Algorithm {
+    id: AlgorithmID,
+    ecdh: &'static agreement::Algorithm,
+    kem: &'static kem::Algorithm,
+}
+
+impl Algorithm {
+    /// The TLS `NamedGroup` code point assigned to this algorithm.
+    #[must_use]
+    pub fn tls_group_id(&self) -> u16 {
+        match self.id {
+            AlgorithmID::X25519_MLKEM768 => 0x11EC,
+            AlgorithmID::SECP256R1_MLKEM768 => 0x11EB,
+        }
+    }
+
+    /// Length of the key share sent by the client.
+    #[must_use]
+    pub fn client_key_share_len(&self) -> usize {
+        self.kem.encapsulate_key_size() + self.ecdh.id.pub_key_len()
+    }
+
+    /// Length of the key share sent by the server.
+    #[must_use]
+    pub fn server_key_share_len(&self) -> usize {
+        self.kem.ciphertext_size() + self.ecdh.id.pub_key_len()
+    }
+
+    // X25519MLKEM768 places the ML-KEM component first, while SecP256r1MLKEM768 places the ECDH
+    // component first. The same order applies to key shares and the shared secret.
+    #[inline]
+    fn kem_first(&self) -> bool {
+        match self.id {
+            AlgorithmID::X25519_MLKEM768 => true,
+            AlgorithmID::SECP256R1_MLKEM768 => false,
+        }
+    }
+
+    fn split<'a>(&self, value: &'a [u8], kem_len: usize) -> (&'a [u8], &'a [u8]) {
+        if self.kem_first() {
+            value.split_at(kem_len)
+        } else {
+            let (ecdh, kem) = value.split_at(value.len() - kem_len);
+            (kem, ecdh)
+        }
+    }
+
+    fn concatenate(&self, kem: &[u8], ecdh: &[u8]) -> Vec<u8> {
+        let (first, second) = if self.kem_first() {
+            (kem, ecdh)
+        } else {
+            (ecdh, kem)
+        };
+        let mut value = Vec::with_capacity(first.len() + second.len());
+        value.extend_from_slice(first);
+        value.extend_from_slice(second);
+        value
+    }
+}
+
+impl PartialEq for Algorithm {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Algorithm {}
+
+impl Debug for Algorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!("Algorithm {{ {:?} }}", self.id))
+    }
+}
+
+/// X25519 combined with ML-KEM-768, the TLS `X25519MLKEM768` group.
+pub static X25519_MLKEM768: Algorithm = Algorithm {
+    id: AlgorithmID::X25519_MLKEM768,
+    ecdh: &X25519,
+    kem: &ML_KEM_768,
+};
+
+/// ECDH using the P-256 (secp256r1) curve combined with ML-KEM-768, the TLS `SecP256r1MLKEM768`
+/// group.
+///
+/// The ECDH component of the key shares is an uncompressed point.
+pub static SECP256R1_MLKEM768: Algorithm = Algorithm {
+    id: AlgorithmID::SECP256R1_MLKEM768,
+    ecdh: &ECDH_P256,
+    kem: &ML_KEM_768,
+};
+
+/// A key share for a hybrid key agreement.
+#[derive(Clone)]
+pub struct KeyShare(Box<[u8]>);
+
+impl AsRef<[u8]> for KeyShare {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Debug for KeyShare {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!("KeyShare(\"{}\")", hex::encode(&self.0)))
+    }
+}
+
+/// An ephemeral private key for use (only) with [`EphemeralPrivateKey::agree`]. The signature
+/// of `agree` ensures that an `EphemeralPrivateKey` can be used for at most one key agreement.
+pub struct EphemeralPrivateKey {
+    algorithm: &'static Algorithm,
+    ecdh_private_key: agreement::PrivateKey,
+    decapsulation_key: DecapsulationKey,
+}
+
+impl Debug for EphemeralPrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "EphemeralPrivateKey {{ algorithm: {:?} }}",
+            self.algorithm
+        ))
+    }
+}
+
+impl EphemeralPrivateKey {
+    /// Generate a new ephemeral private key for the given algorithm.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn generate(alg: &'static Algorithm) -> Result<Self, Unspecified> {
+        Ok(Self {
+            algorithm: alg,
+            ecdh_private_key: agreement::PrivateKey::generate(alg.ecdh)?,
+            decapsulation_key: DecapsulationKey::generate(alg.kem)?,
+        })
+    }
+
+    /// The algorithm for the private key.
+    #[must_use]
+    pub fn algorithm(&self) -> &'static Algorithm {
+        self.algorithm
+    }
+
+    /// Computes the key share to be sent to the server.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn compute_key_share(&self) -> Result<KeyShare, Unspecified> {
+        let ecdh_public_key = self.ecdh_private_key.compute_public_key()?;
+        let encapsulation_key = self.decapsulation_key.encapsulation_key()?;
+        let encapsulation_key_bytes = encapsulation_key.key_bytes()?;
+        let key_share = self
+            .algorithm
+            .concatenate(encapsulation_key_bytes.as_ref(), ecdh_public_key.as_ref());
+        Ok(KeyShare(key_share.into_boxed_slice()))
+    }
+
+    /// Computes the shared secret from the key share sent by the server.
+    ///
+    /// `server_key_share` must be exactly `algorithm().server_key_share_len()` bytes long.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the server's key share is malformed, or when the operation fails
+    /// due to internal error.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn agree(self, server_key_share: &[u8]) -> Result<SharedSecret, Unspecified> {
+        let alg = self.algorithm;
+        if server_key_share.len() != alg.server_key_share_len() {
+            return Err(Unspecified);
+        }
+        let (ciphertext, ecdh_public_key) = alg.split(server_key_share, alg.kem.ciphertext_size());
+
+        let kem_secret = self
+            .decapsulation_key
+            .decapsulate(Ciphertext::from(ciphertext))?;
+        let mut ecdh_secret = agree(
+            &self.ecdh_private_key,
+            &UnparsedPublicKey::new(alg.ecdh, ecdh_public_key),
+            Unspecified,
+            |secret| Ok(secret.to_vec()),
+        )?;
+
+        let secret = alg.concatenate(kem_secret.as_ref(), &ecdh_secret);
+        ecdh_secret.zeroize();
+        Ok(SharedSecret::new(secret.into_boxed_slice()))
+    }
+}
+
+/// Responds to the key share sent by the client, producing the server's key share and the
+/// shared secret.
+///
+/// `client_key_share` must be exactly `alg.client_key_share_len()` bytes long.
+///
+/// # Errors
+/// `error::Unspecified` if the client's key share is malformed, or when the operation fails
+/// due to internal error.
+pub fn respond(
+    alg: &'static Algorithm,
+    client_key_share: &[u8],
+) -> Result<(KeyShare, SharedSecret), Unspecified> {
+    if client_key_share.len() != alg.client_key_share_len() {
+        return Err(Unspecified);
+    }
+    let (encapsulation_key, ecdh_public_key) =
+        alg.split(client_key_share, alg.kem.encapsulate_key_size());
+
+    let encapsulation_key = EncapsulationKey::new(alg.kem, encapsulation_key)?;
+    let (ciphertext, kem_secret) = encapsulation_key.encapsulate()?;
+
+    let ecdh_private_key = agreement::PrivateKey::generate(alg.ecdh)?;
+    let ecdh_key_share = ecdh_private_key.compute_public_key()?;
+    let mut ecdh_secret = agree(
+        &ecdh_private_key,
+        &UnparsedPublicKey::new(alg.ecdh, ecdh_public_key),
+        Unspecified,
+        |secret| Ok(secret.to_vec()),
+    )?;
+
+    let key_share = alg.concatenate(ciphertext.as_ref(), ecdh_key_share.as_ref());
+    let secret = alg.concatenate(kem_secret.as_ref(), &ecdh_secret);
+    ecdh_secret.zeroize();
+
+    Ok((
+        KeyShare(key_share.into_boxed_slice()),
+        SharedSecret::new(secret.into_boxed_slice()),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{respond, EphemeralPrivateKey, SECP256R1_MLKEM768, X25519_MLKEM768};
+    use crate::agreement::{self, hybrid};
+    use crate::kem::{DecapsulationKey, ML_KEM_768};
+    use crate::{test, test_file};
+
+    #[test]
+    fn hybrid_agreement_tests() {
+        test::run(
+            test_file!("data/hybrid_agreement_tests.txt"),
+            |section, test_case| {
+                let alg = match section {
+                    "X25519MLKEM768" => &X25519_MLKEM768,
+                    "SecP256r1MLKEM768" => &SECP256R1_MLKEM768,
+                    _ => unreachable!(),
+                };
+                let ecdh_private_key = test_case.consume_bytes("EcdhPrivateKey");
+                let decapsulation_key = test_case.consume_bytes("DecapsulationKey");
+                let client_key_share = test_case.consume_bytes("ClientKeyShare");
+                let server_key_share = test_case.consume_bytes("ServerKeyShare");
+                let shared_secret = test_case.consume_bytes("SharedSecret");
+
+                let private_key = EphemeralPrivateKey {
+                    algorithm: alg,
+                    ecdh_private_key: agreement::PrivateKey::from_private_key(
+                        alg.ecdh,
+                        &ecdh_private_key,
+                    )?,
+                    decapsulation_key: DecapsulationKey::new(&ML_KEM_768, &decapsulation_key)?,
+                };
+                assert_eq!(
+                    private_key.compute_key_share()?.as_ref(),
+                    client_key_share.as_slice()
+                );
+                let secret = private_key.agree(&server_key_share)?;
+                assert_eq!(secret.as_ref(), shared_secret.as_slice());
+
+                Ok(())
+            },
+        );
+    }
+
+    #[test]
+    fn hybrid_agreement_e2e() {
+        for alg in [&X25519_MLKEM768, &SECP256R1_MLKEM768] {
+            let client_private_key = EphemeralPrivateKey::generate(alg).unwrap();
+            assert_eq!(client_private_key.algorithm(), alg);
+
+            let client_key_share = client_private_key.compute_key_share().unwrap();
+            assert_eq!(client_key_share.as_ref().len(), alg.client_key_share_len());
+
+            let (server_key_share, server_secret) =
+                respond(alg, client_key_share.as_ref()).unwrap();
+            assert_eq!(server_key_share.as_ref().len(), alg.server_key_share_len());
+            assert_eq!(server_secret.as_ref().len(), 64);
+
+            let client_secret = client_private_key.agree(server_key_share.as_ref()).unwrap();
+            assert_eq!(client_secret.as_ref(), server_secret.as_ref());
+        }
+    }
+
+    #[test]
+    fn hybrid_agreement_wrong_sizes() {
+        for alg in [&X25519_MLKEM768, &SECP256R1_MLKEM768] {
+            let client_private_key = EphemeralPrivateKey::generate(alg).unwrap();
+            let client_key_share = client_private_key.compute_key_share().unwrap();
+            let client_key_share = client_key_share.as_ref();
+
+            assert!(respond(alg, &client_key_share[1..]).is_err());
+            let mut too_long = client_key_share.to_vec();
+            too_long.push(0);
+            assert!(respond(alg, &too_long).is_err());
+
+            let (server_key_share, _) = respond(alg, client_key_share).unwrap();
+            let server_key_share = server_key_share.as_ref();
+            assert!(client_private_key
+                .agree(&server_key_share[..server_key_share.len() - 1])
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn hybrid_agreement_invalid_ecdh_share() {
+        // The all-zero X25519 public key results in an all-zero shared secret, and
+        // the all-zero P-256 point is not on the curve.
+        for alg in [&X25519_MLKEM768, &SECP256R1_MLKEM768] {
+            let client_private_key = EphemeralPrivateKey::generate(alg).unwrap();
+            let client_key_share = client_private_key.compute_key_share().unwrap();
+            let mut client_key_share = client_key_share.as_ref().to_vec();
+            let ecdh_len = alg.ecdh.id.pub_key_len();
+            let ecdh_range = if alg.kem_first() {
+                client_key_share.len() - ecdh_len..client_key_share.len()
+            } else {
+                0..ecdh_len
+            };
+            client_key_share[ecdh_range].fill(0);
+            assert!(respond(alg, &client_key_share).is_err());
+        }
+    }
+
+    #[test]
+    fn hybrid_algorithm_traits() {
+        assert_eq!(X25519_MLKEM768.tls_group_id(), 0x11EC);
+        assert_eq!(SECP256R1_MLKEM768.tls_group_id(), 0x11EB);
+        assert_eq!(X25519_MLKEM768.client_key_share_len(), 1216);
+        assert_eq!(X25519_MLKEM768.server_key_share_len(), 1120);
+        assert_eq!(SECP256R1_MLKEM768.client_key_share_len(), 1249);
+        assert_eq!(SECP256R1_MLKEM768.server_key_share_len(), 1153);
+        assert_ne!(X25519_MLKEM768, SECP256R1_MLKEM768);
+        assert_eq!(
+            format!("{:?}", hybrid::X25519_MLKEM768),
+            "Algorithm { X25519_MLKEM768 }"
+        );
+        let private_key = EphemeralPrivateKey::generate(&SECP256R1_MLKEM768).unwrap();
+        assert_eq!(
+            format!("{private_key:?}"),
+            "EphemeralPrivateKey { algorithm: Algorithm { SECP256R1_MLKEM768 } }"
+        );
+    }
+}
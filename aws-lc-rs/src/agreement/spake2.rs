@@ -0,0 +1,515 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! SPAKE2 password-authenticated key exchange over Curve25519.
+//!
+//! SPAKE2 allows two parties that share a low-entropy password, such as a short PIN, to agree on
+//! a high-entropy shared key. An eavesdropper learns nothing about the password, and an active
+//! attacker can only test one password guess per exchange.
+//!
+//! The two parties take the roles of [`Role::Alice`] and [`Role::Bob`], which must be agreed in
+//! advance. Each party creates a [`Spake2`] context with its role and the identities of both
+//! parties, generates a [`Message`] from the password and sends it to its peer, then processes the
+//! peer's message to obtain a [`SessionKey`]. Every step consumes the preceding state, so a
+//! context can only be used for a single exchange. The contexts are zeroized when dropped.
+//!
+//! The exchange is the one implemented by AWS-LC and BoringSSL, which predates [RFC 9382] and
+//! uses different group elements, so it does not interoperate with RFC 9382 implementations.
+//!
+//! # Key Confirmation
+//!
+//! Both parties arrive at the same [`SessionKey`] only if they used the same password and
+//! identities. A party learns that its peer holds the same key by exchanging confirmation tags.
+//!
+//! The confirmation scheme is specific to this crate. It is modelled on [RFC 9382], but is not
+//! the scheme of RFC 9382, whose transcript also contains the shared group element and the
+//! password scalar, which AWS-LC does not expose. The 64 byte secret produced by the exchange is
+//! split into `Ke || Ka`, where `Ke` is the session key, and the confirmation keys are derived as
+//! `KcA || KcB = HKDF-SHA512(salt = nil, IKM = Ka, info = "ConfirmationKeys")`. Both tags are
+//! computed over the transcript
+//! `TT = len(A) || A || len(B) || B || len(pA) || pA || len(pB) || pB`, where `A` and `B` are the
+//! identities of Alice and Bob, `pA` and `pB` are their messages, and each length is encoded as
+//! an 8 byte little-endian integer. Alice sends `HMAC-SHA512(KcA, TT)` and Bob sends
+//! `HMAC-SHA512(KcB, TT)`.
+//!
+//! # FIPS
+//! SPAKE2 is not a FIPS approved algorithm, and the APIs offered in this module
+//! must not be used.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::agreement::spake2::{Role, Spake2};
+//!
+//! let alice = Spake2::new(Role::Alice, b"device", b"phone")?;
+//! let bob = Spake2::new(Role::Bob, b"phone", b"device")?;
+//!
+//! let (alice, alice_message) = alice.generate_message(b"1234")?;
+//! let (bob, bob_message) = bob.generate_message(b"1234")?;
+//!
+//! let alice_key = alice.process_message(bob_message.as_ref())?;
+//! let bob_key = bob.process_message(alice_message.as_ref())?;
+//!
+//! // Each party confirms that its peer derived the same key.
+//! let alice_tag = alice_key.confirmation_tag();
+//! let bob_tag = bob_key.confirmation_tag();
+//! bob_key.verify_confirmation_tag(alice_tag.as_ref())?;
+//! alice_key.verify_confirmation_tag(bob_tag.as_ref())?;
+//!
+//! assert_eq!(alice_key.as_ref(), bob_key.as_ref());
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//!
+//! [RFC 9382]: https://www.rfc-editor.org/rfc/rfc9382
+
+use crate::error::Unspecified;
+use crate::ptr::LcPtr;
+use crate::{hex, hkdf, hmac};
+use aws_lc::{
+    spake2_role_t, spake2_role_t_spake2_role_alice, spake2_role_t_spake2_role_bob, SPAKE2_CTX_new,
+    SPAKE2_generate_msg, SPAKE2_process_msg, SPAKE2_CTX, SPAKE2_MAX_KEY_SIZE, SPAKE2_MAX_MSG_SIZE,
+};
+use core::fmt;
+use core::fmt::{Debug, Formatter};
+use zeroize::Zeroize;
+
+/// The length of a SPAKE2 message.
+pub const MESSAGE_LEN: usize = SPAKE2_MAX_MSG_SIZE as usize;
+
+/// The length of a SPAKE2 session key.
+pub const SESSION_KEY_LEN: usize = SECRET_LEN / 2;
+
+const SECRET_LEN: usize = SPAKE2_MAX_KEY_SIZE as usize;
+
+const CONFIRMATION_KEYS_INFO: &[u8] = b"ConfirmationKeys";
+
+/// The role of a party in a SPAKE2 exchange. The two parties must take different roles.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    /// The party commonly known as `A`.
+    Alice,
+    /// The party commonly known as `B`.
+    Bob,
+}
+
+impl Role {
+    fn as_spake2_role(self) -> spake2_role_t {
+        match self {
+            Role::Alice => spake2_role_t_spake2_role_alice,
+            Role::Bob => spake2_role_t_spake2_role_bob,
+        }
+    }
+}
+
+/// A SPAKE2 message, to be sent to the peer.
+#[derive(Clone)]
+pub struct Message([u8; MESSAGE_LEN]);
+
+impl AsRef<[u8]> for Message {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Debug for Message {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_tuple("Message")
+            .field(&hex::encode(self.0))
+            .finish()
+    }
+}
+
+/// A SPAKE2 context that has not yet generated its message.
+pub struct Spake2 {
+    role: Role,
+    ctx: LcPtr<SPAKE2_CTX>,
+    my_name: Vec<u8>,
+    their_name: Vec<u8>,
+}
+
+impl Debug for Spake2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("Spake2")
+            .field("role", &self.role)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Spake2 {
+    /// Constructs a new context for the party with the given `role`.
+    ///
+    /// `my_name` and `their_name` identify the two parties and may be empty. They are bound into
+    /// the session key, so each party's `my_name` must be its peer's `their_name`.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn new(role: Role, my_name: &[u8], their_name: &[u8]) -> Result<Self, Unspecified> {
+        let ctx = LcPtr::new(unsafe {
+            SPAKE2_CTX_new(
+                role.as_spake2_role(),
+                my_name.as_ptr(),
+                my_name.len(),
+                their_name.as_ptr(),
+                their_name.len(),
+            )
+        })?;
+        Ok(Self {
+            role,
+            ctx,
+            my_name: my_name.to_vec(),
+            their_name: their_name.to_vec(),
+        })
+    }
+
+    /// The role of this party.
+    #[must_use]
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Generates this party's message from the shared `password`.
+    ///
+    /// Returns the context awaiting the peer's message, along with the message to be sent to the
+    /// peer.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn generate_message(
+        mut self,
+        password: &[u8],
+    ) -> Result<(Spake2Pending, Message), Unspecified> {
+        let mut message = [0u8; MESSAGE_LEN];
+        let mut out_len = 0;
+        if 1 != unsafe {
+            SPAKE2_generate_msg(
+                *self.ctx.as_mut(),
+                message.as_mut_ptr(),
+                &mut out_len,
+                message.len(),
+                password.as_ptr(),
+                password.len(),
+            )
+        } || out_len != MESSAGE_LEN
+        {
+            return Err(Unspecified);
+        }
+        Ok((
+            Spake2Pending {
+                role: self.role,
+                ctx: self.ctx,
+                my_name: self.my_name,
+                their_name: self.their_name,
+                my_message: message,
+            },
+            Message(message),
+        ))
+    }
+}
+
+/// A SPAKE2 context that has generated its message and is awaiting the peer's message.
+pub struct Spake2Pending {
+    role: Role,
+    ctx: LcPtr<SPAKE2_CTX>,
+    my_name: Vec<u8>,
+    their_name: Vec<u8>,
+    my_message: [u8; MESSAGE_LEN],
+}
+
+impl Debug for Spake2Pending {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("Spake2Pending")
+            .field("role", &self.role)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Spake2Pending {
+    /// The role of this party.
+    #[must_use]
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Processes the peer's message, producing the session key.
+    ///
+    /// A session key is produced even if the peer used a different password, in which case the
+    /// two parties hold different keys. Use [`SessionKey::confirmation_tag`] and
+    /// [`SessionKey::verify_confirmation_tag`] to detect this before relying on the key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the peer's message is malformed, or when the operation fails due
+    /// to internal error.
+    pub fn process_message(mut self, their_message: &[u8]) -> Result<SessionKey, Unspecified> {
+        if their_message.len() != MESSAGE_LEN {
+            return Err(Unspecified);
+        }
+        let mut secret = [0u8; SECRET_LEN];
+        let mut out_len = 0;
+        if 1 != unsafe {
+            SPAKE2_process_msg(
+                *self.ctx.as_mut(),
+                secret.as_mut_ptr(),
+                &mut out_len,
+                secret.len(),
+                their_message.as_ptr(),
+                their_message.len(),
+            )
+        } || out_len != SECRET_LEN
+        {
+            secret.zeroize();
+            return Err(Unspecified);
+        }
+
+        let mine = (self.my_name.as_slice(), &self.my_message[..]);
+        let theirs = (self.their_name.as_slice(), their_message);
+        let transcript = match self.role {
+            Role::Alice => transcript(mine, theirs),
+            Role::Bob => transcript(theirs, mine),
+        };
+        let session_key = SessionKey::new(self.role, &secret, transcript);
+        secret.zeroize();
+        session_key
+    }
+}
+
+// Encodes the transcript `TT` confirmed by both parties, given the identity and message of Alice
+// and of Bob.
+fn transcript(alice: (&[u8], &[u8]), bob: (&[u8], &[u8])) -> Vec<u8> {
+    let (alice_name, alice_message) = alice;
+    let (bob_name, bob_message) = bob;
+    let mut transcript = Vec::new();
+    for value in [alice_name, bob_name, alice_message, bob_message] {
+        transcript.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        transcript.extend_from_slice(value);
+    }
+    transcript
+}
+
+/// The session key produced by a SPAKE2 exchange, along with its confirmation keys.
+///
+/// The keys are zeroized when dropped.
+pub struct SessionKey {
+    role: Role,
+    key: [u8; SESSION_KEY_LEN],
+    my_confirmation_key: hmac::Key,
+    their_confirmation_key: hmac::Key,
+    transcript: Vec<u8>,
+}
+
+impl SessionKey {
+    fn new(
+        role: Role,
+        secret: &[u8; SECRET_LEN],
+        transcript: Vec<u8>,
+    ) -> Result<Self, Unspecified> {
+        let (ke, ka) = secret.split_at(SESSION_KEY_LEN);
+        let mut key = [0u8; SESSION_KEY_LEN];
+        key.copy_from_slice(ke);
+
+        let mut confirmation_keys = [0u8; SECRET_LEN];
+        hkdf::Salt::new(hkdf::HKDF_SHA512, &[])
+            .extract(ka)
+            .expand(&[CONFIRMATION_KEYS_INFO], hkdf::HKDF_SHA512)?
+            .fill(&mut confirmation_keys)?;
+        let (kc_a, kc_b) = confirmation_keys.split_at(SECRET_LEN / 2);
+        let kc_a = hmac::Key::new(hmac::HMAC_SHA512, kc_a);
+        let kc_b = hmac::Key::new(hmac::HMAC_SHA512, kc_b);
+        confirmation_keys.zeroize();
+
+        let (my_confirmation_key, their_confirmation_key) = match role {
+            Role::Alice => (kc_a, kc_b),
+            Role::Bob => (kc_b, kc_a),
+        };
+        Ok(Self {
+            role,
+            key,
+            my_confirmation_key,
+            their_confirmation_key,
+            transcript,
+        })
+    }
+
+    /// The role of this party.
+    #[must_use]
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    /// Computes this party's confirmation tag over the transcript of the exchange, to be sent to
+    /// the peer.
+    #[must_use]
+    pub fn confirmation_tag(&self) -> hmac::Tag {
+        hmac::sign(&self.my_confirmation_key, &self.transcript)
+    }
+
+    /// Verifies the confirmation tag sent by the peer, in constant time.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the tag is not valid, which indicates that the peer used a
+    /// different password or identities.
+    pub fn verify_confirmation_tag(&self, tag: &[u8]) -> Result<(), Unspecified> {
+        hmac::verify(&self.their_confirmation_key, &self.transcript, tag)
+    }
+}
+
+impl AsRef<[u8]> for SessionKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl Drop for SessionKey {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+impl Debug for SessionKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("SessionKey")
+            .field("role", &self.role)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Role, SessionKey, Spake2, MESSAGE_LEN, SESSION_KEY_LEN};
+
+    fn exchange(
+        alice: (&[u8], &[u8], &[u8]),
+        bob: (&[u8], &[u8], &[u8]),
+    ) -> (SessionKey, SessionKey) {
+        let (alice_name, alice_peer, alice_password) = alice;
+        let (bob_name, bob_peer, bob_password) = bob;
+        let alice = Spake2::new(Role::Alice, alice_name, alice_peer).unwrap();
+        let bob = Spake2::new(Role::Bob, bob_name, bob_peer).unwrap();
+
+        let (alice, alice_message) = alice.generate_message(alice_password).unwrap();
+        let (bob, bob_message) = bob.generate_message(bob_password).unwrap();
+        assert_eq!(MESSAGE_LEN, alice_message.as_ref().len());
+        assert_ne!(alice_message.as_ref(), bob_message.as_ref());
+
+        (
+            alice.process_message(bob_message.as_ref()).unwrap(),
+            bob.process_message(alice_message.as_ref()).unwrap(),
+        )
+    }
+
+    fn confirms(alice_key: &SessionKey, bob_key: &SessionKey) -> bool {
+        let alice_confirmed = alice_key
+            .verify_confirmation_tag(bob_key.confirmation_tag().as_ref())
+            .is_ok();
+        let bob_confirmed = bob_key
+            .verify_confirmation_tag(alice_key.confirmation_tag().as_ref())
+            .is_ok();
+        assert_eq!(alice_confirmed, bob_confirmed);
+        alice_confirmed
+    }
+
+    #[test]
+    fn spake2_same_password() {
+        for (alice_name, bob_name) in [(&b"alice"[..], &b"bob"[..]), (&[][..], &[][..])] {
+            let (alice_key, bob_key) = exchange(
+                (alice_name, bob_name, b"password"),
+                (bob_name, alice_name, b"password"),
+            );
+            assert_eq!(SESSION_KEY_LEN, alice_key.as_ref().len());
+            assert_eq!(alice_key.as_ref(), bob_key.as_ref());
+            assert!(confirms(&alice_key, &bob_key));
+
+            // Both parties confirm the same transcript, in which Alice's values come first.
+            assert_eq!(alice_key.transcript, bob_key.transcript);
+            let mut prefix = (alice_name.len() as u64).to_le_bytes().to_vec();
+            prefix.extend_from_slice(alice_name);
+            prefix.extend_from_slice(&(bob_name.len() as u64).to_le_bytes());
+            prefix.extend_from_slice(bob_name);
+            assert!(alice_key.transcript.starts_with(&prefix));
+
+            // The confirmation tags of the two parties differ, so a tag can't be reflected.
+            assert_ne!(
+                alice_key.confirmation_tag().as_ref(),
+                bob_key.confirmation_tag().as_ref()
+            );
+            assert!(alice_key
+                .verify_confirmation_tag(alice_key.confirmation_tag().as_ref())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn spake2_wrong_password() {
+        let (alice_key, bob_key) = exchange(
+            (b"alice", b"bob", b"password"),
+            (b"bob", b"alice", b"wrong password"),
+        );
+        assert_ne!(alice_key.as_ref(), bob_key.as_ref());
+        assert!(!confirms(&alice_key, &bob_key));
+    }
+
+    #[test]
+    fn spake2_wrong_names() {
+        let (alice_key, bob_key) = exchange(
+            (b"alice", b"bob", b"password"),
+            (b"bob", b"charlie", b"password"),
+        );
+        assert_ne!(alice_key.as_ref(), bob_key.as_ref());
+        assert!(!confirms(&alice_key, &bob_key));
+    }
+
+    #[test]
+    fn spake2_same_role() {
+        let alice = Spake2::new(Role::Alice, b"alice", b"bob").unwrap();
+        let bob = Spake2::new(Role::Alice, b"bob", b"alice").unwrap();
+        let (alice, alice_message) = alice.generate_message(b"password").unwrap();
+        let (bob, bob_message) = bob.generate_message(b"password").unwrap();
+        let alice_key = alice.process_message(bob_message.as_ref()).unwrap();
+        let bob_key = bob.process_message(alice_message.as_ref()).unwrap();
+        assert_ne!(alice_key.as_ref(), bob_key.as_ref());
+    }
+
+    #[test]
+    fn spake2_corrupted_message() {
+        let alice = Spake2::new(Role::Alice, b"alice", b"bob").unwrap();
+        let bob = Spake2::new(Role::Bob, b"bob", b"alice").unwrap();
+        let (alice, alice_message) = alice.generate_message(b"password").unwrap();
+        let (bob, bob_message) = bob.generate_message(b"password").unwrap();
+
+        let mut corrupted = bob_message.as_ref().to_vec();
+        corrupted[0] ^= 1;
+        if let Ok(alice_key) = alice.process_message(&corrupted) {
+            let bob_key = bob.process_message(alice_message.as_ref()).unwrap();
+            assert_ne!(alice_key.as_ref(), bob_key.as_ref());
+            assert!(!confirms(&alice_key, &bob_key));
+        }
+    }
+
+    #[test]
+    fn spake2_wrong_message_length() {
+        let alice = Spake2::new(Role::Alice, b"alice", b"bob").unwrap();
+        let bob = Spake2::new(Role::Bob, b"bob", b"alice").unwrap();
+        let (alice, _) = alice.generate_message(b"password").unwrap();
+        let (_, bob_message) = bob.generate_message(b"password").unwrap();
+        assert!(alice
+            .process_message(&bob_message.as_ref()[..MESSAGE_LEN - 1])
+            .is_err());
+    }
+
+    #[test]
+    fn spake2_debug() {
+        let alice = Spake2::new(Role::Alice, b"alice", b"bob").unwrap();
+        assert_eq!(Role::Alice, alice.role());
+        assert_eq!("Spake2 { role: Alice, .. }", format!("{alice:?}"));
+        let (alice, _) = alice.generate_message(b"password").unwrap();
+        assert_eq!("Spake2Pending { role: Alice, .. }", format!("{alice:?}"));
+
+        let bob = Spake2::new(Role::Bob, b"bob", b"alice").unwrap();
+        let (_, bob_message) = bob.generate_message(b"password").unwrap();
+        assert!(format!("{bob_message:?}").starts_with("Message(\""));
+        let key = alice.process_message(bob_message.as_ref()).unwrap();
+        assert_eq!(Role::Alice, key.role());
+        assert_eq!("SessionKey { role: Alice, .. }", format!("{key:?}"));
+    }
+}
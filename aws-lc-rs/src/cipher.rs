@@ -83,6 +83,142 @@
 //! # }
 //! ```
 //!
+//! ### AES-128 CFB 128-bit
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{DecryptingKey, EncryptingKey, UnboundCipherKey, AES_128};
+//!
+//! let original_message = "This is a secret message!".as_bytes();
+//! let mut in_out_buffer = Vec::from(original_message);
+//!
+//! let key_bytes: &[u8] = &[
+//!     0xff, 0x0b, 0xe5, 0x84, 0x64, 0x0b, 0x00, 0xc8, 0x90, 0x7a, 0x4b, 0xbf, 0x82, 0x7c, 0xb6,
+//!     0xd1,
+//! ];
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let encrypting_key = EncryptingKey::cfb128(key)?;
+//! let context = encrypting_key.encrypt(&mut in_out_buffer)?;
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let decrypting_key = DecryptingKey::cfb128(key)?;
+//! let plaintext = decrypting_key.decrypt(&mut in_out_buffer, context)?;
+//! assert_eq!(original_message, plaintext);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### AES-128 CBC with ciphertext stealing
+//!
+//! The ciphertext stealing modes (CS1, CS2 and CS3 of the Addendum to NIST SP 800-38A) produce
+//! a ciphertext of the same length as the plaintext, which must be at least one block long.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{DecryptingKey, EncryptingKey, UnboundCipherKey, AES_128};
+//!
+//! let original_message = "This is a secret message!".as_bytes();
+//! let mut in_out_buffer = Vec::from(original_message);
+//!
+//! let key_bytes: &[u8] = &[
+//!     0xff, 0x0b, 0xe5, 0x84, 0x64, 0x0b, 0x00, 0xc8, 0x90, 0x7a, 0x4b, 0xbf, 0x82, 0x7c, 0xb6,
+//!     0xd1,
+//! ];
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let encrypting_key = EncryptingKey::cbc_cs3(key)?;
+//! let context = encrypting_key.encrypt(&mut in_out_buffer)?;
+//! assert_eq!(original_message.len(), in_out_buffer.len());
+//!
+//! let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+//! let decrypting_key = DecryptingKey::cbc_cs3(key)?;
+//! let plaintext = decrypting_key.decrypt(&mut in_out_buffer, context)?;
+//! assert_eq!(original_message, plaintext);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### AES-256 XTS
+//!
+//! XTS is intended for encrypting storage, where each data unit (e.g. a disk sector) is
+//! encrypted under a tweak derived from its position. Data units need not be a multiple of
+//! the block length, but must be at least one block long.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{
+//!     DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, UnboundCipherKey,
+//!     AES_256_XTS,
+//! };
+//!
+//! let original_sector = [0x5au8; 520];
+//! let mut in_out_buffer = Vec::from(original_sector.as_slice());
+//!
+//! // The data key and the tweak key, which must differ.
+//! let mut key_bytes = [0u8; 64];
+//! key_bytes[..32].copy_from_slice(&[0x11; 32]);
+//! key_bytes[32..].copy_from_slice(&[0x22; 32]);
+//!
+//! let key = UnboundCipherKey::new(&AES_256_XTS, &key_bytes)?;
+//! let encrypting_key = EncryptingKey::xts(key)?;
+//! encrypting_key.less_safe_encrypt(&mut in_out_buffer, EncryptionContext::xts_sector(42))?;
+//!
+//! let key = UnboundCipherKey::new(&AES_256_XTS, &key_bytes)?;
+//! let decrypting_key = DecryptingKey::xts(key)?;
+//! let plaintext = decrypting_key.decrypt(&mut in_out_buffer, DecryptionContext::xts_sector(42))?;
+//! assert_eq!(original_sector.as_slice(), plaintext);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! ### ChaCha20
+//!
+//! ChaCha20 and XChaCha20 keys are used in CTR mode. The context holds the nonce and the
+//! counter of the initial keystream block.
+//!
+//! ```rust
+//! # use std::error::Error;
+//! #
+//! # fn main() -> Result<(), Box<dyn Error>> {
+//! use aws_lc_rs::cipher::{
+//!     DecryptingKey, EncryptingKey, EncryptionContext, UnboundCipherKey, CHACHA20,
+//! };
+//! use aws_lc_rs::iv::FixedLength;
+//!
+//! let original_message = "This is a secret message!".as_bytes();
+//! let mut in_out_buffer = Vec::from(original_message);
+//!
+//! let key_bytes: &[u8] = &[
+//!     0xa5, 0xf3, 0x3c, 0x8e, 0x1c, 0x5d, 0x9e, 0x2f, 0x57, 0x60, 0x6b, 0x42, 0x0d, 0x8c, 0xc1,
+//!     0x77, 0x2e, 0x04, 0x93, 0xd8, 0x51, 0x36, 0xa9, 0xeb, 0x7d, 0x14, 0x0f, 0x6a, 0xb2, 0xc0,
+//!     0x38, 0x9e,
+//! ];
+//! let nonce = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x4a, 0x00, 0x00, 0x00, 0x00];
+//!
+//! let key = UnboundCipherKey::new(&CHACHA20, key_bytes)?;
+//! let encrypting_key = EncryptingKey::ctr(key)?;
+//! let context = EncryptionContext::Nonce96Counter32(FixedLength::from(nonce), 1);
+//! let context = encrypting_key.less_safe_encrypt(&mut in_out_buffer, context)?;
+//!
+//! let key = UnboundCipherKey::new(&CHACHA20, key_bytes)?;
+//! let decrypting_key = DecryptingKey::ctr(key)?;
+//! let plaintext = decrypting_key.decrypt(&mut in_out_buffer, context)?;
+//! assert_eq!(original_message, plaintext);
+//! #
+//! # Ok(())
+//! # }
+//! ```
+//!
 //! ### AES-128 CBC Streaming Cipher
 //!
 //! ```rust
@@ -192,12 +328,17 @@
 pub(crate) mod aes;
 pub(crate) mod block;
 pub(crate) mod chacha;
+mod cts;
 pub(crate) mod key;
 mod padded;
 mod streaming;
+mod xts;
 
 pub use padded::{PaddedBlockDecryptingKey, PaddedBlockEncryptingKey};
-pub use streaming::{BufferUpdate, StreamingDecryptingKey, StreamingEncryptingKey};
+pub use streaming::{
+    BufferUpdate, DecryptingReader, DecryptingWriter, EncryptingReader, EncryptingWriter,
+    StreamingDecryptingKey, StreamingEncryptingKey,
+};
 
 use crate::buffer::Buffer;
 use crate::error::Unspecified;
@@ -205,29 +346,58 @@ use crate::fips::indicator_check;
 use crate::hkdf;
 use crate::hkdf::KeyType;
 use crate::iv::{FixedLength, IV_LEN_128_BIT};
-use crate::ptr::ConstPointer;
+use crate::ptr::{ConstPointer, LcPtr};
 use aws_lc::{
-    AES_cbc_encrypt, AES_ctr128_encrypt, EVP_aes_128_cbc, EVP_aes_128_ctr, EVP_aes_256_cbc,
-    EVP_aes_256_ctr, AES_DECRYPT, AES_ENCRYPT, AES_KEY, EVP_CIPHER,
+    AES_cbc_encrypt, AES_cfb128_encrypt, AES_ctr128_encrypt, AES_ofb128_encrypt,
+    EVP_CIPHER_CTX_copy, EVP_CIPHER_CTX_new, EVP_CipherInit_ex, EVP_CipherUpdate, EVP_aes_128_cbc,
+    EVP_aes_128_cfb128, EVP_aes_128_cfb8, EVP_aes_128_ctr, EVP_aes_128_ofb, EVP_aes_192_cbc,
+    EVP_aes_192_cfb128, EVP_aes_192_cfb8, EVP_aes_192_ctr, EVP_aes_192_ofb, EVP_aes_256_cbc,
+    EVP_aes_256_cfb128, EVP_aes_256_cfb8, EVP_aes_256_ctr, EVP_aes_256_ofb, EVP_aes_256_xts,
+    AES_DECRYPT, AES_ENCRYPT, AES_KEY, EVP_CIPHER, EVP_CIPHER_CTX,
 };
 use core::fmt::Debug;
 use core::mem::MaybeUninit;
+use core::ptr::{null, null_mut};
 use key::SymmetricCipherKey;
 use zeroize::Zeroize;
 
 /// The number of bytes in an AES 128-bit key
 pub const AES_128_KEY_LEN: usize = 16;
 
+/// The number of bytes in an AES 192-bit key
+pub const AES_192_KEY_LEN: usize = 24;
+
 /// The number of bytes in an AES 256-bit key
 pub const AES_256_KEY_LEN: usize = 32;
 
-const MAX_CIPHER_KEY_LEN: usize = AES_256_KEY_LEN;
+/// The number of bytes in an AES-256-XTS key, which is two AES 256-bit keys.
+pub const AES_256_XTS_KEY_LEN: usize = 2 * AES_256_KEY_LEN;
+
+const MAX_CIPHER_KEY_LEN: usize = AES_256_XTS_KEY_LEN;
 
 /// The number of bytes for an AES-CBC initialization vector (IV)
 pub const AES_CBC_IV_LEN: usize = 16;
 
 /// The number of bytes for an AES-CTR initialization vector (IV)
 pub const AES_CTR_IV_LEN: usize = 16;
+
+/// The number of bytes for an AES-CFB initialization vector (IV)
+pub const AES_CFB_IV_LEN: usize = 16;
+
+/// The number of bytes for an AES-OFB initialization vector (IV)
+pub const AES_OFB_IV_LEN: usize = 16;
+
+/// The number of bytes for an AES-XTS tweak
+pub const AES_XTS_TWEAK_LEN: usize = 16;
+
+/// The number of bytes in a ChaCha20 or XChaCha20 key.
+pub const CHACHA20_KEY_LEN: usize = chacha::KEY_LEN;
+
+/// The number of bytes for a ChaCha20 nonce.
+pub const CHACHA20_NONCE_LEN: usize = chacha::NONCE_LEN;
+
+/// The number of bytes for an XChaCha20 nonce.
+pub const XCHACHA20_NONCE_LEN: usize = chacha::XCHACHA20_NONCE_LEN;
 const AES_BLOCK_LEN: usize = 16;
 
 const MAX_CIPHER_BLOCK_LEN: usize = AES_BLOCK_LEN;
@@ -241,18 +411,73 @@ pub enum OperatingMode {
 
     /// Counter (CTR) mode.
     CTR,
+
+    /// XEX-based tweaked-codebook mode with ciphertext stealing (XTS), as specified by
+    /// IEEE 1619 and NIST SP 800-38E.
+    XTS,
+
+    /// Cipher feedback mode with an 8-bit segment size (CFB8).
+    CFB8,
+
+    /// Cipher feedback mode with a 128-bit segment size (CFB128).
+    CFB128,
+
+    /// Output feedback (OFB) mode.
+    OFB,
+
+    /// Cipher block chaining mode with ciphertext stealing, variant CS1 of the Addendum to
+    /// NIST SP 800-38A. A final partial ciphertext block precedes the last complete block.
+    #[allow(non_camel_case_types)]
+    CBC_CS1,
+
+    /// Cipher block chaining mode with ciphertext stealing, variant CS2 of the Addendum to
+    /// NIST SP 800-38A. The last two ciphertext blocks are swapped only when the final block
+    /// is partial.
+    #[allow(non_camel_case_types)]
+    CBC_CS2,
+
+    /// Cipher block chaining mode with ciphertext stealing, variant CS3 of the Addendum to
+    /// NIST SP 800-38A, as used by Kerberos (RFC 3962). The last two ciphertext blocks are
+    /// always swapped.
+    #[allow(non_camel_case_types)]
+    CBC_CS3,
 }
 
 impl OperatingMode {
-    #[allow(dead_code)]
-    fn evp_cipher(&self, algorithm: &Algorithm) -> ConstPointer<EVP_CIPHER> {
-        ConstPointer::new(match (self, algorithm.id) {
+    /// Whether this is one of the CBC ciphertext stealing modes.
+    fn is_cbc_cts(self) -> bool {
+        matches!(
+            self,
+            OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3
+        )
+    }
+
+    // The ciphertext stealing modes are built on plain CBC, with the padding disabled.
+    fn evp_cipher(self, algorithm: &Algorithm) -> Result<ConstPointer<EVP_CIPHER>, Unspecified> {
+        let mode = if self.is_cbc_cts() {
+            OperatingMode::CBC
+        } else {
+            self
+        };
+        Ok(ConstPointer::new(match (mode, algorithm.id) {
             (OperatingMode::CBC, AlgorithmId::Aes128) => unsafe { EVP_aes_128_cbc() },
             (OperatingMode::CTR, AlgorithmId::Aes128) => unsafe { EVP_aes_128_ctr() },
+            (OperatingMode::CBC, AlgorithmId::Aes192) => unsafe { EVP_aes_192_cbc() },
+            (OperatingMode::CTR, AlgorithmId::Aes192) => unsafe { EVP_aes_192_ctr() },
             (OperatingMode::CBC, AlgorithmId::Aes256) => unsafe { EVP_aes_256_cbc() },
             (OperatingMode::CTR, AlgorithmId::Aes256) => unsafe { EVP_aes_256_ctr() },
-        })
-        .unwrap()
+            (OperatingMode::CFB8, AlgorithmId::Aes128) => unsafe { EVP_aes_128_cfb8() },
+            (OperatingMode::CFB8, AlgorithmId::Aes192) => unsafe { EVP_aes_192_cfb8() },
+            (OperatingMode::CFB8, AlgorithmId::Aes256) => unsafe { EVP_aes_256_cfb8() },
+            (OperatingMode::CFB128, AlgorithmId::Aes128) => unsafe { EVP_aes_128_cfb128() },
+            (OperatingMode::CFB128, AlgorithmId::Aes192) => unsafe { EVP_aes_192_cfb128() },
+            (OperatingMode::CFB128, AlgorithmId::Aes256) => unsafe { EVP_aes_256_cfb128() },
+            (OperatingMode::OFB, AlgorithmId::Aes128) => unsafe { EVP_aes_128_ofb() },
+            (OperatingMode::OFB, AlgorithmId::Aes192) => unsafe { EVP_aes_192_ofb() },
+            (OperatingMode::OFB, AlgorithmId::Aes256) => unsafe { EVP_aes_256_ofb() },
+            (OperatingMode::XTS, AlgorithmId::Aes256Xts) => unsafe { EVP_aes_256_xts() },
+            _ => return Err(Unspecified),
+        })?)
     }
 }
 
@@ -263,6 +488,24 @@ macro_rules! define_cipher_context {
         pub enum $name {
             /// A 128-bit Initialization Vector.
             Iv128(FixedLength<IV_LEN_128_BIT>),
+
+            /// A 128-bit XTS tweak.
+            Tweak128(FixedLength<AES_XTS_TWEAK_LEN>),
+
+            /// A 96-bit ChaCha20 nonce and the 32-bit counter of the initial keystream block.
+            Nonce96Counter32(FixedLength<CHACHA20_NONCE_LEN>, u32),
+
+            /// A 192-bit XChaCha20 nonce and the 32-bit counter of the initial keystream block.
+            Nonce192Counter32(FixedLength<XCHACHA20_NONCE_LEN>, u32),
+        }
+
+        impl $name {
+            /// Constructs the XTS tweak for the data unit (e.g. disk sector) numbered `sector`,
+            /// encoded as a 128-bit little-endian integer as specified by IEEE 1619.
+            #[must_use]
+            pub fn xts_sector(sector: u128) -> Self {
+                $name::Tweak128(FixedLength::from(sector.to_le_bytes()))
+            }
         }
 
         impl<'a> TryFrom<&'a $name> for &'a [u8] {
@@ -271,6 +514,8 @@ macro_rules! define_cipher_context {
             fn try_from(value: &'a $name) -> Result<Self, Unspecified> {
                 match value {
                     $name::Iv128(iv) => Ok(iv.as_ref()),
+                    $name::Tweak128(tweak) => Ok(tweak.as_ref()),
+                    $name::Nonce96Counter32(..) | $name::Nonce192Counter32(..) => Err(Unspecified),
                 }
             }
         }
@@ -279,6 +524,9 @@ macro_rules! define_cipher_context {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 match self {
                     Self::Iv128(_) => write!(f, "Iv128"),
+                    Self::Tweak128(_) => write!(f, "Tweak128"),
+                    Self::Nonce96Counter32(..) => write!(f, "Nonce96Counter32"),
+                    Self::Nonce192Counter32(..) => write!(f, "Nonce192Counter32"),
                 }
             }
         }
@@ -287,6 +535,13 @@ macro_rules! define_cipher_context {
             fn from(value: $other) -> Self {
                 match value {
                     $other::Iv128(iv) => $name::Iv128(iv),
+                    $other::Tweak128(tweak) => $name::Tweak128(tweak),
+                    $other::Nonce96Counter32(nonce, counter) => {
+                        $name::Nonce96Counter32(nonce, counter)
+                    }
+                    $other::Nonce192Counter32(nonce, counter) => {
+                        $name::Nonce192Counter32(nonce, counter)
+                    }
                 }
             }
         }
@@ -303,8 +558,20 @@ pub enum AlgorithmId {
     /// AES 128-bit
     Aes128,
 
+    /// AES 192-bit
+    Aes192,
+
     /// AES 256-bit
     Aes256,
+
+    /// AES 256-bit in XTS mode, using two AES 256-bit keys.
+    Aes256Xts,
+
+    /// ChaCha20
+    ChaCha20,
+
+    /// XChaCha20
+    XChaCha20,
 }
 
 /// A cipher algorithm.
@@ -322,6 +589,13 @@ pub static AES_128: Algorithm = Algorithm {
     block_len: AES_BLOCK_LEN,
 };
 
+/// AES 192-bit cipher
+pub static AES_192: Algorithm = Algorithm {
+    id: AlgorithmId::Aes192,
+    key_len: AES_192_KEY_LEN,
+    block_len: AES_BLOCK_LEN,
+};
+
 /// AES 256-bit cipher
 pub static AES_256: Algorithm = Algorithm {
     id: AlgorithmId::Aes256,
@@ -329,6 +603,36 @@ pub static AES_256: Algorithm = Algorithm {
     block_len: AES_BLOCK_LEN,
 };
 
+/// AES 256-bit cipher for XTS mode.
+///
+/// The key is the concatenation of the 256-bit data key and the 256-bit tweak key, which must
+/// differ. AES-128-XTS is not supported by AWS-LC.
+pub static AES_256_XTS: Algorithm = Algorithm {
+    id: AlgorithmId::Aes256Xts,
+    key_len: AES_256_XTS_KEY_LEN,
+    block_len: AES_BLOCK_LEN,
+};
+
+/// ChaCha20 stream cipher, as specified by RFC 8439.
+///
+/// Keys for this algorithm are used in CTR mode (e.g. with [`EncryptingKey::ctr`]), with a
+/// [`EncryptionContext::Nonce96Counter32`] context.
+pub static CHACHA20: Algorithm = Algorithm {
+    id: AlgorithmId::ChaCha20,
+    key_len: CHACHA20_KEY_LEN,
+    block_len: chacha::KEYSTREAM_BLOCK_LEN,
+};
+
+/// XChaCha20 stream cipher, as specified by draft-irtf-cfrg-xchacha.
+///
+/// Keys for this algorithm are used in CTR mode (e.g. with [`EncryptingKey::ctr`]), with a
+/// [`EncryptionContext::Nonce192Counter32`] context.
+pub static XCHACHA20: Algorithm = Algorithm {
+    id: AlgorithmId::XChaCha20,
+    key_len: CHACHA20_KEY_LEN,
+    block_len: chacha::KEYSTREAM_BLOCK_LEN,
+};
+
 impl Algorithm {
     fn id(&self) -> &AlgorithmId {
         &self.id
@@ -345,31 +649,94 @@ impl Algorithm {
         mode: OperatingMode,
     ) -> Result<EncryptionContext, Unspecified> {
         match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
-                    Ok(EncryptionContext::Iv128(FixedLength::new()?))
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => match mode {
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB8
+                | OperatingMode::CFB128
+                | OperatingMode::OFB
+                | OperatingMode::CBC_CS1
+                | OperatingMode::CBC_CS2
+                | OperatingMode::CBC_CS3 => Ok(EncryptionContext::Iv128(FixedLength::new()?)),
+                OperatingMode::XTS => Err(Unspecified),
+            },
+            AlgorithmId::Aes256Xts => match mode {
+                OperatingMode::XTS => Ok(EncryptionContext::Tweak128(FixedLength::new()?)),
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB8
+                | OperatingMode::CFB128
+                | OperatingMode::OFB
+                | OperatingMode::CBC_CS1
+                | OperatingMode::CBC_CS2
+                | OperatingMode::CBC_CS3 => Err(Unspecified),
+            },
+            AlgorithmId::ChaCha20 => match mode {
+                OperatingMode::CTR => {
+                    Ok(EncryptionContext::Nonce96Counter32(FixedLength::new()?, 0))
+                }
+                _ => Err(Unspecified),
+            },
+            AlgorithmId::XChaCha20 => match mode {
+                OperatingMode::CTR => {
+                    Ok(EncryptionContext::Nonce192Counter32(FixedLength::new()?, 0))
                 }
+                _ => Err(Unspecified),
             },
         }
     }
 
     fn is_valid_encryption_context(&self, mode: OperatingMode, input: &EncryptionContext) -> bool {
         match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
-                    matches!(input, EncryptionContext::Iv128(_))
-                }
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => match mode {
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB8
+                | OperatingMode::CFB128
+                | OperatingMode::OFB
+                | OperatingMode::CBC_CS1
+                | OperatingMode::CBC_CS2
+                | OperatingMode::CBC_CS3 => matches!(input, EncryptionContext::Iv128(_)),
+                OperatingMode::XTS => false,
             },
+            AlgorithmId::Aes256Xts => {
+                mode == OperatingMode::XTS && matches!(input, EncryptionContext::Tweak128(_))
+            }
+            AlgorithmId::ChaCha20 => {
+                mode == OperatingMode::CTR
+                    && matches!(input, EncryptionContext::Nonce96Counter32(..))
+            }
+            AlgorithmId::XChaCha20 => {
+                mode == OperatingMode::CTR
+                    && matches!(input, EncryptionContext::Nonce192Counter32(..))
+            }
         }
     }
 
     fn is_valid_decryption_context(&self, mode: OperatingMode, input: &DecryptionContext) -> bool {
         match self.id {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => match mode {
-                OperatingMode::CBC | OperatingMode::CTR => {
-                    matches!(input, DecryptionContext::Iv128(_))
-                }
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => match mode {
+                OperatingMode::CBC
+                | OperatingMode::CTR
+                | OperatingMode::CFB8
+                | OperatingMode::CFB128
+                | OperatingMode::OFB
+                | OperatingMode::CBC_CS1
+                | OperatingMode::CBC_CS2
+                | OperatingMode::CBC_CS3 => matches!(input, DecryptionContext::Iv128(_)),
+                OperatingMode::XTS => false,
             },
+            AlgorithmId::Aes256Xts => {
+                mode == OperatingMode::XTS && matches!(input, DecryptionContext::Tweak128(_))
+            }
+            AlgorithmId::ChaCha20 => {
+                mode == OperatingMode::CTR
+                    && matches!(input, DecryptionContext::Nonce96Counter32(..))
+            }
+            AlgorithmId::XChaCha20 => {
+                mode == OperatingMode::CTR
+                    && matches!(input, DecryptionContext::Nonce192Counter32(..))
+            }
         }
     }
 }
@@ -411,7 +778,15 @@ impl UnboundCipherKey {
     /// # Errors
     ///
     /// * [`Unspecified`] if `key_bytes.len()` does not match the length required by `algorithm`.
+    /// * [`Unspecified`] if `algorithm` is `AES_256_XTS` and the two halves of `key_bytes` are
+    ///   equal.
     pub fn new(algorithm: &'static Algorithm, key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if algorithm.id == AlgorithmId::Aes256Xts {
+            if key_bytes.len() != algorithm.key_len {
+                return Err(Unspecified);
+            }
+            xts::check_key_halves(key_bytes)?;
+        }
         let key_bytes = Buffer::new(key_bytes.to_vec());
         Ok(UnboundCipherKey {
             algorithm,
@@ -433,7 +808,12 @@ impl TryInto<SymmetricCipherKey> for UnboundCipherKey {
     fn try_into(self) -> Result<SymmetricCipherKey, Self::Error> {
         match self.algorithm.id() {
             AlgorithmId::Aes128 => SymmetricCipherKey::aes128(self.key_bytes.as_ref()),
+            AlgorithmId::Aes192 => SymmetricCipherKey::aes192(self.key_bytes.as_ref()),
             AlgorithmId::Aes256 => SymmetricCipherKey::aes256(self.key_bytes.as_ref()),
+            AlgorithmId::Aes256Xts => SymmetricCipherKey::aes256_xts(self.key_bytes.as_ref()),
+            AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                SymmetricCipherKey::chacha20(self.key_bytes.as_ref())
+            }
         }
     }
 }
@@ -443,14 +823,80 @@ pub struct EncryptingKey {
     algorithm: &'static Algorithm,
     key: SymmetricCipherKey,
     mode: OperatingMode,
+    cfb8_ctx: Option<Cfb8Context>,
 }
 
 impl EncryptingKey {
+    /// Constructs an `EncryptingKey` operating in cipher block chaining (CBC) mode using the provided key.
+    ///
+    /// No padding is applied, so the plaintext must be a multiple of the block length. See
+    /// [`PaddedBlockEncryptingKey::cbc_pkcs7`] for plaintexts of arbitrary length.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cbc(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CBC)
+    }
+
+    /// Constructs an `EncryptingKey` operating in cipher block chaining mode with ciphertext
+    /// stealing, variant CS1 of the Addendum to NIST SP 800-38A, using the provided key.
+    ///
+    /// The ciphertext has the same length as the plaintext, which must be at least one block
+    /// long.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cbc_cs1(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CBC_CS1)
+    }
+
+    /// Constructs an `EncryptingKey` operating in cipher block chaining mode with ciphertext
+    /// stealing, variant CS2 of the Addendum to NIST SP 800-38A, using the provided key.
+    ///
+    /// The ciphertext has the same length as the plaintext, which must be at least one block
+    /// long.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cbc_cs2(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CBC_CS2)
+    }
+
+    /// Constructs an `EncryptingKey` operating in cipher block chaining mode with ciphertext
+    /// stealing, variant CS3 of the Addendum to NIST SP 800-38A, using the provided key.
+    ///
+    /// The ciphertext has the same length as the plaintext, which must be at least one block
+    /// long.
+    ///
+    /// This is the variant used by Kerberos (RFC 3962).
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cbc_cs3(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CBC_CS3)
+    }
+
     /// Constructs an `EncryptingKey` operating in counter (CTR) mode using the provided key.
     ///
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
@@ -459,14 +905,73 @@ impl EncryptingKey {
         EncryptingKey::new(key, OperatingMode::CTR)
     }
 
+    /// Constructs an `EncryptingKey` operating in cipher feedback 8-bit (CFB8) mode using the provided key.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cfb8(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CFB8)
+    }
+
+    /// Constructs an `EncryptingKey` operating in cipher feedback 128-bit (CFB128) mode using the provided key.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn cfb128(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::CFB128)
+    }
+
+    /// Constructs an `EncryptingKey` operating in output feedback (OFB) mode using the provided key.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `EncryptingKey`.
+    pub fn ofb(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        EncryptingKey::new(key, OperatingMode::OFB)
+    }
+
+    /// Constructs an `EncryptingKey` operating in XTS mode using the provided key.
+    ///
+    /// Each call to `less_safe_encrypt` encrypts one data unit, e.g. a disk sector, under the
+    /// tweak given by the context, typically constructed with [`EncryptionContext::xts_sector`].
+    /// Data units must be at least one block long; ciphertext stealing is used when their length
+    /// is not a multiple of the block length.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_256_XTS`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key was not constructed for `AES_256_XTS`.
+    pub fn xts(key: UnboundCipherKey) -> Result<EncryptingKey, Unspecified> {
+        if key.algorithm().id != AlgorithmId::Aes256Xts {
+            return Err(Unspecified);
+        }
+        EncryptingKey::new(key, OperatingMode::XTS)
+    }
+
     #[allow(clippy::unnecessary_wraps)]
     fn new(key: UnboundCipherKey, mode: OperatingMode) -> Result<EncryptingKey, Unspecified> {
         let algorithm = key.algorithm();
+        let cfb8_ctx = if mode == OperatingMode::CFB8 {
+            Some(Cfb8Context::new(&key, true)?)
+        } else {
+            None
+        };
         let key = key.try_into()?;
         Ok(EncryptingKey {
             algorithm,
             key,
             mode,
+            cfb8_ctx,
         })
     }
 
@@ -513,8 +1018,47 @@ impl EncryptingKey {
         {
             return Err(Unspecified);
         }
+        if let Some(cfb8_ctx) = &self.cfb8_ctx {
+            cfb8_ctx.apply((&context).try_into()?, in_out)?;
+            return Ok(context.into());
+        }
         encrypt(self.algorithm(), &self.key, self.mode, in_out, context)
     }
+
+    /// Encrypts the data provided in `in_out` in-place, as the portion of a CTR mode ciphertext
+    /// that starts `offset` bytes into the stream encrypted using the provided `EncryptionContext`.
+    ///
+    /// The counter block for `offset` is computed directly, so the preceding portion of the
+    /// stream does not need to be processed. The counter is incremented as a 128-bit big-endian
+    /// integer that wraps around on overflow, which is consistent with [`Self::less_safe_encrypt`]
+    /// and [`StreamingEncryptingKey::ctr`].
+    ///
+    /// This is considered "less safe" because the caller could potentially construct
+    /// a `EncryptionContext` from a previously used IV (initialization vector).
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key is not operating in CTR mode, or if the
+    ///   `EncryptionContext` is not valid for it.
+    pub fn less_safe_encrypt_at(
+        &self,
+        in_out: &mut [u8],
+        context: EncryptionContext,
+        offset: u64,
+    ) -> Result<(), Unspecified> {
+        if self.mode != OperatingMode::CTR
+            || !self
+                .algorithm()
+                .is_valid_encryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
+        match self.algorithm().id() {
+            AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                apply_chacha20_mode_at(&self.key, &context, offset, in_out)
+            }
+            _ => apply_aes_ctr_mode_at(&self.key, (&context).try_into()?, offset, in_out),
+        }
+    }
 }
 
 impl Debug for EncryptingKey {
@@ -531,34 +1075,156 @@ pub struct DecryptingKey {
     algorithm: &'static Algorithm,
     key: SymmetricCipherKey,
     mode: OperatingMode,
+    cfb8_ctx: Option<Cfb8Context>,
 }
 
 impl DecryptingKey {
-    /// Constructs a cipher decrypting key operating in counter (CTR) mode using the provided key and context.
+    /// Constructs an `DecryptingKey` operating in cipher block chaining (CBC) mode using the provided key.
+    ///
+    /// No padding is applied, so the ciphertext must be a multiple of the block length. See
+    /// [`PaddedBlockDecryptingKey::cbc_pkcs7`] for ciphertexts of arbitrary length.
     ///
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
-    /// * [`Unspecified`]: Returned if there is an error during decryption.
-    pub fn ctr(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
-        DecryptingKey::new(key, OperatingMode::CTR)
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cbc(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CBC)
     }
 
-    #[allow(clippy::unnecessary_wraps)]
-    fn new(key: UnboundCipherKey, mode: OperatingMode) -> Result<DecryptingKey, Unspecified> {
-        let algorithm = key.algorithm();
-        let key = key.try_into()?;
-        Ok(DecryptingKey {
-            algorithm,
-            key,
-            mode,
-        })
+    /// Constructs an `DecryptingKey` operating in cipher block chaining mode with ciphertext
+    /// stealing, variant CS1 of the Addendum to NIST SP 800-38A, using the provided key.
+    ///
+    /// The ciphertext has the same length as the plaintext, which must be at least one block
+    /// long.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cbc_cs1(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CBC_CS1)
     }
 
-    /// Returns the cipher algorithm.
+    /// Constructs an `DecryptingKey` operating in cipher block chaining mode with ciphertext
+    /// stealing, variant CS2 of the Addendum to NIST SP 800-38A, using the provided key.
+    ///
+    /// The ciphertext has the same length as the plaintext, which must be at least one block
+    /// long.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cbc_cs2(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CBC_CS2)
+    }
+
+    /// Constructs an `DecryptingKey` operating in cipher block chaining mode with ciphertext
+    /// stealing, variant CS3 of the Addendum to NIST SP 800-38A, using the provided key.
+    ///
+    /// The ciphertext has the same length as the plaintext, which must be at least one block
+    /// long.
+    ///
+    /// This is the variant used by Kerberos (RFC 3962).
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cbc_cs3(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CBC_CS3)
+    }
+
+    /// Constructs a cipher decrypting key operating in counter (CTR) mode using the provided key and context.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_128`
+    // * `AES_192`
+    // * `AES_256`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error during decryption.
+    pub fn ctr(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CTR)
+    }
+
+    /// Constructs a cipher decrypting key operating in cipher feedback 8-bit (CFB8) mode using the provided key.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cfb8(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CFB8)
+    }
+
+    /// Constructs a cipher decrypting key operating in cipher feedback 128-bit (CFB128) mode using the provided key.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn cfb128(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::CFB128)
+    }
+
+    /// Constructs a cipher decrypting key operating in output feedback (OFB) mode using the provided key.
+    ///
+    // # FIPS
+    // This function must not be used.
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if there is an error constructing the `DecryptingKey`.
+    pub fn ofb(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        DecryptingKey::new(key, OperatingMode::OFB)
+    }
+
+    /// Constructs a cipher decrypting key operating in XTS mode using the provided key.
+    ///
+    /// Each call to `decrypt` decrypts one data unit under the tweak given by the context.
+    ///
+    // # FIPS
+    // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
+    // * `AES_256_XTS`
+    //
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key was not constructed for `AES_256_XTS`.
+    pub fn xts(key: UnboundCipherKey) -> Result<DecryptingKey, Unspecified> {
+        if key.algorithm().id != AlgorithmId::Aes256Xts {
+            return Err(Unspecified);
+        }
+        DecryptingKey::new(key, OperatingMode::XTS)
+    }
+
+    #[allow(clippy::unnecessary_wraps)]
+    fn new(key: UnboundCipherKey, mode: OperatingMode) -> Result<DecryptingKey, Unspecified> {
+        let algorithm = key.algorithm();
+        let cfb8_ctx = if mode == OperatingMode::CFB8 {
+            Some(Cfb8Context::new(&key, false)?)
+        } else {
+            None
+        };
+        let key = key.try_into()?;
+        Ok(DecryptingKey {
+            algorithm,
+            key,
+            mode,
+            cfb8_ctx,
+        })
+    }
+
+    /// Returns the cipher algorithm.
     #[must_use]
     pub fn algorithm(&self) -> &Algorithm {
         self.algorithm
@@ -581,8 +1247,45 @@ impl DecryptingKey {
         in_out: &'in_out mut [u8],
         context: DecryptionContext,
     ) -> Result<&'in_out mut [u8], Unspecified> {
+        if let Some(cfb8_ctx) = &self.cfb8_ctx {
+            cfb8_ctx.apply((&context).try_into()?, in_out)?;
+            return Ok(in_out);
+        }
         decrypt(self.algorithm, &self.key, self.mode, in_out, context)
     }
+
+    /// Decrypts the data provided in `in_out` in-place, as the portion of a CTR mode ciphertext
+    /// that starts `offset` bytes into the stream. Returns a reference to the decrypted data.
+    ///
+    /// The counter block for `offset` is computed directly, so the preceding portion of the
+    /// ciphertext does not need to be processed. The counter is incremented as a 128-bit
+    /// big-endian integer that wraps around on overflow, which is consistent with
+    /// [`EncryptingKey::encrypt`] and [`StreamingEncryptingKey::ctr`].
+    ///
+    /// # Errors
+    /// * [`Unspecified`]: Returned if the key is not operating in CTR mode, or if the
+    ///   `DecryptionContext` is not valid for it.
+    pub fn decrypt_at<'in_out>(
+        &self,
+        in_out: &'in_out mut [u8],
+        context: DecryptionContext,
+        offset: u64,
+    ) -> Result<&'in_out mut [u8], Unspecified> {
+        if self.mode != OperatingMode::CTR
+            || !self
+                .algorithm()
+                .is_valid_decryption_context(self.mode, &context)
+        {
+            return Err(Unspecified);
+        }
+        match self.algorithm().id() {
+            AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                apply_chacha20_mode_at(&self.key, &context.into(), offset, in_out)?;
+            }
+            _ => apply_aes_ctr_mode_at(&self.key, (&context).try_into()?, offset, in_out)?,
+        }
+        Ok(in_out)
+    }
 }
 
 impl Debug for DecryptingKey {
@@ -604,8 +1307,15 @@ fn encrypt(
     let block_len = algorithm.block_len();
 
     match mode {
-        OperatingMode::CTR => {}
-        _ => {
+        OperatingMode::CTR
+        | OperatingMode::XTS
+        | OperatingMode::CFB8
+        | OperatingMode::CFB128
+        | OperatingMode::OFB
+        | OperatingMode::CBC_CS1
+        | OperatingMode::CBC_CS2
+        | OperatingMode::CBC_CS3 => {}
+        OperatingMode::CBC => {
             if (in_out.len() % block_len) != 0 {
                 return Err(Unspecified);
             }
@@ -614,11 +1324,48 @@ fn encrypt(
 
     match mode {
         OperatingMode::CBC => match algorithm.id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_cbc_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_cbc_mode(key, context, in_out)
+            }
+            AlgorithmId::Aes256Xts | AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                Err(Unspecified)
+            }
         },
         OperatingMode::CTR => match algorithm.id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => encrypt_aes_ctr_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_ctr_mode(key, context, in_out)
+            }
+            AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                encrypt_chacha20_mode(key, context, in_out)
+            }
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::CFB8 | OperatingMode::CFB128 | OperatingMode::OFB => match algorithm.id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                encrypt_aes_feedback_mode(key, mode, context, in_out)
+            }
+            AlgorithmId::Aes256Xts | AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                Err(Unspecified)
+            }
+        },
+        OperatingMode::XTS => match algorithm.id() {
+            AlgorithmId::Aes256Xts => encrypt_aes_xts_mode(key, context, in_out),
+            AlgorithmId::Aes128
+            | AlgorithmId::Aes192
+            | AlgorithmId::Aes256
+            | AlgorithmId::ChaCha20
+            | AlgorithmId::XChaCha20 => Err(Unspecified),
         },
+        OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => {
+            match algorithm.id() {
+                AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                    encrypt_aes_cbc_cts_mode(key, mode, context, in_out)
+                }
+                AlgorithmId::Aes256Xts | AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                    Err(Unspecified)
+                }
+            }
+        }
     }
 }
 
@@ -632,8 +1379,15 @@ fn decrypt<'in_out>(
     let block_len = algorithm.block_len();
 
     match mode {
-        OperatingMode::CTR => {}
-        _ => {
+        OperatingMode::CTR
+        | OperatingMode::XTS
+        | OperatingMode::CFB8
+        | OperatingMode::CFB128
+        | OperatingMode::OFB
+        | OperatingMode::CBC_CS1
+        | OperatingMode::CBC_CS2
+        | OperatingMode::CBC_CS3 => {}
+        OperatingMode::CBC => {
             if (in_out.len() % block_len) != 0 {
                 return Err(Unspecified);
             }
@@ -642,11 +1396,48 @@ fn decrypt<'in_out>(
 
     match mode {
         OperatingMode::CBC => match algorithm.id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_cbc_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_cbc_mode(key, context, in_out)
+            }
+            AlgorithmId::Aes256Xts | AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                Err(Unspecified)
+            }
         },
         OperatingMode::CTR => match algorithm.id() {
-            AlgorithmId::Aes128 | AlgorithmId::Aes256 => decrypt_aes_ctr_mode(key, context, in_out),
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_ctr_mode(key, context, in_out)
+            }
+            AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                decrypt_chacha20_mode(key, context, in_out)
+            }
+            AlgorithmId::Aes256Xts => Err(Unspecified),
+        },
+        OperatingMode::CFB8 | OperatingMode::CFB128 | OperatingMode::OFB => match algorithm.id() {
+            AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                decrypt_aes_feedback_mode(key, mode, context, in_out)
+            }
+            AlgorithmId::Aes256Xts | AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                Err(Unspecified)
+            }
+        },
+        OperatingMode::XTS => match algorithm.id() {
+            AlgorithmId::Aes256Xts => decrypt_aes_xts_mode(key, context, in_out),
+            AlgorithmId::Aes128
+            | AlgorithmId::Aes192
+            | AlgorithmId::Aes256
+            | AlgorithmId::ChaCha20
+            | AlgorithmId::XChaCha20 => Err(Unspecified),
         },
+        OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => {
+            match algorithm.id() {
+                AlgorithmId::Aes128 | AlgorithmId::Aes192 | AlgorithmId::Aes256 => {
+                    decrypt_aes_cbc_cts_mode(key, mode, context, in_out)
+                }
+                AlgorithmId::Aes256Xts | AlgorithmId::ChaCha20 | AlgorithmId::XChaCha20 => {
+                    Err(Unspecified)
+                }
+            }
+        }
     }
 }
 
@@ -657,9 +1448,9 @@ fn encrypt_aes_ctr_mode(
 ) -> Result<DecryptionContext, Unspecified> {
     #[allow(clippy::match_wildcard_for_single_variants)]
     let key = match &key {
-        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
-            enc_key
-        }
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
         _ => return Err(Unspecified),
     };
 
@@ -686,16 +1477,105 @@ fn decrypt_aes_ctr_mode<'in_out>(
     encrypt_aes_ctr_mode(key, context.into(), in_out).map(|_| in_out)
 }
 
-fn encrypt_aes_cbc_mode(
+fn encrypt_chacha20_mode(
     key: &SymmetricCipherKey,
     context: EncryptionContext,
     in_out: &mut [u8],
 ) -> Result<DecryptionContext, Unspecified> {
+    apply_chacha20_mode_at(key, &context, 0, in_out)?;
+    Ok(context.into())
+}
+
+fn decrypt_chacha20_mode<'in_out>(
+    key: &SymmetricCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    // it's the same in CTR, just providing a nice named wrapper to match
+    encrypt_chacha20_mode(key, context.into(), in_out).map(|_| in_out)
+}
+
+fn apply_chacha20_mode_at(
+    key: &SymmetricCipherKey,
+    context: &EncryptionContext,
+    offset: u64,
+    in_out: &mut [u8],
+) -> Result<(), Unspecified> {
     #[allow(clippy::match_wildcard_for_single_variants)]
     let key = match &key {
-        SymmetricCipherKey::Aes128 { enc_key, .. } | SymmetricCipherKey::Aes256 { enc_key, .. } => {
-            enc_key
+        SymmetricCipherKey::ChaCha20 { raw_key } => raw_key,
+        _ => return Err(Unspecified),
+    };
+
+    match context {
+        EncryptionContext::Nonce96Counter32(nonce, counter) => {
+            chacha::apply_keystream(key, nonce.as_ref(), *counter, offset, in_out)
+        }
+        EncryptionContext::Nonce192Counter32(nonce, counter) => {
+            let (subkey, nonce) = chacha::xchacha20_subkey(key, nonce.as_ref())?;
+            chacha::apply_keystream(&subkey, &nonce, *counter, offset, in_out)
         }
+        EncryptionContext::Iv128(_) | EncryptionContext::Tweak128(_) => Err(Unspecified),
+    }
+}
+
+fn apply_aes_ctr_mode_at(
+    key: &SymmetricCipherKey,
+    iv: &[u8],
+    offset: u64,
+    in_out: &mut [u8],
+) -> Result<(), Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    let block_len = AES_BLOCK_LEN as u64;
+    let mut iv = u128::from_be_bytes(iv.try_into()?)
+        .wrapping_add(u128::from(offset / block_len))
+        .to_be_bytes();
+
+    let mut buffer = [0u8; AES_BLOCK_LEN];
+    let mut num: u32 = 0;
+
+    // Discard the keystream preceding `offset` within its block.
+    let mut skipped = [0u8; AES_BLOCK_LEN];
+    #[allow(clippy::cast_possible_truncation)]
+    let skipped_len = (offset % block_len) as usize;
+    for data in [&mut skipped[..skipped_len], in_out] {
+        indicator_check!(unsafe {
+            AES_ctr128_encrypt(
+                data.as_ptr(),
+                data.as_mut_ptr(),
+                data.len(),
+                key,
+                iv.as_mut_ptr(),
+                buffer.as_mut_ptr(),
+                &mut num,
+            );
+        });
+    }
+
+    iv.zeroize();
+    buffer.zeroize();
+    skipped.zeroize();
+
+    Ok(())
+}
+
+fn encrypt_aes_cbc_mode(
+    key: &SymmetricCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
         _ => return Err(Unspecified),
     };
 
@@ -719,9 +1599,9 @@ fn decrypt_aes_cbc_mode<'in_out>(
 ) -> Result<&'in_out mut [u8], Unspecified> {
     #[allow(clippy::match_wildcard_for_single_variants)]
     let key = match &key {
-        SymmetricCipherKey::Aes128 { dec_key, .. } | SymmetricCipherKey::Aes256 { dec_key, .. } => {
-            dec_key
-        }
+        SymmetricCipherKey::Aes128 { dec_key, .. }
+        | SymmetricCipherKey::Aes192 { dec_key, .. }
+        | SymmetricCipherKey::Aes256 { dec_key, .. } => dec_key,
         _ => return Err(Unspecified),
     };
 
@@ -737,6 +1617,185 @@ fn decrypt_aes_cbc_mode<'in_out>(
     Ok(in_out)
 }
 
+fn encrypt_aes_cbc_cts_mode(
+    key: &SymmetricCipherKey,
+    mode: OperatingMode,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = {
+        let mut iv = [0u8; AES_CBC_IV_LEN];
+        iv.copy_from_slice((&context).try_into()?);
+        iv
+    };
+
+    let tail_len = cts::tail_len(in_out.len())?;
+    let (head, tail) = in_out.split_at_mut(in_out.len() - tail_len);
+    aes_cbc_encrypt(key, &mut iv, head);
+    let result = cts::encrypt_tail(mode, tail, |blocks| {
+        aes_cbc_encrypt(key, &mut iv, blocks);
+        Ok(())
+    });
+    iv.zeroize();
+    result?;
+
+    Ok(context.into())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn decrypt_aes_cbc_cts_mode<'in_out>(
+    key: &SymmetricCipherKey,
+    mode: OperatingMode,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key {
+        SymmetricCipherKey::Aes128 { dec_key, .. }
+        | SymmetricCipherKey::Aes192 { dec_key, .. }
+        | SymmetricCipherKey::Aes256 { dec_key, .. } => dec_key,
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = {
+        let mut iv = [0u8; AES_CBC_IV_LEN];
+        iv.copy_from_slice((&context).try_into()?);
+        iv
+    };
+
+    let tail_len = cts::tail_len(in_out.len())?;
+    let (head, tail) = in_out.split_at_mut(in_out.len() - tail_len);
+    aes_cbc_decrypt(key, &mut iv, head);
+    // After the leading blocks `iv` holds the ciphertext block that precedes the tail.
+    let mut chain = iv;
+    let result = cts::decrypt_tail(mode, tail, &chain, |blocks| {
+        aes_cbc_decrypt(key, &mut iv, blocks);
+        Ok(())
+    });
+    iv.zeroize();
+    chain.zeroize();
+    result?;
+
+    Ok(in_out)
+}
+
+fn encrypt_aes_feedback_mode(
+    key: &SymmetricCipherKey,
+    mode: OperatingMode,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    aes_feedback_mode(key, mode, (&context).try_into()?, in_out, true)?;
+    Ok(context.into())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn decrypt_aes_feedback_mode<'in_out>(
+    key: &SymmetricCipherKey,
+    mode: OperatingMode,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    aes_feedback_mode(key, mode, (&context).try_into()?, in_out, false)?;
+    Ok(in_out)
+}
+
+// CFB and OFB only use the forward cipher, for both encryption and decryption.
+fn aes_feedback_mode(
+    key: &SymmetricCipherKey,
+    mode: OperatingMode,
+    context_iv: &[u8],
+    in_out: &mut [u8],
+    encrypt: bool,
+) -> Result<(), Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key = match &key {
+        SymmetricCipherKey::Aes128 { enc_key, .. }
+        | SymmetricCipherKey::Aes192 { enc_key, .. }
+        | SymmetricCipherKey::Aes256 { enc_key, .. } => enc_key,
+        _ => return Err(Unspecified),
+    };
+
+    let mut iv = [0u8; AES_BLOCK_LEN];
+    iv.copy_from_slice(context_iv);
+
+    let result = match mode {
+        OperatingMode::CFB128 => {
+            aes_cfb128_encrypt(key, &mut iv, in_out, encrypt);
+            Ok(())
+        }
+        OperatingMode::OFB => {
+            aes_ofb128_encrypt(key, &mut iv, in_out);
+            Ok(())
+        }
+        // CFB8 keys are processed by their `Cfb8Context`.
+        OperatingMode::CFB8
+        | OperatingMode::CBC
+        | OperatingMode::CTR
+        | OperatingMode::XTS
+        | OperatingMode::CBC_CS1
+        | OperatingMode::CBC_CS2
+        | OperatingMode::CBC_CS3 => Err(Unspecified),
+    };
+    iv.zeroize();
+
+    result
+}
+
+fn encrypt_aes_xts_mode(
+    key: &SymmetricCipherKey,
+    context: EncryptionContext,
+    in_out: &mut [u8],
+) -> Result<DecryptionContext, Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key_bytes = match &key {
+        SymmetricCipherKey::Aes256Xts { key_bytes } => key_bytes,
+        _ => return Err(Unspecified),
+    };
+
+    let tweak = match &context {
+        EncryptionContext::Tweak128(tweak) => tweak.as_ref(),
+        EncryptionContext::Iv128(_)
+        | EncryptionContext::Nonce96Counter32(..)
+        | EncryptionContext::Nonce192Counter32(..) => return Err(Unspecified),
+    };
+
+    xts::aes_256_xts(key_bytes, tweak, in_out, true)?;
+
+    Ok(context.into())
+}
+
+fn decrypt_aes_xts_mode<'in_out>(
+    key: &SymmetricCipherKey,
+    context: DecryptionContext,
+    in_out: &'in_out mut [u8],
+) -> Result<&'in_out mut [u8], Unspecified> {
+    #[allow(clippy::match_wildcard_for_single_variants)]
+    let key_bytes = match &key {
+        SymmetricCipherKey::Aes256Xts { key_bytes } => key_bytes,
+        _ => return Err(Unspecified),
+    };
+
+    let tweak = match &context {
+        DecryptionContext::Tweak128(tweak) => tweak.as_ref(),
+        DecryptionContext::Iv128(_)
+        | DecryptionContext::Nonce96Counter32(..)
+        | DecryptionContext::Nonce192Counter32(..) => return Err(Unspecified),
+    };
+
+    xts::aes_256_xts(key_bytes, tweak, in_out, false)?;
+
+    Ok(in_out)
+}
+
 fn aes_ctr128_encrypt(key: &AES_KEY, iv: &mut [u8], block_buffer: &mut [u8], in_out: &mut [u8]) {
     let mut num = MaybeUninit::<u32>::new(0);
 
@@ -755,6 +1814,92 @@ fn aes_ctr128_encrypt(key: &AES_KEY, iv: &mut [u8], block_buffer: &mut [u8], in_
     Zeroize::zeroize(block_buffer);
 }
 
+fn aes_cfb128_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8], encrypt: bool) {
+    let mut num: i32 = 0;
+    indicator_check!(unsafe {
+        AES_cfb128_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            &mut num,
+            if encrypt { AES_ENCRYPT } else { AES_DECRYPT },
+        );
+    });
+}
+
+fn aes_ofb128_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
+    let mut num: i32 = 0;
+    indicator_check!(unsafe {
+        AES_ofb128_encrypt(
+            in_out.as_ptr(),
+            in_out.as_mut_ptr(),
+            in_out.len(),
+            key,
+            iv.as_mut_ptr(),
+            &mut num,
+        );
+    });
+}
+
+// AWS-LC has no low-level CFB8 function, so CFB8 goes through the EVP interface. The context is
+// keyed once when the key is constructed, and each operation works on a copy with only the IV set.
+struct Cfb8Context(LcPtr<EVP_CIPHER_CTX>);
+
+impl Cfb8Context {
+    fn new(key: &UnboundCipherKey, encrypt: bool) -> Result<Self, Unspecified> {
+        let cipher = OperatingMode::CFB8.evp_cipher(key.algorithm)?;
+        let mut ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+        if 1 != unsafe {
+            EVP_CipherInit_ex(
+                *ctx.as_mut(),
+                *cipher,
+                null_mut(),
+                key.key_bytes.as_ref().as_ptr(),
+                null(),
+                i32::from(encrypt),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Self(ctx))
+    }
+
+    fn apply(&self, iv: &[u8], in_out: &mut [u8]) -> Result<(), Unspecified> {
+        let mut ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+        if 1 != unsafe { EVP_CIPHER_CTX_copy(*ctx.as_mut(), *self.0.as_const()) } {
+            return Err(Unspecified);
+        }
+        // A null cipher and key keep the ones the context was initialized with.
+        if 1 != unsafe {
+            EVP_CipherInit_ex(*ctx.as_mut(), null(), null_mut(), null(), iv.as_ptr(), -1)
+        } {
+            return Err(Unspecified);
+        }
+        let in_out_len: i32 = in_out.len().try_into()?;
+        let mut out_len: i32 = 0;
+        if 1 != indicator_check!(unsafe {
+            EVP_CipherUpdate(
+                *ctx.as_mut(),
+                in_out.as_mut_ptr(),
+                &mut out_len,
+                in_out.as_ptr(),
+                in_out_len,
+            )
+        }) {
+            return Err(Unspecified);
+        }
+        debug_assert_eq!(out_len, in_out_len);
+        Ok(())
+    }
+}
+
+// The keyed context is only read, by `EVP_CIPHER_CTX_copy`, after construction.
+unsafe impl Send for Cfb8Context {}
+
+unsafe impl Sync for Cfb8Context {}
+
 fn aes_cbc_encrypt(key: &AES_KEY, iv: &mut [u8], in_out: &mut [u8]) {
     indicator_check!(unsafe {
         AES_cbc_encrypt(
@@ -834,6 +1979,17 @@ mod tests {
                 DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, key_bytes).unwrap()).unwrap();
             assert_eq!("DecryptingKey { algorithm: Algorithm { id: Aes128, key_len: 16, block_len: 16 }, mode: CTR, .. }", format!("{key:?}"));
         }
+
+        {
+            let mut key_bytes = [0u8; 64];
+            key_bytes[63] = 1;
+            let key = EncryptingKey::xts(UnboundCipherKey::new(&AES_256_XTS, &key_bytes).unwrap())
+                .unwrap();
+            assert_eq!("EncryptingKey { algorithm: Algorithm { id: Aes256Xts, key_len: 64, block_len: 16 }, mode: XTS, .. }", format!("{key:?}"));
+            let mut data = vec![0u8; 17];
+            let context = key.encrypt(&mut data).unwrap();
+            assert_eq!("Tweak128", format!("{context:?}"));
+        }
     }
 
     fn helper_test_cipher_n_bytes(
@@ -883,6 +2039,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_aes_feedback_modes() {
+        let key =
+            from_hex("000102030405060708090a0b0c0d0e0f000102030405060708090a0b0c0d0e0f").unwrap();
+        for mode in [
+            OperatingMode::CFB8,
+            OperatingMode::CFB128,
+            OperatingMode::OFB,
+        ] {
+            for (alg, key) in [
+                (&AES_128, &key[..16]),
+                (&AES_192, &key[..24]),
+                (&AES_256, &key[..]),
+            ] {
+                for i in 0..=50 {
+                    helper_test_cipher_n_bytes(key, alg, mode, i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_aes_cfb8_key_reuse() {
+        let key = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        let encrypting_key =
+            EncryptingKey::cfb8(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+        let iv = [7u8; IV_LEN_128_BIT];
+
+        let mut first = [0u8; 33];
+        encrypting_key
+            .less_safe_encrypt(&mut first, EncryptionContext::Iv128(FixedLength::from(iv)))
+            .unwrap();
+        let mut second = [0u8; 33];
+        encrypting_key
+            .less_safe_encrypt(&mut second, EncryptionContext::Iv128(FixedLength::from(iv)))
+            .unwrap();
+        assert_eq!(first, second);
+    }
+
     macro_rules! cipher_kat {
         ($name:ident, $alg:expr, $mode:expr, $key:literal, $iv: literal, $plaintext:literal, $ciphertext:literal) => {
             #[test]
@@ -12,6 +12,11 @@ use crate::error;
 
 pub(crate) const KEY_LEN: usize = 32usize;
 pub(crate) const NONCE_LEN: usize = 96 / 8;
+pub(crate) const XCHACHA20_NONCE_LEN: usize = 192 / 8;
+pub(crate) const KEYSTREAM_BLOCK_LEN: usize = 64;
+
+const HCHACHA20_NONCE_LEN: usize = 128 / 8;
+const SIGMA: &[u8; 16] = b"expand 32-byte k";
 
 pub(crate) struct ChaCha20Key(pub(super) [u8; KEY_LEN]);
 
@@ -103,6 +108,95 @@ pub(crate) fn encrypt_in_place_chacha20(
     crate::fips::set_fips_service_status_unapproved();
 }
 
+/// Applies the keystream for `nonce` to `in_out`, starting `offset` bytes after the start of
+/// block `counter`.
+///
+/// Returns an error if the keystream would need a block counter beyond `u32::MAX`, rather than
+/// wrapping the counter around and repeating the keystream.
+pub(crate) fn apply_keystream(
+    key: &ChaCha20Key,
+    nonce: &[u8; NONCE_LEN],
+    counter: u32,
+    offset: u64,
+    in_out: &mut [u8],
+) -> Result<(), error::Unspecified> {
+    if in_out.is_empty() {
+        return Ok(());
+    }
+    let block_len = KEYSTREAM_BLOCK_LEN as u64;
+    let end = offset
+        .checked_add(u64::try_from(in_out.len())?)
+        .ok_or(error::Unspecified)?;
+    // The counter of the last block used must not exceed `u32::MAX`.
+    if u64::from(counter) + (end - 1) / block_len > u64::from(u32::MAX) {
+        return Err(error::Unspecified);
+    }
+    let mut counter = u32::try_from(u64::from(counter) + offset / block_len)?;
+
+    // Discard the keystream preceding `offset` within its block.
+    #[allow(clippy::cast_possible_truncation)]
+    let skipped_len = (offset % block_len) as usize;
+    let in_out = if skipped_len == 0 {
+        in_out
+    } else {
+        let mut block = [0u8; KEYSTREAM_BLOCK_LEN];
+        encrypt_in_place_chacha20(key, nonce, &mut block, counter);
+        let (partial, rest) =
+            in_out.split_at_mut(in_out.len().min(KEYSTREAM_BLOCK_LEN - skipped_len));
+        for (b, k) in partial.iter_mut().zip(&block[skipped_len..]) {
+            *b ^= k;
+        }
+        block.zeroize();
+        if rest.is_empty() {
+            return Ok(());
+        }
+        counter += 1;
+        rest
+    };
+    encrypt_in_place_chacha20(key, nonce, in_out, counter);
+    Ok(())
+}
+
+/// Derives the XChaCha20 subkey and 96-bit ChaCha20 nonce for a 192-bit `nonce`, as specified by
+/// draft-irtf-cfrg-xchacha.
+pub(crate) fn xchacha20_subkey(
+    key: &ChaCha20Key,
+    nonce: &[u8; XCHACHA20_NONCE_LEN],
+) -> Result<(ChaCha20Key, [u8; NONCE_LEN]), error::Unspecified> {
+    let (hchacha20_nonce, remainder) = nonce.split_at(HCHACHA20_NONCE_LEN);
+    let subkey = hchacha20(key, hchacha20_nonce.try_into()?);
+    let mut chacha20_nonce = [0u8; NONCE_LEN];
+    chacha20_nonce[NONCE_LEN - remainder.len()..].copy_from_slice(remainder);
+    Ok((subkey, chacha20_nonce))
+}
+
+// HChaCha20 is the ChaCha20 block function without the final addition of the input state,
+// keeping only the first and last rows. The ChaCha20 keystream block for the same input (with
+// the first four bytes of `nonce` as the counter) is the output of the rounds plus the input
+// state, so HChaCha20 is recovered by subtracting the corresponding input words.
+fn hchacha20(key: &ChaCha20Key, nonce: &[u8; HCHACHA20_NONCE_LEN]) -> ChaCha20Key {
+    let (counter, chacha20_nonce) = nonce.split_at(4);
+    let counter = u32::from_le_bytes(counter.try_into().unwrap());
+    let mut block = [0u8; KEYSTREAM_BLOCK_LEN];
+    encrypt_in_place_chacha20(key, chacha20_nonce.try_into().unwrap(), &mut block, counter);
+
+    let mut subkey = [0u8; KEY_LEN];
+    let rows = [(0, SIGMA), (48, nonce)];
+    for (out, (block_offset, input)) in subkey.chunks_exact_mut(16).zip(rows) {
+        for (i, word) in out.chunks_exact_mut(4).enumerate() {
+            let output_word = u32::from_le_bytes(
+                block[block_offset + 4 * i..block_offset + 4 * i + 4]
+                    .try_into()
+                    .unwrap(),
+            );
+            let input_word = u32::from_le_bytes(input[4 * i..4 * i + 4].try_into().unwrap());
+            word.copy_from_slice(&output_word.wrapping_sub(input_word).to_le_bytes());
+        }
+    }
+    block.zeroize();
+    ChaCha20Key(subkey)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,4 +276,53 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn hchacha20_test() {
+        // draft-irtf-cfrg-xchacha-03, Section 2.2.1.
+        let key: [u8; KEY_LEN] =
+            test::from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let nonce: [u8; HCHACHA20_NONCE_LEN] = test::from_hex("000000090000004a0000000031415927")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let subkey = hchacha20(&ChaCha20Key::from(key), &nonce);
+        assert_eq!(
+            test::from_hex("82413b4227b27bfed30e42508a877d73a0f9e4d58a74a853c12ec41326d3ecdc")
+                .unwrap(),
+            subkey.0
+        );
+    }
+
+    #[test]
+    fn apply_keystream_test() {
+        let key = ChaCha20Key::from([7u8; KEY_LEN]);
+        let nonce = [9u8; NONCE_LEN];
+        let mut expected = [0u8; 300];
+        key.encrypt_in_place(&nonce, &mut expected, 5);
+
+        for (start, end) in [
+            (0, 300),
+            (1, 2),
+            (63, 65),
+            (64, 128),
+            (100, 300),
+            (299, 300),
+        ] {
+            let mut in_out = vec![0u8; end - start];
+            apply_keystream(&key, &nonce, 5, start as u64, &mut in_out).unwrap();
+            assert_eq!(&expected[start..end], in_out.as_slice());
+        }
+
+        // The last block counter may be `u32::MAX`, but not beyond.
+        let mut in_out = [0u8; KEYSTREAM_BLOCK_LEN];
+        apply_keystream(&key, &nonce, u32::MAX, 0, &mut in_out).unwrap();
+        assert!(apply_keystream(&key, &nonce, u32::MAX, 1, &mut in_out).is_err());
+        assert!(apply_keystream(&key, &nonce, u32::MAX - 1, 0, &mut [0u8; 129]).is_err());
+        apply_keystream(&key, &nonce, u32::MAX, 63, &mut [0u8; 1]).unwrap();
+        apply_keystream(&key, &nonce, u32::MAX, 1 << 40, &mut []).unwrap();
+    }
 }
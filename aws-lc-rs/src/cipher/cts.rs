@@ -0,0 +1,147 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! CBC with ciphertext stealing, as specified by the Addendum to NIST SP 800-38A.
+//!
+//! The leading blocks of a message are processed with plain CBC. Only the final
+//! `block_len + d` bytes (the "tail"), where `d` is the length of the last, possibly partial,
+//! block, need special handling; the functions here operate on that tail given a CBC
+//! primitive that continues the chain of the preceding blocks.
+
+use crate::cipher::{OperatingMode, AES_BLOCK_LEN};
+use crate::error::Unspecified;
+use zeroize::Zeroize;
+
+/// Returns the length of the tail processed with ciphertext stealing for a message of
+/// `len` bytes.
+///
+/// # Errors
+/// `Unspecified` if the message is shorter than one block.
+pub(super) fn tail_len(len: usize) -> Result<usize, Unspecified> {
+    if len < AES_BLOCK_LEN {
+        return Err(Unspecified);
+    }
+    if len == AES_BLOCK_LEN {
+        return Ok(AES_BLOCK_LEN);
+    }
+    match len % AES_BLOCK_LEN {
+        0 => Ok(2 * AES_BLOCK_LEN),
+        d => Ok(AES_BLOCK_LEN + d),
+    }
+}
+
+/// Encrypts the tail in place. `cbc_encrypt` must CBC-encrypt whole blocks, continuing the
+/// chain of the preceding ciphertext.
+pub(super) fn encrypt_tail(
+    mode: OperatingMode,
+    tail: &mut [u8],
+    cbc_encrypt: impl FnOnce(&mut [u8]) -> Result<(), Unspecified>,
+) -> Result<(), Unspecified> {
+    if !(AES_BLOCK_LEN..=2 * AES_BLOCK_LEN).contains(&tail.len()) {
+        return Err(Unspecified);
+    }
+    if tail.len() == AES_BLOCK_LEN {
+        return cbc_encrypt(tail);
+    }
+    let d = tail.len() - AES_BLOCK_LEN;
+
+    // The last plaintext block is padded with zeros, which is equivalent to "stealing" the end
+    // of the penultimate ciphertext block.
+    let mut blocks = [0u8; 2 * AES_BLOCK_LEN];
+    blocks[..tail.len()].copy_from_slice(tail);
+    let result = cbc_encrypt(&mut blocks).and_then(|()| {
+        let (x, y) = blocks.split_at(AES_BLOCK_LEN);
+        match mode {
+            OperatingMode::CBC_CS1 => {
+                tail[..d].copy_from_slice(&x[..d]);
+                tail[d..].copy_from_slice(y);
+            }
+            OperatingMode::CBC_CS2 if d == AES_BLOCK_LEN => tail.copy_from_slice(&blocks),
+            OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => {
+                tail[..AES_BLOCK_LEN].copy_from_slice(y);
+                tail[AES_BLOCK_LEN..].copy_from_slice(&x[..d]);
+            }
+            _ => return Err(Unspecified),
+        }
+        Ok(())
+    });
+    blocks.zeroize();
+
+    result
+}
+
+/// Decrypts the tail in place. `chain` is the ciphertext block preceding the tail (or the IV),
+/// and `cbc_decrypt` must CBC-decrypt whole blocks, continuing from that chain.
+pub(super) fn decrypt_tail(
+    mode: OperatingMode,
+    tail: &mut [u8],
+    chain: &[u8; AES_BLOCK_LEN],
+    mut cbc_decrypt: impl FnMut(&mut [u8]) -> Result<(), Unspecified>,
+) -> Result<(), Unspecified> {
+    if !(AES_BLOCK_LEN..=2 * AES_BLOCK_LEN).contains(&tail.len()) {
+        return Err(Unspecified);
+    }
+    let d = tail.len() - AES_BLOCK_LEN;
+
+    // Recover the final ciphertext block `y` and the truncated penultimate block `x`.
+    let (x_start, y_start) = match mode {
+        _ if d == 0 => return cbc_decrypt(tail),
+        OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 if d == AES_BLOCK_LEN => {
+            return cbc_decrypt(tail)
+        }
+        OperatingMode::CBC_CS1 => (0, d),
+        OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => (AES_BLOCK_LEN, 0),
+        _ => return Err(Unspecified),
+    };
+    let mut y = [0u8; AES_BLOCK_LEN];
+    y.copy_from_slice(&tail[y_start..y_start + AES_BLOCK_LEN]);
+    let mut x = [0u8; AES_BLOCK_LEN];
+    x[..d].copy_from_slice(&tail[x_start..x_start + d]);
+
+    // Decrypting `y` after `chain` yields D(y) ^ chain.
+    let mut decrypted_y = y;
+    let mut decrypted_x = [0u8; AES_BLOCK_LEN];
+    let result = cbc_decrypt(&mut decrypted_y).and_then(|()| {
+        xor_in_place(&mut decrypted_y, chain);
+        // D(y) = (P_n || 0) ^ x, so its trailing bytes complete the stolen block.
+        x[d..].copy_from_slice(&decrypted_y[d..]);
+        // The chain now continues from `y`, so decrypting `x` yields D(x) ^ y.
+        decrypted_x = x;
+        cbc_decrypt(&mut decrypted_x)
+    });
+    if result.is_ok() {
+        xor_in_place(&mut decrypted_x, &y);
+        xor_in_place(&mut decrypted_x, chain);
+        xor_in_place(&mut decrypted_y[..d], &x[..d]);
+        tail[..AES_BLOCK_LEN].copy_from_slice(&decrypted_x);
+        tail[AES_BLOCK_LEN..].copy_from_slice(&decrypted_y[..d]);
+    }
+    x.zeroize();
+    decrypted_x.zeroize();
+    decrypted_y.zeroize();
+
+    result
+}
+
+fn xor_in_place(target: &mut [u8], other: &[u8]) {
+    for (t, o) in target.iter_mut().zip(other) {
+        *t ^= o;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tail_len;
+
+    #[test]
+    fn tail_len_test() {
+        assert!(tail_len(0).is_err());
+        assert!(tail_len(15).is_err());
+        assert_eq!(16, tail_len(16).unwrap());
+        assert_eq!(17, tail_len(17).unwrap());
+        assert_eq!(32, tail_len(32).unwrap());
+        assert_eq!(17, tail_len(33).unwrap());
+        assert_eq!(31, tail_len(47).unwrap());
+        assert_eq!(32, tail_len(48).unwrap());
+    }
+}
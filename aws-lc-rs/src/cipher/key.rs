@@ -4,7 +4,8 @@
 use crate::cipher::aes::encrypt_block_aes;
 use crate::cipher::block::Block;
 use crate::cipher::chacha::ChaCha20Key;
-use crate::cipher::{AES_128_KEY_LEN, AES_256_KEY_LEN};
+use crate::cipher::xts::check_key_halves;
+use crate::cipher::{AES_128_KEY_LEN, AES_192_KEY_LEN, AES_256_KEY_LEN, AES_256_XTS_KEY_LEN};
 use crate::error::Unspecified;
 use aws_lc::{AES_set_decrypt_key, AES_set_encrypt_key, AES_KEY};
 use core::mem::{size_of, MaybeUninit};
@@ -15,9 +16,24 @@ use std::os::raw::c_uint;
 use zeroize::Zeroize;
 
 pub(crate) enum SymmetricCipherKey {
-    Aes128 { enc_key: AES_KEY, dec_key: AES_KEY },
-    Aes256 { enc_key: AES_KEY, dec_key: AES_KEY },
-    ChaCha20 { raw_key: ChaCha20Key },
+    Aes128 {
+        enc_key: AES_KEY,
+        dec_key: AES_KEY,
+    },
+    Aes192 {
+        enc_key: AES_KEY,
+        dec_key: AES_KEY,
+    },
+    Aes256 {
+        enc_key: AES_KEY,
+        dec_key: AES_KEY,
+    },
+    ChaCha20 {
+        raw_key: ChaCha20Key,
+    },
+    Aes256Xts {
+        key_bytes: [u8; AES_256_XTS_KEY_LEN],
+    },
 }
 
 unsafe impl Send for SymmetricCipherKey {}
@@ -30,6 +46,7 @@ impl Drop for SymmetricCipherKey {
         // Aes128Key, Aes256Key and ChaCha20Key implement Drop separately.
         match self {
             SymmetricCipherKey::Aes128 { enc_key, dec_key }
+            | SymmetricCipherKey::Aes192 { enc_key, dec_key }
             | SymmetricCipherKey::Aes256 { enc_key, dec_key } => unsafe {
                 let enc_bytes: &mut [u8; size_of::<AES_KEY>()] = (enc_key as *mut AES_KEY)
                     .cast::<[u8; size_of::<AES_KEY>()]>()
@@ -43,6 +60,7 @@ impl Drop for SymmetricCipherKey {
                 dec_bytes.zeroize();
             },
             SymmetricCipherKey::ChaCha20 { .. } => {}
+            SymmetricCipherKey::Aes256Xts { key_bytes } => key_bytes.zeroize(),
         }
     }
 }
@@ -82,6 +100,39 @@ impl SymmetricCipherKey {
         }
     }
 
+    pub(crate) fn aes192(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        if key_bytes.len() != AES_192_KEY_LEN {
+            return Err(Unspecified);
+        }
+        unsafe {
+            let mut enc_key = MaybeUninit::<AES_KEY>::uninit();
+            #[allow(clippy::cast_possible_truncation)]
+            if 0 != AES_set_encrypt_key(
+                key_bytes.as_ptr(),
+                (key_bytes.len() * 8) as c_uint,
+                enc_key.as_mut_ptr(),
+            ) {
+                return Err(Unspecified);
+            }
+            let enc_key = enc_key.assume_init();
+
+            let mut dec_key = MaybeUninit::<AES_KEY>::uninit();
+            #[allow(clippy::cast_possible_truncation)]
+            if 0 != AES_set_decrypt_key(
+                key_bytes.as_ptr(),
+                (key_bytes.len() * 8) as c_uint,
+                dec_key.as_mut_ptr(),
+            ) {
+                return Err(Unspecified);
+            }
+            let dec_key = dec_key.assume_init();
+
+            let mut kb = MaybeUninit::<[u8; AES_192_KEY_LEN]>::uninit();
+            copy_nonoverlapping(key_bytes.as_ptr(), kb.as_mut_ptr().cast(), AES_192_KEY_LEN);
+            Ok(SymmetricCipherKey::Aes192 { enc_key, dec_key })
+        }
+    }
+
     pub(crate) fn aes256(key_bytes: &[u8]) -> Result<Self, Unspecified> {
         if key_bytes.len() != AES_256_KEY_LEN {
             return Err(Unspecified);
@@ -128,13 +179,22 @@ impl SymmetricCipherKey {
         }
     }
 
+    pub(crate) fn aes256_xts(key_bytes: &[u8]) -> Result<Self, Unspecified> {
+        let key_bytes: [u8; AES_256_XTS_KEY_LEN] = key_bytes.try_into()?;
+        check_key_halves(&key_bytes)?;
+        Ok(SymmetricCipherKey::Aes256Xts { key_bytes })
+    }
+
     #[allow(dead_code)]
     #[inline]
     pub(crate) fn encrypt_block(&self, block: Block) -> Block {
         match self {
             SymmetricCipherKey::Aes128 { enc_key, .. }
+            | SymmetricCipherKey::Aes192 { enc_key, .. }
             | SymmetricCipherKey::Aes256 { enc_key, .. } => encrypt_block_aes(enc_key, block),
-            SymmetricCipherKey::ChaCha20 { .. } => panic!("Unsupported algorithm!"),
+            SymmetricCipherKey::ChaCha20 { .. } | SymmetricCipherKey::Aes256Xts { .. } => {
+                panic!("Unsupported algorithm!")
+            }
         }
     }
 }
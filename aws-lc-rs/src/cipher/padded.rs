@@ -81,6 +81,7 @@ impl PaddedBlockEncryptingKey {
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
@@ -187,6 +188,7 @@ impl PaddedBlockDecryptingKey {
     // # FIPS
     // Use this function with an `UnboundCipherKey` constructed with one of the following algorithms:
     // * `AES_128`
+    // * `AES_192`
     // * `AES_256`
     //
     /// # Errors
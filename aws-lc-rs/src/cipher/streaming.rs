@@ -2,17 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
 use crate::cipher::{
-    Algorithm, DecryptionContext, EncryptionContext, OperatingMode, UnboundCipherKey,
+    cts, Algorithm, DecryptionContext, EncryptionContext, OperatingMode, UnboundCipherKey,
+    AES_BLOCK_LEN,
 };
 use crate::error::Unspecified;
 use crate::fips::indicator_check;
 use crate::ptr::LcPtr;
 use aws_lc::{
-    EVP_CIPHER_CTX_new, EVP_CIPHER_iv_length, EVP_CIPHER_key_length, EVP_DecryptFinal_ex,
-    EVP_DecryptInit_ex, EVP_DecryptUpdate, EVP_EncryptFinal_ex, EVP_EncryptInit_ex,
-    EVP_EncryptUpdate, EVP_CIPHER_CTX,
+    EVP_CIPHER_CTX_new, EVP_CIPHER_CTX_set_padding, EVP_CIPHER_iv_length, EVP_CIPHER_key_length,
+    EVP_DecryptFinal_ex, EVP_DecryptInit_ex, EVP_DecryptUpdate, EVP_EncryptFinal_ex,
+    EVP_EncryptInit_ex, EVP_EncryptUpdate, EVP_CIPHER_CTX,
 };
+use core::fmt::{Debug, Formatter};
+use std::io::{self, Read, Write};
 use std::ptr::null_mut;
+use zeroize::Zeroizing;
 
 /// A key for streaming encryption operations.
 pub struct StreamingEncryptingKey {
@@ -20,6 +24,10 @@ pub struct StreamingEncryptingKey {
     mode: OperatingMode,
     cipher_ctx: LcPtr<EVP_CIPHER_CTX>,
     context: EncryptionContext,
+    // XTS processes a complete data unit at once, so its input is buffered until `finish`.
+    xts_buffer: Zeroizing<Vec<u8>>,
+    // The CBC ciphertext stealing modes hold back the final one to two blocks until `finish`.
+    cts_tail: Zeroizing<Vec<u8>>,
 }
 
 /// A struct indicating the portion of a buffer written to, and/or not written to, during an
@@ -65,7 +73,7 @@ impl StreamingEncryptingKey {
     ) -> Result<Self, Unspecified> {
         let algorithm = key.algorithm();
         let mut cipher_ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
-        let cipher = mode.evp_cipher(key.algorithm);
+        let cipher = mode.evp_cipher(key.algorithm)?;
         let key_bytes = key.key_bytes.as_ref();
         debug_assert_eq!(
             key_bytes.len(),
@@ -89,12 +97,17 @@ impl StreamingEncryptingKey {
         } {
             return Err(Unspecified);
         }
+        if mode.is_cbc_cts() {
+            disable_padding(&mut cipher_ctx)?;
+        }
 
         Ok(Self {
             algorithm,
             mode,
             cipher_ctx,
             context,
+            xts_buffer: Zeroizing::new(Vec::new()),
+            cts_tail: Zeroizing::new(Vec::new()),
         })
     }
 
@@ -108,11 +121,19 @@ impl StreamingEncryptingKey {
     /// * Returns an error if the `output` buffer is smaller than the length of
     ///   the `input` plus the algorithm's block length (e.g. [`Algorithm::block_len`]) minus one.
     /// * May return an error if the length of `input` plus the algorithm's block length is larger than `i32::MAX`.
+    ///
+    /// In XTS mode no bytes are written; the input is buffered until [`Self::finish`]. In the
+    /// CBC ciphertext stealing modes the final one to two blocks are held back until
+    /// [`Self::finish`].
     pub fn update<'a>(
         &mut self,
         input: &[u8],
         output: &'a mut [u8],
     ) -> Result<BufferUpdate<'a>, Unspecified> {
+        if self.mode == OperatingMode::XTS {
+            self.xts_buffer.extend_from_slice(input);
+            return Ok(BufferUpdate::new(output, 0));
+        }
         let min_outsize = input
             .len()
             .checked_add(self.algorithm().block_len())
@@ -121,6 +142,17 @@ impl StreamingEncryptingKey {
         if output.len() < min_outsize {
             return Err(Unspecified);
         }
+        if self.mode.is_cbc_cts() {
+            self.cts_tail.extend_from_slice(input);
+            let outlen = cts_ready_len(self.cts_tail.len());
+            if outlen > 0 {
+                let output_blocks = &mut output[..outlen];
+                output_blocks.copy_from_slice(&self.cts_tail[..outlen]);
+                cbc_update_in_place(&mut self.cipher_ctx, output_blocks, true)?;
+                self.cts_tail.drain(..outlen);
+            }
+            return Ok(BufferUpdate::new(output, outlen));
+        }
         let mut outlen: i32 = 0;
         let inlen: i32 = input.len().try_into()?;
 
@@ -146,13 +178,37 @@ impl StreamingEncryptingKey {
     /// The number of bytes written to `output` can be up to the block length of
     /// [`Algorithm::block_len`].
     ///
+    /// In XTS mode the entire buffered data unit is written to `output`. In the CBC ciphertext
+    /// stealing modes up to two blocks are written.
+    ///
     /// # Errors
     /// * Returns an error if the `output` buffer is smaller than the algorithm's
     ///   block length.
+    /// * In XTS mode, returns an error if the buffered data unit is shorter than one block or
+    ///   the `output` buffer is smaller than the data unit.
+    /// * In the CBC ciphertext stealing modes, returns an error if the plaintext was shorter than
+    ///   one block or the `output` buffer is smaller than the held back blocks.
     pub fn finish(
         mut self,
         output: &mut [u8],
     ) -> Result<(DecryptionContext, BufferUpdate), Unspecified> {
+        if self.mode == OperatingMode::XTS {
+            let outlen = finish_xts(&mut self.cipher_ctx, &self.xts_buffer, output, true)?;
+            return Ok((self.context.into(), BufferUpdate::new(output, outlen)));
+        }
+        if self.mode.is_cbc_cts() {
+            let outlen = self.cts_tail.len();
+            if output.len() < outlen {
+                return Err(Unspecified);
+            }
+            let tail = &mut output[..outlen];
+            tail.copy_from_slice(&self.cts_tail);
+            let cipher_ctx = &mut self.cipher_ctx;
+            cts::encrypt_tail(self.mode, tail, |blocks| {
+                cbc_update_in_place(cipher_ctx, blocks, true)
+            })?;
+            return Ok((self.context.into(), BufferUpdate::new(output, outlen)));
+        }
         if output.len() < self.algorithm().block_len() {
             return Err(Unspecified);
         }
@@ -233,6 +289,218 @@ impl StreamingEncryptingKey {
     ) -> Result<Self, Unspecified> {
         Self::new(key, OperatingMode::CBC, context)
     }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CBC cipher mode
+    /// with ciphertext stealing, variant CS1 of the Addendum to NIST SP 800-38A.
+    /// The resulting ciphertext will be the same length as the plaintext, which must be at
+    /// least one block long.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cbc_cs1(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key
+            .algorithm()
+            .new_encryption_context(OperatingMode::CBC_CS1)?;
+        Self::less_safe_cbc_cs1(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CBC cipher mode
+    /// with ciphertext stealing, variant CS1 of the Addendum to NIST SP 800-38A.
+    /// The resulting ciphertext will be the same length as the plaintext, which must be at
+    /// least one block long.
+    ///
+    /// This is considered less safe because the caller could potentially construct
+    /// an `EncryptionContext` from a previously used initialization vector (IV).
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn less_safe_cbc_cs1(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC_CS1, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CBC cipher mode
+    /// with ciphertext stealing, variant CS2 of the Addendum to NIST SP 800-38A.
+    /// The resulting ciphertext will be the same length as the plaintext, which must be at
+    /// least one block long.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cbc_cs2(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key
+            .algorithm()
+            .new_encryption_context(OperatingMode::CBC_CS2)?;
+        Self::less_safe_cbc_cs2(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CBC cipher mode
+    /// with ciphertext stealing, variant CS2 of the Addendum to NIST SP 800-38A.
+    /// The resulting ciphertext will be the same length as the plaintext, which must be at
+    /// least one block long.
+    ///
+    /// This is considered less safe because the caller could potentially construct
+    /// an `EncryptionContext` from a previously used initialization vector (IV).
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn less_safe_cbc_cs2(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC_CS2, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CBC cipher mode
+    /// with ciphertext stealing, variant CS3 of the Addendum to NIST SP 800-38A.
+    /// The resulting ciphertext will be the same length as the plaintext, which must be at
+    /// least one block long.
+    /// This is the variant used by Kerberos (RFC 3962).
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cbc_cs3(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key
+            .algorithm()
+            .new_encryption_context(OperatingMode::CBC_CS3)?;
+        Self::less_safe_cbc_cs3(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CBC cipher mode
+    /// with ciphertext stealing, variant CS3 of the Addendum to NIST SP 800-38A.
+    /// The resulting ciphertext will be the same length as the plaintext, which must be at
+    /// least one block long.
+    ///
+    /// This is considered less safe because the caller could potentially construct
+    /// an `EncryptionContext` from a previously used initialization vector (IV).
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn less_safe_cbc_cs3(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC_CS3, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CFB8 cipher mode.
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cfb8(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key
+            .algorithm()
+            .new_encryption_context(OperatingMode::CFB8)?;
+        Self::less_safe_cfb8(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CFB8 cipher mode.
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// This is considered less safe because the caller could potentially construct
+    /// an `EncryptionContext` from a previously used initialization vector (IV).
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn less_safe_cfb8(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CFB8, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CFB128 cipher mode.
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cfb128(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key
+            .algorithm()
+            .new_encryption_context(OperatingMode::CFB128)?;
+        Self::less_safe_cfb128(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the CFB128 cipher mode.
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// This is considered less safe because the caller could potentially construct
+    /// an `EncryptionContext` from a previously used initialization vector (IV).
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn less_safe_cfb128(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CFB128, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the OFB cipher mode.
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn ofb(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key.algorithm().new_encryption_context(OperatingMode::OFB)?;
+        Self::less_safe_ofb(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting data using the OFB cipher mode.
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// This is considered less safe because the caller could potentially construct
+    /// an `EncryptionContext` from a previously used initialization vector (IV).
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn less_safe_ofb(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::OFB, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting a single data unit (e.g. a disk
+    /// sector) using the XTS cipher mode with a random tweak.
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// XTS cannot encrypt a data unit incrementally: `update` buffers its input and the
+    /// whole ciphertext is written by `finish`.
+    ///
+    /// # Errors
+    /// Returns an error if the key was not constructed for `AES_256_XTS`, or on an internal
+    /// failure.
+    pub fn xts(key: UnboundCipherKey) -> Result<Self, Unspecified> {
+        let context = key.algorithm().new_encryption_context(OperatingMode::XTS)?;
+        Self::less_safe_xts(key, context)
+    }
+
+    /// Constructs a `StreamingEncryptingKey` for encrypting a single data unit using the XTS
+    /// cipher mode with the tweak provided by `context`, typically constructed with
+    /// [`EncryptionContext::xts_sector`].
+    /// The resulting ciphertext will be the same length as the plaintext.
+    ///
+    /// This is considered less safe because the caller could potentially reuse a tweak for
+    /// different data under the same key.
+    ///
+    /// # Errors
+    /// Returns an error if the key was not constructed for `AES_256_XTS`, or on an internal
+    /// failure.
+    pub fn less_safe_xts(
+        key: UnboundCipherKey,
+        context: EncryptionContext,
+    ) -> Result<Self, Unspecified> {
+        if !key
+            .algorithm()
+            .is_valid_encryption_context(OperatingMode::XTS, &context)
+        {
+            return Err(Unspecified);
+        }
+        Self::new(key, OperatingMode::XTS, context)
+    }
 }
 
 /// A key for streaming decryption operations.
@@ -240,7 +508,91 @@ pub struct StreamingDecryptingKey {
     algorithm: &'static Algorithm,
     mode: OperatingMode,
     cipher_ctx: LcPtr<EVP_CIPHER_CTX>,
+    // XTS processes a complete data unit at once, so its input is buffered until `finish`.
+    xts_buffer: Zeroizing<Vec<u8>>,
+    // The CBC ciphertext stealing modes hold back the final one to two blocks until `finish`.
+    cts_tail: Zeroizing<Vec<u8>>,
+    // The last ciphertext block passed to the cipher, initially the IV.
+    cts_chain: [u8; AES_BLOCK_LEN],
+}
+
+/// Processes the buffered XTS data unit into `output`, returning the number of bytes written.
+fn finish_xts(
+    cipher_ctx: &mut LcPtr<EVP_CIPHER_CTX>,
+    xts_buffer: &[u8],
+    output: &mut [u8],
+    encrypt: bool,
+) -> Result<usize, Unspecified> {
+    if xts_buffer.len() < AES_BLOCK_LEN || output.len() < xts_buffer.len() {
+        return Err(Unspecified);
+    }
+    let mut outlen: i32 = 0;
+    let inlen: i32 = xts_buffer.len().try_into()?;
+    let update = if encrypt {
+        EVP_EncryptUpdate
+    } else {
+        EVP_DecryptUpdate
+    };
+    if 1 != indicator_check!(unsafe {
+        update(
+            *cipher_ctx.as_mut(),
+            output.as_mut_ptr(),
+            &mut outlen,
+            xts_buffer.as_ptr(),
+            inlen,
+        )
+    }) {
+        return Err(Unspecified);
+    }
+    let outlen: usize = outlen.try_into()?;
+    debug_assert_eq!(outlen, xts_buffer.len());
+    Ok(outlen)
 }
+
+fn disable_padding(cipher_ctx: &mut LcPtr<EVP_CIPHER_CTX>) -> Result<(), Unspecified> {
+    if 1 != unsafe { EVP_CIPHER_CTX_set_padding(*cipher_ctx.as_mut(), 0) } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// Returns how many of the `held_len` bytes held back in a CBC ciphertext stealing mode can be
+/// processed, leaving more than one and at most two blocks for `finish`.
+fn cts_ready_len(held_len: usize) -> usize {
+    if held_len <= 2 * AES_BLOCK_LEN {
+        return 0;
+    }
+    (held_len - AES_BLOCK_LEN - 1) / AES_BLOCK_LEN * AES_BLOCK_LEN
+}
+
+/// Passes whole blocks through an unpadded CBC context, in place.
+fn cbc_update_in_place(
+    cipher_ctx: &mut LcPtr<EVP_CIPHER_CTX>,
+    blocks: &mut [u8],
+    encrypt: bool,
+) -> Result<(), Unspecified> {
+    let mut outlen: i32 = 0;
+    let inlen: i32 = blocks.len().try_into()?;
+    let update = if encrypt {
+        EVP_EncryptUpdate
+    } else {
+        EVP_DecryptUpdate
+    };
+    if 1 != unsafe {
+        update(
+            *cipher_ctx.as_mut(),
+            blocks.as_mut_ptr(),
+            &mut outlen,
+            blocks.as_ptr(),
+            inlen,
+        )
+    } || outlen != inlen
+    {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
 impl StreamingDecryptingKey {
     #[allow(clippy::needless_pass_by_value)]
     fn new(
@@ -250,7 +602,7 @@ impl StreamingDecryptingKey {
     ) -> Result<Self, Unspecified> {
         let mut cipher_ctx = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
         let algorithm = key.algorithm();
-        let cipher = mode.evp_cipher(key.algorithm);
+        let cipher = mode.evp_cipher(key.algorithm)?;
         let key_bytes = key.key_bytes.as_ref();
         debug_assert_eq!(
             key_bytes.len(),
@@ -274,11 +626,19 @@ impl StreamingDecryptingKey {
         } {
             return Err(Unspecified);
         }
+        let mut cts_chain = [0u8; AES_BLOCK_LEN];
+        if mode.is_cbc_cts() {
+            disable_padding(&mut cipher_ctx)?;
+            cts_chain.copy_from_slice(iv);
+        }
 
         Ok(Self {
             algorithm,
             mode,
             cipher_ctx,
+            xts_buffer: Zeroizing::new(Vec::new()),
+            cts_tail: Zeroizing::new(Vec::new()),
+            cts_chain,
         })
     }
 
@@ -292,11 +652,19 @@ impl StreamingDecryptingKey {
     ///   the `input` plus the algorithm's block length.
     /// * May return an error if the length of `input` plus the algorithm's block length is larger
     ///   than `i32::MAX`.
+    ///
+    /// In XTS mode no bytes are written; the input is buffered until [`Self::finish`]. In the
+    /// CBC ciphertext stealing modes the final one to two blocks are held back until
+    /// [`Self::finish`].
     pub fn update<'a>(
         &mut self,
         input: &[u8],
         output: &'a mut [u8],
     ) -> Result<BufferUpdate<'a>, Unspecified> {
+        if self.mode == OperatingMode::XTS {
+            self.xts_buffer.extend_from_slice(input);
+            return Ok(BufferUpdate::new(output, 0));
+        }
         let mut outlen: i32 = 0;
         let inlen: i32 = input.len().try_into()?;
 
@@ -307,6 +675,19 @@ impl StreamingDecryptingKey {
         if output.len() < min_outsize {
             return Err(Unspecified);
         }
+        if self.mode.is_cbc_cts() {
+            self.cts_tail.extend_from_slice(input);
+            let outlen = cts_ready_len(self.cts_tail.len());
+            if outlen > 0 {
+                let output_blocks = &mut output[..outlen];
+                output_blocks.copy_from_slice(&self.cts_tail[..outlen]);
+                cbc_update_in_place(&mut self.cipher_ctx, output_blocks, false)?;
+                self.cts_chain
+                    .copy_from_slice(&self.cts_tail[outlen - AES_BLOCK_LEN..outlen]);
+                self.cts_tail.drain(..outlen);
+            }
+            return Ok(BufferUpdate::new(output, outlen));
+        }
 
         if 1 != unsafe {
             EVP_DecryptUpdate(
@@ -329,10 +710,34 @@ impl StreamingDecryptingKey {
     /// The number of bytes written to `output` can be up to the block length of
     /// the cipher algorithm (e.g., [`Algorithm::block_len`]).
     ///
+    /// In XTS mode the entire buffered data unit is written to `output`. In the CBC ciphertext
+    /// stealing modes up to two blocks are written.
+    ///
     /// # Errors
     /// * Returns an error if the `output` buffer is smaller than the algorithm's
     ///   block length.
+    /// * In XTS mode, returns an error if the buffered data unit is shorter than one block or
+    ///   the `output` buffer is smaller than the data unit.
+    /// * In the CBC ciphertext stealing modes, returns an error if the ciphertext was shorter
+    ///   than one block or the `output` buffer is smaller than the held back blocks.
     pub fn finish(mut self, output: &mut [u8]) -> Result<BufferUpdate, Unspecified> {
+        if self.mode == OperatingMode::XTS {
+            let outlen = finish_xts(&mut self.cipher_ctx, &self.xts_buffer, output, false)?;
+            return Ok(BufferUpdate::new(output, outlen));
+        }
+        if self.mode.is_cbc_cts() {
+            let outlen = self.cts_tail.len();
+            if output.len() < outlen {
+                return Err(Unspecified);
+            }
+            let tail = &mut output[..outlen];
+            tail.copy_from_slice(&self.cts_tail);
+            let cipher_ctx = &mut self.cipher_ctx;
+            cts::decrypt_tail(self.mode, tail, &self.cts_chain, |blocks| {
+                cbc_update_in_place(cipher_ctx, blocks, false)
+            })?;
+            return Ok(BufferUpdate::new(output, outlen));
+        }
         if output.len() < self.algorithm().block_len() {
             return Err(Unspecified);
         }
@@ -380,18 +785,598 @@ impl StreamingDecryptingKey {
     ) -> Result<Self, Unspecified> {
         Self::new(key, OperatingMode::CBC, context)
     }
+
+    /// Constructs a `StreamingDecryptingKey` for decrypting using the CBC cipher mode with
+    /// ciphertext stealing, variant CS1 of the Addendum to NIST SP 800-38A.
+    /// The resulting plaintext will be the same length as the ciphertext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cbc_cs1(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC_CS1, context)
+    }
+
+    /// Constructs a `StreamingDecryptingKey` for decrypting using the CBC cipher mode with
+    /// ciphertext stealing, variant CS2 of the Addendum to NIST SP 800-38A.
+    /// The resulting plaintext will be the same length as the ciphertext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cbc_cs2(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC_CS2, context)
+    }
+
+    /// Constructs a `StreamingDecryptingKey` for decrypting using the CBC cipher mode with
+    /// ciphertext stealing, variant CS3 of the Addendum to NIST SP 800-38A.
+    /// The resulting plaintext will be the same length as the ciphertext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cbc_cs3(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CBC_CS3, context)
+    }
+
+    /// Constructs a `StreamingDecryptingKey` for decrypting using the CFB8 cipher mode.
+    /// The resulting plaintext will be the same length as the ciphertext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cfb8(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CFB8, context)
+    }
+
+    /// Constructs a `StreamingDecryptingKey` for decrypting using the CFB128 cipher mode.
+    /// The resulting plaintext will be the same length as the ciphertext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn cfb128(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::CFB128, context)
+    }
+
+    /// Constructs a `StreamingDecryptingKey` for decrypting using the OFB cipher mode.
+    /// The resulting plaintext will be the same length as the ciphertext.
+    ///
+    /// # Errors
+    /// Returns an error on an internal failure.
+    pub fn ofb(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        Self::new(key, OperatingMode::OFB, context)
+    }
+
+    /// Constructs a `StreamingDecryptingKey` for decrypting a single data unit using the XTS
+    /// cipher mode.
+    /// The resulting plaintext will be the same length as the ciphertext.
+    ///
+    /// XTS cannot decrypt a data unit incrementally: `update` buffers its input and the
+    /// whole plaintext is written by `finish`.
+    ///
+    /// # Errors
+    /// Returns an error if the key was not constructed for `AES_256_XTS`, or on an internal
+    /// failure.
+    pub fn xts(key: UnboundCipherKey, context: DecryptionContext) -> Result<Self, Unspecified> {
+        if !key
+            .algorithm()
+            .is_valid_decryption_context(OperatingMode::XTS, &context)
+        {
+            return Err(Unspecified);
+        }
+        Self::new(key, OperatingMode::XTS, context)
+    }
+}
+
+/// The maximum number of bytes passed to a streaming key by a single read or write of the I/O
+/// adapters.
+const IO_CHUNK_LEN: usize = 16 * 1024;
+
+fn io_error(kind: io::ErrorKind) -> impl FnOnce(Unspecified) -> io::Error {
+    move |e| io::Error::new(kind, e)
+}
+
+impl StreamingEncryptingKey {
+    /// The size of the output buffer needed by `finish`.
+    fn finish_output_len(&self) -> usize {
+        self.algorithm()
+            .block_len()
+            .max(self.xts_buffer.len())
+            .max(self.cts_tail.len())
+    }
+}
+
+impl StreamingDecryptingKey {
+    /// The size of the output buffer needed by `finish`.
+    fn finish_output_len(&self) -> usize {
+        self.algorithm()
+            .block_len()
+            .max(self.xts_buffer.len())
+            .max(self.cts_tail.len())
+    }
+}
+
+/// Output produced by a streaming key that has not yet been written to a writer.
+struct WriteBuffer {
+    output: Zeroizing<Vec<u8>>,
+    position: usize,
+}
+
+impl WriteBuffer {
+    fn new() -> Self {
+        Self {
+            output: Zeroizing::new(Vec::new()),
+            position: 0,
+        }
+    }
+
+    /// Writes the pending output to `inner`. On error the output not yet accepted by `inner`
+    /// stays pending.
+    fn write_to<W: Write>(&mut self, inner: &mut W) -> io::Result<()> {
+        while self.position < self.output.len() {
+            match inner.write(&self.output[self.position..]) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(len) => self.position += len,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        self.output.clear();
+        self.position = 0;
+        Ok(())
+    }
+
+    /// Returns an output buffer of `len` bytes. There must be no pending output.
+    fn prepare(&mut self, len: usize) -> &mut [u8] {
+        debug_assert!(self.output.is_empty());
+        self.output.resize(len, 0);
+        &mut self.output
+    }
+
+    /// Marks the first `len` bytes of the output buffer as pending.
+    fn set_pending(&mut self, len: usize) {
+        self.output.truncate(len);
+        self.position = 0;
+    }
+}
+
+/// A [`Write`] adapter that encrypts the data written to it with a [`StreamingEncryptingKey`],
+/// writing the ciphertext to an inner writer.
+///
+/// The remaining ciphertext, such as the final padded block in CBC mode, is only written by
+/// [`Self::finish`], which must be called to complete the ciphertext.
+///
+/// Once data has been encrypted, [`Write::write`] reports it as written even if the inner writer
+/// then fails. The ciphertext not accepted by the inner writer is kept, and is written before
+/// anything else by the next call to `write`, `flush` or `finish`, which return the error if it
+/// persists.
+///
+/// ```rust
+/// # use std::error::Error;
+/// #
+/// # fn main() -> Result<(), Box<dyn Error>> {
+/// use aws_lc_rs::cipher::{
+///     DecryptingReader, EncryptingWriter, StreamingDecryptingKey, StreamingEncryptingKey,
+///     UnboundCipherKey, AES_128,
+/// };
+/// use std::io::Read;
+///
+/// let original_message = b"This is a message longer than a single block.";
+/// let key_bytes: &[u8] = &[
+///     0xff, 0x0b, 0xe5, 0x84, 0x64, 0x0b, 0x00, 0xc8, 0x90, 0x7a, 0x4b, 0xbf, 0x82, 0x7c, 0xb6,
+///     0xd1,
+/// ];
+///
+/// let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+/// let mut writer = EncryptingWriter::new(StreamingEncryptingKey::cbc_pkcs7(key)?, Vec::new());
+/// std::io::copy(&mut &original_message[..], &mut writer)?;
+/// let (ciphertext, context) = writer.finish()?;
+///
+/// let key = UnboundCipherKey::new(&AES_128, key_bytes)?;
+/// let decrypting_key = StreamingDecryptingKey::cbc_pkcs7(key, context)?;
+/// let mut reader = DecryptingReader::new(decrypting_key, ciphertext.as_slice());
+/// let mut plaintext = Vec::new();
+/// reader.read_to_end(&mut plaintext)?;
+/// assert_eq!(original_message.as_slice(), plaintext.as_slice());
+/// #
+/// #   Ok(())
+/// # }
+/// ```
+pub struct EncryptingWriter<W> {
+    key: StreamingEncryptingKey,
+    inner: W,
+    buffer: WriteBuffer,
+}
+
+impl<W: Write> EncryptingWriter<W> {
+    /// Constructs a writer that encrypts with `key` and writes the ciphertext to `inner`.
+    #[must_use]
+    pub fn new(key: StreamingEncryptingKey, inner: W) -> Self {
+        Self {
+            key,
+            inner,
+            buffer: WriteBuffer::new(),
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    #[must_use]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Finishes the encryption, writing the remaining ciphertext to the inner writer and flushing
+    /// it.
+    ///
+    /// Returns the inner writer and the context needed to decrypt the ciphertext.
+    ///
+    /// # Errors
+    /// Returns an error if the encryption cannot be finished, or if writing to the inner writer
+    /// fails.
+    pub fn finish(mut self) -> io::Result<(W, DecryptionContext)> {
+        self.buffer.write_to(&mut self.inner)?;
+        let Self {
+            key,
+            mut inner,
+            mut buffer,
+        } = self;
+        let output_len = key.finish_output_len();
+        let (context, update) = key
+            .finish(buffer.prepare(output_len))
+            .map_err(io_error(io::ErrorKind::Other))?;
+        inner.write_all(update.written())?;
+        inner.flush()?;
+        Ok((inner, context))
+    }
+}
+
+impl<W: Write> Write for EncryptingWriter<W> {
+    /// Encrypts a prefix of `buf`, writing all of the resulting ciphertext to the inner writer.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.write_to(&mut self.inner)?;
+        let input = &buf[..buf.len().min(IO_CHUNK_LEN)];
+        let output = self
+            .buffer
+            .prepare(input.len() + self.key.algorithm().block_len());
+        let written_len = self
+            .key
+            .update(input, output)
+            .map_err(io_error(io::ErrorKind::Other))?
+            .written()
+            .len();
+        self.buffer.set_pending(written_len);
+        // The input has been consumed by the key, so it is reported as written even if the
+        // inner writer fails; the ciphertext stays pending for the next call.
+        let _ = self.buffer.write_to(&mut self.inner);
+        Ok(input.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.buffer.write_to(&mut self.inner)?;
+        self.inner.flush()
+    }
+}
+
+impl<W> Debug for EncryptingWriter<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EncryptingWriter")
+            .field("algorithm", self.key.algorithm())
+            .field("mode", &self.key.mode())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Write`] adapter that decrypts the data written to it with a [`StreamingDecryptingKey`],
+/// writing the plaintext to an inner writer.
+///
+/// The remaining plaintext is only written by [`Self::finish`], which also verifies the padding in
+/// CBC mode, and must be called to complete the plaintext.
+///
+/// As with [`EncryptingWriter`], plaintext not accepted by a failing inner writer is kept and
+/// written by the next call to `write`, `flush` or `finish`.
+pub struct DecryptingWriter<W> {
+    key: StreamingDecryptingKey,
+    inner: W,
+    buffer: WriteBuffer,
+}
+
+impl<W: Write> DecryptingWriter<W> {
+    /// Constructs a writer that decrypts with `key` and writes the plaintext to `inner`.
+    #[must_use]
+    pub fn new(key: StreamingDecryptingKey, inner: W) -> Self {
+        Self {
+            key,
+            inner,
+            buffer: WriteBuffer::new(),
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    #[must_use]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Finishes the decryption, writing the remaining plaintext to the inner writer and flushing
+    /// it.
+    ///
+    /// # Errors
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the decryption cannot be
+    /// finished (e.g. the padding is invalid), or an error if writing to the inner writer fails.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.buffer.write_to(&mut self.inner)?;
+        let Self {
+            key,
+            mut inner,
+            mut buffer,
+        } = self;
+        let output_len = key.finish_output_len();
+        let update = key
+            .finish(buffer.prepare(output_len))
+            .map_err(io_error(io::ErrorKind::InvalidData))?;
+        inner.write_all(update.written())?;
+        inner.flush()?;
+        Ok(inner)
+    }
+}
+
+impl<W: Write> Write for DecryptingWriter<W> {
+    /// Decrypts a prefix of `buf`, writing all of the resulting plaintext to the inner writer.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.write_to(&mut self.inner)?;
+        let input = &buf[..buf.len().min(IO_CHUNK_LEN)];
+        let output = self
+            .buffer
+            .prepare(input.len() + self.key.algorithm().block_len());
+        let written_len = self
+            .key
+            .update(input, output)
+            .map_err(io_error(io::ErrorKind::InvalidData))?
+            .written()
+            .len();
+        self.buffer.set_pending(written_len);
+        // The input has been consumed by the key, so it is reported as written even if the
+        // inner writer fails; the plaintext stays pending for the next call.
+        let _ = self.buffer.write_to(&mut self.inner);
+        Ok(input.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.buffer.write_to(&mut self.inner)?;
+        self.inner.flush()
+    }
+}
+
+impl<W> Debug for DecryptingWriter<W> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DecryptingWriter")
+            .field("algorithm", self.key.algorithm())
+            .field("mode", &self.key.mode())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Output produced by a streaming key that has not yet been returned by a reader.
+struct ReadBuffer {
+    input: Zeroizing<Vec<u8>>,
+    output: Zeroizing<Vec<u8>>,
+    position: usize,
+}
+
+impl ReadBuffer {
+    fn new() -> Self {
+        Self {
+            input: Zeroizing::new(Vec::new()),
+            output: Zeroizing::new(Vec::new()),
+            position: 0,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position >= self.output.len()
+    }
+
+    /// Copies pending output into `buf`, returning the number of bytes copied.
+    fn copy_to(&mut self, buf: &mut [u8]) -> usize {
+        let pending = &self.output[self.position..];
+        let len = pending.len().min(buf.len());
+        buf[..len].copy_from_slice(&pending[..len]);
+        self.position += len;
+        len
+    }
+
+    /// Reads the next chunk of input from `inner`, returning it along with an output buffer of
+    /// `output_len(input.len())` bytes.
+    fn fill<R: Read>(
+        &mut self,
+        inner: &mut R,
+        output_len: impl FnOnce(usize) -> usize,
+    ) -> io::Result<(&[u8], &mut [u8])> {
+        self.input.resize(IO_CHUNK_LEN, 0);
+        let len = inner.read(&mut self.input)?;
+        self.output.resize(output_len(len), 0);
+        self.position = 0;
+        Ok((&self.input[..len], &mut self.output))
+    }
+
+    /// Marks the first `len` bytes of the output buffer as pending.
+    fn set_pending(&mut self, len: usize) {
+        self.output.truncate(len);
+    }
+}
+
+/// A [`Read`] adapter that encrypts the data read from an inner reader with a
+/// [`StreamingEncryptingKey`].
+///
+/// The encryption is finished when the inner reader reaches end-of-file, after which the context
+/// needed to decrypt the ciphertext can be obtained with [`Self::finish`].
+pub struct EncryptingReader<R> {
+    key: Option<StreamingEncryptingKey>,
+    context: Option<DecryptionContext>,
+    inner: R,
+    buffer: ReadBuffer,
+}
+
+impl<R: Read> EncryptingReader<R> {
+    /// Constructs a reader that encrypts the plaintext read from `inner` with `key`.
+    #[must_use]
+    pub fn new(key: StreamingEncryptingKey, inner: R) -> Self {
+        Self {
+            key: Some(key),
+            context: None,
+            inner,
+            buffer: ReadBuffer::new(),
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader and the context needed to decrypt the ciphertext.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the ciphertext has not been read to the end.
+    pub fn finish(self) -> Result<(R, DecryptionContext), Unspecified> {
+        match self.context {
+            Some(context) if self.buffer.is_empty() => Ok((self.inner, context)),
+            _ => Err(Unspecified),
+        }
+    }
+}
+
+impl<R: Read> Read for EncryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.buffer.is_empty() && !buf.is_empty() {
+            let key = match self.key.as_mut() {
+                Some(key) => key,
+                None => return Ok(0),
+            };
+            let block_len = key.algorithm().block_len();
+            let finish_output_len = key.finish_output_len();
+            let (input, output) = self.buffer.fill(&mut self.inner, |len| match len {
+                0 => finish_output_len,
+                len => len + block_len,
+            })?;
+            let written_len = if input.is_empty() {
+                let key = self.key.take().ok_or(Unspecified);
+                let (context, update) = key
+                    .and_then(|key| key.finish(output))
+                    .map_err(io_error(io::ErrorKind::Other))?;
+                self.context = Some(context);
+                update.written().len()
+            } else {
+                key.update(input, output)
+                    .map_err(io_error(io::ErrorKind::Other))?
+                    .written()
+                    .len()
+            };
+            self.buffer.set_pending(written_len);
+        }
+        Ok(self.buffer.copy_to(buf))
+    }
+}
+
+impl<R> Debug for EncryptingReader<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EncryptingReader")
+            .field("finished", &self.key.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Read`] adapter that decrypts the data read from an inner reader with a
+/// [`StreamingDecryptingKey`].
+///
+/// The decryption is finished when the inner reader reaches end-of-file; in CBC mode, a read
+/// returns an error of kind [`io::ErrorKind::InvalidData`] if the padding is invalid.
+pub struct DecryptingReader<R> {
+    key: Option<StreamingDecryptingKey>,
+    inner: R,
+    buffer: ReadBuffer,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    /// Constructs a reader that decrypts the ciphertext read from `inner` with `key`.
+    #[must_use]
+    pub fn new(key: StreamingDecryptingKey, inner: R) -> Self {
+        Self {
+            key: Some(key),
+            inner,
+            buffer: ReadBuffer::new(),
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the plaintext has not been read to the end.
+    pub fn finish(self) -> Result<R, Unspecified> {
+        if self.key.is_some() || !self.buffer.is_empty() {
+            return Err(Unspecified);
+        }
+        Ok(self.inner)
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.buffer.is_empty() && !buf.is_empty() {
+            let key = match self.key.as_mut() {
+                Some(key) => key,
+                None => return Ok(0),
+            };
+            let block_len = key.algorithm().block_len();
+            let finish_output_len = key.finish_output_len();
+            let (input, output) = self.buffer.fill(&mut self.inner, |len| match len {
+                0 => finish_output_len,
+                len => len + block_len,
+            })?;
+            let written_len = if input.is_empty() {
+                self.key
+                    .take()
+                    .ok_or(Unspecified)
+                    .and_then(|key| key.finish(output))
+                    .map_err(io_error(io::ErrorKind::InvalidData))?
+                    .written()
+                    .len()
+            } else {
+                key.update(input, output)
+                    .map_err(io_error(io::ErrorKind::InvalidData))?
+                    .written()
+                    .len()
+            };
+            self.buffer.set_pending(written_len);
+        }
+        Ok(self.buffer.copy_to(buf))
+    }
+}
+
+impl<R> Debug for DecryptingReader<R> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DecryptingReader")
+            .field("finished", &self.key.is_none())
+            .finish_non_exhaustive()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::cipher::{
-        DecryptionContext, EncryptionContext, OperatingMode, StreamingDecryptingKey,
-        StreamingEncryptingKey, UnboundCipherKey, AES_128, AES_256, AES_256_KEY_LEN,
+        DecryptingReader, DecryptingWriter, DecryptionContext, EncryptingReader, EncryptingWriter,
+        EncryptionContext, OperatingMode, StreamingDecryptingKey, StreamingEncryptingKey,
+        UnboundCipherKey, AES_128, AES_128_KEY_LEN, AES_256, AES_256_KEY_LEN, AES_256_XTS,
+        AES_256_XTS_KEY_LEN,
     };
     use crate::iv::{FixedLength, IV_LEN_128_BIT};
     use crate::rand::{SecureRandom, SystemRandom};
     use crate::test::from_hex;
     use paste::*;
+    use std::io::{Read, Write};
 
     fn step_encrypt(
         mut encrypting_key: StreamingEncryptingKey,
@@ -423,7 +1408,15 @@ mod tests {
                 break;
             }
         }
-        let out_end = out_idx + alg.block_len();
+        // XTS buffers the whole data unit and writes it when finishing, and the ciphertext
+        // stealing modes hold back up to two blocks.
+        let out_end = match mode {
+            OperatingMode::XTS => out_idx + n,
+            OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => {
+                out_idx + 2 * alg.block_len()
+            }
+            _ => out_idx + alg.block_len(),
+        };
         let (decrypt_iv, output) = encrypting_key
             .finish(&mut ciphertext[out_idx..out_end])
             .unwrap();
@@ -434,7 +1427,14 @@ mod tests {
                 assert!(ciphertext.len() > plaintext.len());
                 assert!(ciphertext.len() <= plaintext.len() + alg.block_len());
             }
-            OperatingMode::CTR => {
+            OperatingMode::CTR
+            | OperatingMode::XTS
+            | OperatingMode::CFB8
+            | OperatingMode::CFB128
+            | OperatingMode::OFB
+            | OperatingMode::CBC_CS1
+            | OperatingMode::CBC_CS2
+            | OperatingMode::CBC_CS3 => {
                 assert_eq!(ciphertext.len(), plaintext.len());
             }
         }
@@ -472,7 +1472,15 @@ mod tests {
                 break;
             }
         }
-        let out_end = out_idx + alg.block_len();
+        // XTS buffers the whole data unit and writes it when finishing, and the ciphertext
+        // stealing modes hold back up to two blocks.
+        let out_end = match mode {
+            OperatingMode::XTS => out_idx + n,
+            OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => {
+                out_idx + 2 * alg.block_len()
+            }
+            _ => out_idx + alg.block_len(),
+        };
         let output = decrypting_key
             .finish(&mut plaintext[out_idx..out_end])
             .unwrap();
@@ -483,7 +1491,14 @@ mod tests {
                 assert!(ciphertext.len() > plaintext.len());
                 assert!(ciphertext.len() <= plaintext.len() + alg.block_len());
             }
-            OperatingMode::CTR => {
+            OperatingMode::CTR
+            | OperatingMode::XTS
+            | OperatingMode::CFB8
+            | OperatingMode::CFB128
+            | OperatingMode::OFB
+            | OperatingMode::CBC_CS1
+            | OperatingMode::CBC_CS2
+            | OperatingMode::CBC_CS3 => {
                 assert_eq!(ciphertext.len(), plaintext.len());
             }
         }
@@ -519,6 +1534,102 @@ mod tests {
 
     helper_stream_step_encrypt_test!(cbc_pkcs7);
     helper_stream_step_encrypt_test!(ctr);
+    helper_stream_step_encrypt_test!(xts);
+    helper_stream_step_encrypt_test!(cbc_cts);
+
+    #[test]
+    fn test_step_cbc_cts() {
+        let random = SystemRandom::new();
+        let mut key = [0u8; AES_128_KEY_LEN];
+        random.fill(&mut key).unwrap();
+
+        for mode in [
+            OperatingMode::CBC_CS1,
+            OperatingMode::CBC_CS2,
+            OperatingMode::CBC_CS3,
+        ] {
+            let encrypting_key_creator = || {
+                let key = UnboundCipherKey::new(&AES_128, &key.clone()).unwrap();
+                match mode {
+                    OperatingMode::CBC_CS1 => StreamingEncryptingKey::cbc_cs1(key),
+                    OperatingMode::CBC_CS2 => StreamingEncryptingKey::cbc_cs2(key),
+                    _ => StreamingEncryptingKey::cbc_cs3(key),
+                }
+                .unwrap()
+            };
+            let decrypting_key_creator = |decryption_ctx: DecryptionContext| {
+                let key = UnboundCipherKey::new(&AES_128, &key.clone()).unwrap();
+                match mode {
+                    OperatingMode::CBC_CS1 => StreamingDecryptingKey::cbc_cs1(key, decryption_ctx),
+                    OperatingMode::CBC_CS2 => StreamingDecryptingKey::cbc_cs2(key, decryption_ctx),
+                    _ => StreamingDecryptingKey::cbc_cs3(key, decryption_ctx),
+                }
+                .unwrap()
+            };
+
+            for n in 16..=81 {
+                for step in [1, 7, 16, 17, 32, n] {
+                    helper_test_cbc_cts_stream_encrypt_step_n_bytes(
+                        encrypting_key_creator,
+                        decrypting_key_creator,
+                        n,
+                        step,
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cbc_cts_too_short() {
+        let key = UnboundCipherKey::new(&AES_128, &[0u8; AES_128_KEY_LEN]).unwrap();
+        let mut encrypting_key = StreamingEncryptingKey::cbc_cs3(key).unwrap();
+        let mut output = [0u8; 32];
+        let update = encrypting_key.update(&[0u8; 15], &mut output).unwrap();
+        assert!(update.written().is_empty());
+        assert!(encrypting_key.finish(&mut output).is_err());
+    }
+
+    #[test]
+    fn test_step_xts() {
+        let random = SystemRandom::new();
+        let mut key = [0u8; AES_256_XTS_KEY_LEN];
+        random.fill(&mut key).unwrap();
+
+        let encrypting_key_creator = || {
+            let key = UnboundCipherKey::new(&AES_256_XTS, &key.clone()).unwrap();
+            StreamingEncryptingKey::xts(key).unwrap()
+        };
+        let decrypting_key_creator = |decryption_ctx: DecryptionContext| {
+            let key = UnboundCipherKey::new(&AES_256_XTS, &key.clone()).unwrap();
+            StreamingDecryptingKey::xts(key, decryption_ctx).unwrap()
+        };
+
+        for i in 13..=21 {
+            for j in 124..=131 {
+                helper_test_xts_stream_encrypt_step_n_bytes(
+                    encrypting_key_creator,
+                    decrypting_key_creator,
+                    j,
+                    i,
+                );
+            }
+        }
+        for j in 16..=33 {
+            helper_test_xts_stream_encrypt_step_n_bytes(
+                encrypting_key_creator,
+                decrypting_key_creator,
+                j,
+                1,
+            );
+            helper_test_xts_stream_encrypt_step_n_bytes(
+                encrypting_key_creator,
+                decrypting_key_creator,
+                j,
+                j,
+            );
+        }
+    }
 
     #[test]
     fn test_step_cbc() {
@@ -783,4 +1894,252 @@ mod tests {
         2,
         9
     );
+
+    // RFC 3962 Appendix B
+    streaming_cipher_kat!(
+        test_rfc3962_aes_128_cbc_cs3_17_bytes,
+        &AES_128,
+        OperatingMode::CBC_CS3,
+        "636869636b656e207465726979616b69",
+        "00000000000000000000000000000000",
+        "4920776f756c64206c696b652074686520",
+        "c6353568f2bf8cb4d8a580362da7ff7f97",
+        1,
+        49
+    );
+
+    streaming_cipher_kat!(
+        test_rfc3962_aes_128_cbc_cs3_31_bytes,
+        &AES_128,
+        OperatingMode::CBC_CS3,
+        "636869636b656e207465726979616b69",
+        "00000000000000000000000000000000",
+        "4920776f756c64206c696b65207468652047656e6572616c20476175277320",
+        "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5",
+        1,
+        49
+    );
+
+    streaming_cipher_kat!(
+        test_rfc3962_aes_128_cbc_cs3_48_bytes,
+        &AES_128,
+        OperatingMode::CBC_CS3,
+        "636869636b656e207465726979616b69",
+        "00000000000000000000000000000000",
+        "4920776f756c64206c696b65207468652047656e6572616c20476175277320436869636b656e2c20706c656173652c20",
+        "97687268d6ecccc0c07b25e25ecfe5849dad8bbb96c4cdc03bc103e1a194bbd839312523a78662d5be7fcbcc98ebf5a8",
+        1,
+        49
+    );
+
+    // The CBC-AES128 example of NIST SP 800-38A F.2.1, truncated to a partial final block.
+    streaming_cipher_kat!(
+        test_sp800_38a_aes_128_cbc_cs1_47_bytes,
+        &AES_128,
+        OperatingMode::CBC_CS1,
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52",
+        "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a9176789dc43313f849e395374eac6507d949b7",
+        1,
+        49
+    );
+
+    streaming_cipher_kat!(
+        test_sp800_38a_aes_128_cbc_cs2_47_bytes,
+        &AES_128,
+        OperatingMode::CBC_CS2,
+        "2b7e151628aed2a6abf7158809cf4f3c",
+        "000102030405060708090a0b0c0d0e0f",
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52",
+        "7649abac8119b246cee98e9b12e9197d9dc43313f849e395374eac6507d949b75086cb9b507219ee95db113a917678",
+        1,
+        49
+    );
+
+    fn read_in_chunks<R: Read>(reader: &mut R, chunk_len: usize) -> std::io::Result<Vec<u8>> {
+        let mut output = Vec::new();
+        let mut chunk = vec![0u8; chunk_len];
+        loop {
+            let len = reader.read(&mut chunk)?;
+            if len == 0 {
+                return Ok(output);
+            }
+            output.extend_from_slice(&chunk[..len]);
+        }
+    }
+
+    fn io_round_trip(
+        encrypting_key_creator: impl Fn() -> StreamingEncryptingKey,
+        decrypting_key_creator: impl Fn(DecryptionContext) -> StreamingDecryptingKey,
+        plaintext: &[u8],
+    ) {
+        let mut writer = EncryptingWriter::new(encrypting_key_creator(), Vec::new());
+        std::io::copy(&mut &plaintext[..], &mut writer).unwrap();
+        let (ciphertext, context) = writer.finish().unwrap();
+        let mut writer = DecryptingWriter::new(decrypting_key_creator(context), Vec::new());
+        for chunk in ciphertext.chunks(5) {
+            writer.write_all(chunk).unwrap();
+        }
+        assert_eq!(plaintext, writer.finish().unwrap().as_slice());
+
+        for chunk_len in [1, 7, 16, 4096] {
+            let mut reader = EncryptingReader::new(encrypting_key_creator(), plaintext);
+            let ciphertext = read_in_chunks(&mut reader, chunk_len).unwrap();
+            let (_, context) = reader.finish().unwrap();
+            let mut reader =
+                DecryptingReader::new(decrypting_key_creator(context), ciphertext.as_slice());
+            assert_eq!(plaintext, read_in_chunks(&mut reader, chunk_len).unwrap());
+            assert!(reader.finish().is_ok());
+        }
+    }
+
+    #[test]
+    fn test_io_adapters() {
+        let random = SystemRandom::new();
+        let mut key = [0u8; AES_256_XTS_KEY_LEN];
+        random.fill(&mut key).unwrap();
+        let mut plaintext = vec![0u8; 100_000];
+        random.fill(&mut plaintext).unwrap();
+
+        macro_rules! io_round_trip {
+            ($algorithm:expr, $key_len:expr, $encrypting:ident, $decrypting:ident) => {
+                let key = &key[..$key_len];
+                for len in [16, 17, 31, 32, 100_000] {
+                    io_round_trip(
+                        || {
+                            let key = UnboundCipherKey::new($algorithm, key).unwrap();
+                            StreamingEncryptingKey::$encrypting(key).unwrap()
+                        },
+                        |context| {
+                            let key = UnboundCipherKey::new($algorithm, key).unwrap();
+                            StreamingDecryptingKey::$decrypting(key, context).unwrap()
+                        },
+                        &plaintext[..len],
+                    );
+                }
+            };
+        }
+
+        io_round_trip!(&AES_128, AES_128_KEY_LEN, cbc_pkcs7, cbc_pkcs7);
+        io_round_trip!(&AES_256, AES_256_KEY_LEN, cbc_pkcs7, cbc_pkcs7);
+        io_round_trip!(&AES_256, AES_256_KEY_LEN, ctr, ctr);
+        io_round_trip!(&AES_256, AES_256_KEY_LEN, cfb8, cfb8);
+        io_round_trip!(&AES_256, AES_256_KEY_LEN, cfb128, cfb128);
+        io_round_trip!(&AES_256, AES_256_KEY_LEN, ofb, ofb);
+        io_round_trip!(&AES_256_XTS, AES_256_XTS_KEY_LEN, xts, xts);
+    }
+
+    /// A writer that fails every other call until it has failed `failures` times, and otherwise
+    /// accepts at most 7 bytes.
+    struct FlakyWriter {
+        written: Vec<u8>,
+        calls: usize,
+        failures: usize,
+    }
+
+    impl Write for FlakyWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.calls += 1;
+            if self.calls % 2 == 0 && self.failures > 0 {
+                self.failures -= 1;
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            let len = buf.len().min(7);
+            self.written.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Writes all of `data` to `writer`, retrying after errors, then retries `flush` until all
+    // pending output has reached the inner writer.
+    fn write_with_retries<W: Write>(writer: &mut W, data: &[u8]) {
+        let mut data = data;
+        while !data.is_empty() {
+            if let Ok(len) = writer.write(&data[..data.len().min(10)]) {
+                data = &data[len..];
+            }
+        }
+        while writer.flush().is_err() {}
+    }
+
+    #[test]
+    fn test_io_writers_inner_errors() {
+        let key = [0x42u8; AES_256_KEY_LEN];
+        let plaintext = [0x24u8; 100];
+        let flaky = || FlakyWriter {
+            written: Vec::new(),
+            calls: 0,
+            failures: 5,
+        };
+
+        let encrypting_key =
+            StreamingEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_256, &key).unwrap())
+                .unwrap();
+        let mut writer = EncryptingWriter::new(encrypting_key, flaky());
+        write_with_retries(&mut writer, &plaintext);
+        // Every complete block has reached the inner writer; the final padded block is written by
+        // `finish`.
+        assert_eq!(96, writer.get_ref().written.len());
+        assert_eq!(0, writer.get_ref().failures);
+        let (inner, context) = writer.finish().unwrap();
+        let ciphertext = inner.written;
+        assert_eq!(112, ciphertext.len());
+
+        let decrypting_key = StreamingDecryptingKey::cbc_pkcs7(
+            UnboundCipherKey::new(&AES_256, &key).unwrap(),
+            context,
+        )
+        .unwrap();
+        let mut writer = DecryptingWriter::new(decrypting_key, flaky());
+        write_with_retries(&mut writer, &ciphertext);
+        assert_eq!(0, writer.get_ref().failures);
+        assert_eq!(plaintext.as_slice(), writer.finish().unwrap().written);
+    }
+
+    #[test]
+    fn test_io_adapters_errors() {
+        let key =
+            from_hex("d4a8206dcae01242f9db79a4ecfe277d0f7bb8ccbafd8f9809adb39f35aa9b41").unwrap();
+        let iv = from_hex("24f6076548fb9d93c8f7ed9f6e661ef9").unwrap();
+        let ciphertext =
+            from_hex("f1af484830a149ee0387b854d65fe87ca0e62efc1c8e6909d4b9ab8666470453").unwrap();
+        let decrypting_key = || {
+            let key = UnboundCipherKey::new(&AES_256, &key).unwrap();
+            let context =
+                DecryptionContext::Iv128(FixedLength::<IV_LEN_128_BIT>::try_from(&iv[..]).unwrap());
+            StreamingDecryptingKey::cbc_pkcs7(key, context).unwrap()
+        };
+
+        let mut reader = DecryptingReader::new(decrypting_key(), ciphertext.as_slice());
+        assert_eq!(
+            from_hex("a39c1fdf77ea3e1f18178c0ec237c70a").unwrap(),
+            read_in_chunks(&mut reader, 3).unwrap()
+        );
+
+        // A truncated ciphertext fails when the decryption is finished.
+        let truncated = &ciphertext[..ciphertext.len() - 1];
+        let mut reader = DecryptingReader::new(decrypting_key(), truncated);
+        let err = read_in_chunks(&mut reader, 3).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+        let mut writer = DecryptingWriter::new(decrypting_key(), Vec::new());
+        writer.write_all(truncated).unwrap();
+        let err = writer.finish().unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+
+        // The readers cannot be finished before reaching end-of-file.
+        let mut reader = DecryptingReader::new(decrypting_key(), ciphertext.as_slice());
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf).unwrap();
+        assert!(reader.finish().is_err());
+        let key = UnboundCipherKey::new(&AES_256, &key).unwrap();
+        let encrypting_key = StreamingEncryptingKey::ctr(key).unwrap();
+        let mut reader = EncryptingReader::new(encrypting_key, ciphertext.as_slice());
+        reader.read_exact(&mut buf).unwrap();
+        assert!(reader.finish().is_err());
+    }
 }
@@ -0,0 +1,94 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::cipher::{OperatingMode, AES_256_XTS, AES_BLOCK_LEN, AES_XTS_TWEAK_LEN};
+use crate::constant_time;
+use crate::error::Unspecified;
+use crate::fips::indicator_check;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    EVP_CIPHER_CTX_new, EVP_DecryptInit_ex, EVP_DecryptUpdate, EVP_EncryptInit_ex,
+    EVP_EncryptUpdate, EVP_CIPHER_CTX,
+};
+use core::ptr::null_mut;
+
+/// Rejects XTS keys whose two halves are equal, as required by FIPS 140-3 IG C.I.
+pub(crate) fn check_key_halves(key_bytes: &[u8]) -> Result<(), Unspecified> {
+    if key_bytes.len() % 2 != 0 {
+        return Err(Unspecified);
+    }
+    let (data_key, tweak_key) = key_bytes.split_at(key_bytes.len() / 2);
+    if constant_time::verify_slices_are_equal(data_key, tweak_key).is_ok() {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// Encrypts or decrypts a single XTS data unit in place.
+///
+/// A data unit that is not a multiple of the block length is processed with ciphertext stealing,
+/// but it must be at least one block long.
+pub(crate) fn aes_256_xts(
+    key_bytes: &[u8],
+    tweak: &[u8],
+    in_out: &mut [u8],
+    encrypt: bool,
+) -> Result<(), Unspecified> {
+    if in_out.len() < AES_BLOCK_LEN || tweak.len() != AES_XTS_TWEAK_LEN {
+        return Err(Unspecified);
+    }
+    let cipher = OperatingMode::XTS.evp_cipher(&AES_256_XTS)?;
+    let mut cipher_ctx: LcPtr<EVP_CIPHER_CTX> = LcPtr::new(unsafe { EVP_CIPHER_CTX_new() })?;
+    let in_out_len: i32 = in_out.len().try_into()?;
+    let mut out_len: i32 = 0;
+
+    if encrypt {
+        if 1 != unsafe {
+            EVP_EncryptInit_ex(
+                *cipher_ctx.as_mut(),
+                *cipher,
+                null_mut(),
+                key_bytes.as_ptr(),
+                tweak.as_ptr(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        if 1 != indicator_check!(unsafe {
+            EVP_EncryptUpdate(
+                *cipher_ctx.as_mut(),
+                in_out.as_mut_ptr(),
+                &mut out_len,
+                in_out.as_ptr(),
+                in_out_len,
+            )
+        }) {
+            return Err(Unspecified);
+        }
+    } else {
+        if 1 != unsafe {
+            EVP_DecryptInit_ex(
+                *cipher_ctx.as_mut(),
+                *cipher,
+                null_mut(),
+                key_bytes.as_ptr(),
+                tweak.as_ptr(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        if 1 != indicator_check!(unsafe {
+            EVP_DecryptUpdate(
+                *cipher_ctx.as_mut(),
+                in_out.as_mut_ptr(),
+                &mut out_len,
+                in_out.as_ptr(),
+                in_out_len,
+            )
+        }) {
+            return Err(Unspecified);
+        }
+    }
+    debug_assert_eq!(out_len, in_out_len);
+    Ok(())
+}
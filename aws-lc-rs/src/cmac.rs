@@ -0,0 +1,442 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! CMAC is specified in [RFC 4493] and [NIST SP 800-38B].
+//!
+//! After a `Key` is constructed, it can be used for multiple signing or
+//! verification operations. Separating the construction of the key from the
+//! rest of the CMAC operation allows the per-key precomputation (the AES key
+//! schedule and CMAC subkeys) to be done only once, instead of it being done
+//! in every CMAC operation.
+//!
+//! Frequently all the data to be signed in a message is available in a single
+//! contiguous piece. In that case, the module-level `sign` function can be
+//! used. Otherwise, if the input is in multiple parts, `Context` should be
+//! used.
+//!
+//! # Examples:
+//!
+//! ## Signing a value and verifying it wasn't tampered with
+//!
+//! ```
+//! use aws_lc_rs::{cmac, rand};
+//!
+//! let rng = rand::SystemRandom::new();
+//! let key = cmac::Key::generate(cmac::CMAC_AES_128, &rng)?;
+//!
+//! let msg = "hello, world";
+//!
+//! let tag = cmac::sign(&key, msg.as_bytes());
+//!
+//! // [We give access to the message to an untrusted party, and they give it
+//! // back to us. We need to verify they didn't tamper with it.]
+//!
+//! cmac::verify(&key, msg.as_bytes(), tag.as_ref())?;
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//!
+//! ## Using the multi-part API:
+//! ```
+//! use aws_lc_rs::{cmac, rand};
+//!
+//! let parts = ["hello", ", ", "world"];
+//!
+//! // The sender generates a secure key value and signs the message with it.
+//! // Note that in a real protocol, a key agreement protocol would be used to
+//! // derive `key_value`.
+//! let rng = rand::SystemRandom::new();
+//! let key_value: [u8; 32] = rand::generate(&rng)?.expose();
+//!
+//! let s_key = cmac::Key::new(cmac::CMAC_AES_256, key_value.as_ref())?;
+//! let mut s_ctx = cmac::Context::with_key(&s_key);
+//! for part in &parts {
+//!     s_ctx.update(part.as_bytes());
+//! }
+//! let tag = s_ctx.sign();
+//!
+//! // The receiver (somehow!) knows the key value, and uses it to verify the
+//! // integrity of the message.
+//! let v_key = cmac::Key::new(cmac::CMAC_AES_256, key_value.as_ref())?;
+//! let mut msg = Vec::<u8>::new();
+//! for part in &parts {
+//!     msg.extend(part.as_bytes());
+//! }
+//! cmac::verify(&v_key, &msg.as_ref(), tag.as_ref())?;
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//! [RFC 4493]: https://tools.ietf.org/html/rfc4493
+//! [NIST SP 800-38B]: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-38b.pdf
+
+use crate::error::Unspecified;
+use crate::fips::indicator_check;
+use crate::ptr::{ConstPointer, LcPtr};
+use crate::{constant_time, hkdf};
+use aws_lc::{
+    CMAC_CTX_copy, CMAC_CTX_new, CMAC_Final, CMAC_Init, CMAC_Update, EVP_aes_128_cbc,
+    EVP_aes_192_cbc, EVP_aes_256_cbc, CMAC_CTX, EVP_CIPHER,
+};
+use core::ptr::null_mut;
+
+/// The length of a CMAC tag, which is the AES block length.
+pub const TAG_LEN: usize = 16;
+
+const MAX_KEY_LEN: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AlgorithmId {
+    Aes128,
+    Aes192,
+    Aes256,
+}
+
+/// A CMAC algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Algorithm {
+    id: AlgorithmId,
+    key_len: usize,
+}
+
+impl Algorithm {
+    /// The length in bytes of keys for this algorithm.
+    #[inline]
+    #[must_use]
+    pub fn key_len(&self) -> usize {
+        self.key_len
+    }
+
+    /// The length in bytes of tags produced by this algorithm.
+    #[inline]
+    #[must_use]
+    pub fn tag_len(&self) -> usize {
+        TAG_LEN
+    }
+
+    fn evp_cipher(&self) -> Result<ConstPointer<EVP_CIPHER>, Unspecified> {
+        Ok(ConstPointer::new(match self.id {
+            AlgorithmId::Aes128 => unsafe { EVP_aes_128_cbc() },
+            AlgorithmId::Aes192 => unsafe { EVP_aes_192_cbc() },
+            AlgorithmId::Aes256 => unsafe { EVP_aes_256_cbc() },
+        })?)
+    }
+}
+
+/// CMAC using AES-128.
+pub static CMAC_AES_128: Algorithm = Algorithm {
+    id: AlgorithmId::Aes128,
+    key_len: 16,
+};
+
+/// CMAC using AES-192.
+pub static CMAC_AES_192: Algorithm = Algorithm {
+    id: AlgorithmId::Aes192,
+    key_len: 24,
+};
+
+/// CMAC using AES-256.
+pub static CMAC_AES_256: Algorithm = Algorithm {
+    id: AlgorithmId::Aes256,
+    key_len: 32,
+};
+
+/// A CMAC tag.
+///
+/// For a given tag `t`, use `t.as_ref()` to get the tag value as a byte slice.
+#[derive(Clone, Copy, Debug)]
+pub struct Tag([u8; TAG_LEN]);
+
+impl AsRef<[u8]> for Tag {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+struct LcCmacCtx(LcPtr<CMAC_CTX>);
+
+impl LcCmacCtx {
+    fn try_clone(&self) -> Result<Self, Unspecified> {
+        let mut ctx = LcPtr::new(unsafe { CMAC_CTX_new() })?;
+        if 1 != unsafe { CMAC_CTX_copy(*ctx.as_mut(), *self.0.as_const()) } {
+            return Err(Unspecified);
+        }
+        Ok(LcCmacCtx(ctx))
+    }
+}
+
+unsafe impl Send for LcCmacCtx {}
+
+impl Clone for LcCmacCtx {
+    fn clone(&self) -> Self {
+        self.try_clone().expect("Unable to clone LcCmacCtx")
+    }
+}
+
+/// A key to use for CMAC signing.
+//
+// # FIPS
+// Use this type with one of the following algorithms:
+// * `CMAC_AES_128`
+// * `CMAC_AES_256`
+#[derive(Clone)]
+pub struct Key {
+    algorithm: Algorithm,
+    ctx: LcCmacCtx,
+}
+
+unsafe impl Send for Key {}
+// All uses of *mut CMAC_CTX require the creation of a Context, which will clone the Key.
+unsafe impl Sync for Key {}
+
+#[allow(clippy::missing_fields_in_debug)]
+impl core::fmt::Debug for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        f.debug_struct("Key")
+            .field("algorithm", &self.algorithm)
+            .finish()
+    }
+}
+
+impl Key {
+    /// Generate a CMAC signing key for the given algorithm with a random
+    /// value generated from `rng`.
+    //
+    // # FIPS
+    // Use this function with one of the following algorithms:
+    // * `CMAC_AES_128`
+    // * `CMAC_AES_256`
+    //
+    /// # Errors
+    /// `error::Unspecified` if the `rng` fails.
+    pub fn generate(
+        algorithm: Algorithm,
+        rng: &dyn crate::rand::SecureRandom,
+    ) -> Result<Self, Unspecified> {
+        Self::construct(algorithm, |buf| rng.fill(buf))
+    }
+
+    fn construct<F>(algorithm: Algorithm, fill: F) -> Result<Self, Unspecified>
+    where
+        F: FnOnce(&mut [u8]) -> Result<(), Unspecified>,
+    {
+        let mut key_bytes = [0; MAX_KEY_LEN];
+        let key_bytes = &mut key_bytes[..algorithm.key_len];
+        fill(key_bytes)?;
+        Self::new(algorithm, key_bytes)
+    }
+
+    /// Construct a CMAC signing key for the given algorithm and key value.
+    ///
+    /// `key_value` should be a value generated using a secure random number
+    /// generator or derived from a random key by a key derivation function
+    /// (e.g. `aws_lc_rs::hkdf`). In particular, `key_value` shouldn't be a
+    /// password.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `key_value.len()` is not `algorithm.key_len()`.
+    pub fn new(algorithm: Algorithm, key_value: &[u8]) -> Result<Self, Unspecified> {
+        if key_value.len() != algorithm.key_len {
+            return Err(Unspecified);
+        }
+        let cipher = algorithm.evp_cipher()?;
+        let mut ctx = LcPtr::new(unsafe { CMAC_CTX_new() })?;
+        if 1 != unsafe {
+            CMAC_Init(
+                *ctx.as_mut(),
+                key_value.as_ptr().cast(),
+                key_value.len(),
+                *cipher,
+                null_mut(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            algorithm,
+            ctx: LcCmacCtx(ctx),
+        })
+    }
+
+    /// The algorithm for the key.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+}
+
+impl hkdf::KeyType for Algorithm {
+    #[inline]
+    fn len(&self) -> usize {
+        self.key_len
+    }
+}
+
+impl From<hkdf::Okm<'_, Algorithm>> for Key {
+    fn from(okm: hkdf::Okm<Algorithm>) -> Self {
+        Self::construct(*okm.len(), |buf| okm.fill(buf)).unwrap()
+    }
+}
+
+/// A context for multi-step (Init-Update-Finish) CMAC signing.
+///
+/// Use `sign` for single-step CMAC signing.
+#[derive(Clone)]
+pub struct Context {
+    key: Key,
+}
+
+unsafe impl Send for Context {}
+
+impl core::fmt::Debug for Context {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        f.debug_struct("Context")
+            .field("algorithm", &self.key.algorithm)
+            .finish()
+    }
+}
+
+impl Context {
+    /// Constructs a new CMAC signing context using the given key.
+    #[inline]
+    #[must_use]
+    pub fn with_key(signing_key: &Key) -> Self {
+        Self {
+            key: signing_key.clone(),
+        }
+    }
+
+    /// Updates the CMAC with all the data in `data`. `update` may be called
+    /// zero or more times until `sign` is called.
+    ///
+    /// # Panics
+    /// Panics if the CMAC cannot be updated
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        Self::try_update(self, data).expect("CMAC_Update failed");
+    }
+
+    #[inline]
+    fn try_update(&mut self, data: &[u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe { CMAC_Update(*self.key.ctx.0.as_mut(), data.as_ptr(), data.len()) } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Finalizes the CMAC calculation and returns the CMAC value. `sign`
+    /// consumes the context so it cannot be (mis-)used after `sign` has been
+    /// called.
+    ///
+    /// It is generally not safe to implement CMAC verification by comparing
+    /// the return value of `sign` to a tag. Use `verify` for verification
+    /// instead.
+    ///
+    // # FIPS
+    // Use this method with one of the following algorithms:
+    // * `CMAC_AES_128`
+    // * `CMAC_AES_256`
+    //
+    /// # Panics
+    /// Panics if the CMAC calculation cannot be finalized
+    #[inline]
+    #[must_use]
+    pub fn sign(self) -> Tag {
+        Self::try_sign(self).expect("CMAC_Final failed")
+    }
+
+    #[inline]
+    fn try_sign(mut self) -> Result<Tag, Unspecified> {
+        let mut output = [0u8; TAG_LEN];
+        let mut out_len: usize = 0;
+        if 1 != indicator_check!(unsafe {
+            CMAC_Final(*self.key.ctx.0.as_mut(), output.as_mut_ptr(), &mut out_len)
+        }) {
+            return Err(Unspecified);
+        }
+        debug_assert_eq!(out_len, TAG_LEN);
+        Ok(Tag(output))
+    }
+}
+
+/// Calculates the CMAC of `data` using the key `key` in one step.
+///
+/// Use `Context` to calculate CMACs where the input is in multiple parts.
+///
+/// It is generally not safe to implement CMAC verification by comparing the
+/// return value of `sign` to a tag. Use `verify` for verification instead.
+//
+// # FIPS
+// Use this function with one of the following algorithms:
+// * `CMAC_AES_128`
+// * `CMAC_AES_256`
+#[inline]
+#[must_use]
+pub fn sign(key: &Key, data: &[u8]) -> Tag {
+    let mut ctx = Context::with_key(key);
+    ctx.update(data);
+    ctx.sign()
+}
+
+/// Calculates the CMAC of `data` using the signing key `key`, and verifies
+/// whether the resultant value equals `tag`, in one step.
+///
+/// The verification will be done in constant time to prevent timing attacks.
+///
+/// # Errors
+/// `error::Unspecified` if the inputs are not verified.
+//
+// # FIPS
+// Use this function with one of the following algorithms:
+// * `CMAC_AES_128`
+// * `CMAC_AES_256`
+#[inline]
+pub fn verify(key: &Key, data: &[u8], tag: &[u8]) -> Result<(), Unspecified> {
+    constant_time::verify_slices_are_equal(sign(key, data).as_ref(), tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{cmac, rand};
+
+    #[cfg(feature = "fips")]
+    mod fips;
+
+    // Make sure that `Key::generate` and `verify` aren't completely wacky.
+    #[test]
+    pub fn cmac_signing_key_coverage() {
+        const HELLO_WORLD_GOOD: &[u8] = b"hello, world";
+        const HELLO_WORLD_BAD: &[u8] = b"hello, worle";
+
+        let rng = rand::SystemRandom::new();
+
+        for algorithm in &[cmac::CMAC_AES_128, cmac::CMAC_AES_192, cmac::CMAC_AES_256] {
+            let key = cmac::Key::generate(*algorithm, &rng).unwrap();
+            let tag = cmac::sign(&key, HELLO_WORLD_GOOD);
+            println!("{key:?}");
+            assert!(cmac::verify(&key, HELLO_WORLD_GOOD, tag.as_ref()).is_ok());
+            assert!(cmac::verify(&key, HELLO_WORLD_BAD, tag.as_ref()).is_err());
+        }
+    }
+
+    #[test]
+    fn cmac_coverage() {
+        assert_ne!(cmac::CMAC_AES_128, cmac::CMAC_AES_256);
+
+        for &alg in &[cmac::CMAC_AES_128, cmac::CMAC_AES_192, cmac::CMAC_AES_256] {
+            assert!(cmac::Key::new(alg, &[0; 15]).is_err());
+
+            // Clone after updating context with message, then check if the final Tag is the same.
+            let key = cmac::Key::new(alg, &[0; 32][..alg.key_len()]).unwrap();
+            let mut ctx = cmac::Context::with_key(&key);
+            ctx.update(b"hello, world");
+            let ctx_clone = ctx.clone();
+
+            let orig_tag = ctx.sign();
+            let clone_tag = ctx_clone.sign();
+            assert_eq!(orig_tag.as_ref(), clone_tag.as_ref());
+            assert_eq!(orig_tag.clone().as_ref(), clone_tag.as_ref());
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#![cfg(debug_assertions)]
+
+use crate::{
+    cmac::{sign, verify, Key, CMAC_AES_128, CMAC_AES_256},
+    fips::{assert_fips_status_indicator, FipsServiceStatus},
+    rand::{self, SystemRandom},
+};
+
+const TEST_MESSAGE: &str = "test message";
+
+macro_rules! cmac_api {
+    ($name:ident, $alg:expr, $key_len:expr) => {
+        #[test]
+        fn $name() {
+            let rng = SystemRandom::new();
+
+            let key_value: [u8; $key_len] = rand::generate(&rng).unwrap().expose();
+
+            let s_key = Key::new($alg, key_value.as_ref()).unwrap();
+
+            let tag = assert_fips_status_indicator!(
+                sign(&s_key, TEST_MESSAGE.as_bytes()),
+                FipsServiceStatus::Approved
+            );
+
+            let v_key = Key::new($alg, key_value.as_ref()).unwrap();
+
+            assert_fips_status_indicator!(
+                verify(&v_key, TEST_MESSAGE.as_bytes(), tag.as_ref()).unwrap(),
+                FipsServiceStatus::Approved
+            );
+        }
+    };
+}
+
+cmac_api!(aes_128, CMAC_AES_128, 16);
+cmac_api!(aes_256, CMAC_AES_256, 32);
@@ -5,9 +5,13 @@
 
 //! SHA-2 and the legacy SHA-1 digest algorithm.
 //!
+//! The SHAKE128 and SHAKE256 extendable-output functions are available through
+//! `xof` and `XofContext`, except in FIPS builds.
+//!
 //! If all the data is available in a single contiguous slice then the `digest`
 //! function should be used. Otherwise, the digest can be calculated in
-//! multiple steps using `Context`.
+//! multiple steps using `Context`, or while data is read or written using
+//! `DigestReader` or `DigestWriter`.
 
 //! # Example
 //!
@@ -33,6 +37,9 @@ use crate::{debug, derive_debug_via_id};
 
 pub(crate) mod digest_ctx;
 mod sha;
+// SHAKE is not available in AWS-LC's FIPS module.
+#[cfg(not(feature = "fips"))]
+mod xof;
 use crate::error::Unspecified;
 use crate::ptr::ConstPointer;
 use aws_lc::{
@@ -46,8 +53,13 @@ pub use sha::{
     SHA256_OUTPUT_LEN, SHA384, SHA384_OUTPUT_LEN, SHA3_256, SHA3_384, SHA3_512, SHA512, SHA512_256,
     SHA512_256_OUTPUT_LEN, SHA512_OUTPUT_LEN,
 };
+#[cfg(not(feature = "fips"))]
+pub use xof::{
+    xof, XofAlgorithm, XofContext, XofReader, MAX_XOF_READER_OUTPUT_LEN, SHAKE128, SHAKE256,
+};
 // TODO: Uncomment when MSRV >= 1.64
 //use core::ffi::c_uint;
+use std::io::{self, Read, Write};
 use std::os::raw::c_uint;
 
 /// A context for multi-step (Init-Update-Finish) digest calculations.
@@ -168,6 +180,120 @@ impl Context {
     }
 }
 
+/// A [`Write`] adapter that calculates the digest of the data written through it to an inner
+/// writer.
+///
+/// ```
+/// use aws_lc_rs::digest;
+///
+/// let mut writer = digest::DigestWriter::new(&digest::SHA256, Vec::new());
+/// std::io::copy(&mut &b"hello, world"[..], &mut writer).unwrap();
+/// let (output, digest) = writer.finish();
+///
+/// assert_eq!(b"hello, world", output.as_slice());
+/// assert_eq!(
+///     digest::digest(&digest::SHA256, b"hello, world").as_ref(),
+///     digest.as_ref()
+/// );
+/// ```
+#[derive(Clone)]
+pub struct DigestWriter<W> {
+    context: Context,
+    inner: W,
+}
+
+impl<W: Write> DigestWriter<W> {
+    /// Constructs a writer that calculates a digest using `algorithm` of the data written to
+    /// `inner`.
+    #[must_use]
+    pub fn new(algorithm: &'static Algorithm, inner: W) -> Self {
+        Self {
+            context: Context::new(algorithm),
+            inner,
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    #[must_use]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the inner writer and the digest of the data written to it.
+    #[must_use]
+    pub fn finish(self) -> (W, Digest) {
+        (self.inner, self.context.finish())
+    }
+}
+
+impl<W: Write> Write for DigestWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.context.update(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W> core::fmt::Debug for DigestWriter<W> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.debug_struct("DigestWriter")
+            .field("algorithm", self.context.algorithm())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Read`] adapter that calculates the digest of the data read through it from an inner
+/// reader.
+#[derive(Clone)]
+pub struct DigestReader<R> {
+    context: Context,
+    inner: R,
+}
+
+impl<R: Read> DigestReader<R> {
+    /// Constructs a reader that calculates a digest using `algorithm` of the data read from
+    /// `inner`.
+    #[must_use]
+    pub fn new(algorithm: &'static Algorithm, inner: R) -> Self {
+        Self {
+            context: Context::new(algorithm),
+            inner,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader and the digest of the data read from it.
+    #[must_use]
+    pub fn finish(self) -> (R, Digest) {
+        (self.inner, self.context.finish())
+    }
+}
+
+impl<R: Read> Read for DigestReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.context.update(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<R> core::fmt::Debug for DigestReader<R> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt.debug_struct("DigestReader")
+            .field("algorithm", self.context.algorithm())
+            .finish_non_exhaustive()
+    }
+}
+
 /// Returns the digest of `data` using the given digest algorithm.
 ///
 // # FIPS
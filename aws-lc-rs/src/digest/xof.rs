@@ -0,0 +1,252 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use crate::digest::digest_ctx::DigestContext;
+use crate::error::Unspecified;
+use crate::ptr::ConstPointer;
+use aws_lc::{
+    EVP_DigestFinalXOF, EVP_DigestInit_ex, EVP_DigestUpdate, EVP_shake128, EVP_shake256, EVP_MD,
+};
+use core::ptr::null_mut;
+use zeroize::Zeroizing;
+
+/// The length of a block for SHAKE128, in bytes.
+const SHAKE128_BLOCK_LEN: usize = 168;
+
+/// The length of a block for SHAKE256, in bytes.
+const SHAKE256_BLOCK_LEN: usize = 136;
+
+/// The maximum total number of bytes that can be read from an [`XofReader`].
+///
+/// The one-shot [`xof`] function is not subject to this limit.
+pub const MAX_XOF_READER_OUTPUT_LEN: usize = 1 << 20;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum XofAlgorithmID {
+    SHAKE128,
+    SHAKE256,
+}
+
+/// An extendable-output function (XOF) algorithm.
+#[derive(Debug, Eq, PartialEq)]
+pub struct XofAlgorithm {
+    id: XofAlgorithmID,
+    block_len: usize,
+}
+
+impl XofAlgorithm {
+    /// The internal block length (the sponge rate).
+    #[inline]
+    #[must_use]
+    pub fn block_len(&self) -> usize {
+        self.block_len
+    }
+
+    fn evp_md(&self) -> Result<ConstPointer<EVP_MD>, Unspecified> {
+        Ok(ConstPointer::new(match self.id {
+            XofAlgorithmID::SHAKE128 => unsafe { EVP_shake128() },
+            XofAlgorithmID::SHAKE256 => unsafe { EVP_shake256() },
+        })?)
+    }
+}
+
+/// SHAKE128 as specified in [FIPS 202].
+///
+/// [FIPS 202]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+pub static SHAKE128: XofAlgorithm = XofAlgorithm {
+    id: XofAlgorithmID::SHAKE128,
+    block_len: SHAKE128_BLOCK_LEN,
+};
+
+/// SHAKE256 as specified in [FIPS 202].
+///
+/// [FIPS 202]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+pub static SHAKE256: XofAlgorithm = XofAlgorithm {
+    id: XofAlgorithmID::SHAKE256,
+    block_len: SHAKE256_BLOCK_LEN,
+};
+
+/// A context for absorbing the input of an extendable-output function.
+///
+/// Once all input has been absorbed with `update`, `squeeze` converts the
+/// context into an [`XofReader`] from which any amount of output can be read.
+#[derive(Clone)]
+pub struct XofContext {
+    algorithm: &'static XofAlgorithm,
+    digest_ctx: DigestContext,
+}
+
+impl XofContext {
+    /// Constructs a new context.
+    ///
+    /// # Panics
+    ///
+    /// `new` panics if it fails to initialize an aws-lc digest context for the given
+    /// algorithm.
+    #[must_use]
+    pub fn new(algorithm: &'static XofAlgorithm) -> Self {
+        Self::try_new(algorithm).expect("XOF context initialization failed")
+    }
+
+    fn try_new(algorithm: &'static XofAlgorithm) -> Result<Self, Unspecified> {
+        let evp_md = algorithm.evp_md()?;
+        let mut digest_ctx = DigestContext::new_uninit();
+        if 1 != unsafe { EVP_DigestInit_ex(digest_ctx.as_mut_ptr(), *evp_md, null_mut()) } {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            algorithm,
+            digest_ctx,
+        })
+    }
+
+    /// Absorbs all the data in `data`.
+    ///
+    /// # Panics
+    /// Panics if the data cannot be absorbed.
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        Self::try_update(self, data).expect("XOF update failed");
+    }
+
+    #[inline]
+    fn try_update(&mut self, data: &[u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe {
+            EVP_DigestUpdate(
+                self.digest_ctx.as_mut_ptr(),
+                data.as_ptr().cast(),
+                data.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    // Writes the first `out.len()` bytes of output in a single finalization.
+    fn finish(mut self, out: &mut [u8]) -> Result<(), Unspecified> {
+        if 1 != unsafe {
+            EVP_DigestFinalXOF(self.digest_ctx.as_mut_ptr(), out.as_mut_ptr(), out.len())
+        } {
+            return Err(Unspecified);
+        }
+        Ok(())
+    }
+
+    /// Finishes absorbing input and returns a reader for the output.
+    ///
+    /// `squeeze` consumes the context so no more input can be absorbed after
+    /// it has been called.
+    #[inline]
+    #[must_use]
+    pub fn squeeze(self) -> XofReader {
+        XofReader {
+            algorithm: self.algorithm,
+            digest_ctx: self.digest_ctx,
+            output: Zeroizing::new(Vec::new()),
+            position: 0,
+        }
+    }
+
+    /// The algorithm that this context is using.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static XofAlgorithm {
+        self.algorithm
+    }
+}
+
+/// A reader for the output of an extendable-output function.
+///
+/// Output may be read with any number of calls to `squeeze`; the concatenation
+/// of their outputs is the same as the output of a single call of the combined
+/// length.
+///
+/// AWS-LC can only finalize an XOF once, so the reader cannot continue the
+/// sponge where the previous read stopped. When a read needs more output than
+/// has been generated so far, the reader computes the output again from the
+/// start, generating at least twice as much as before. Reading `n` bytes in
+/// total therefore costs up to about twice the work of a single `n` byte read,
+/// and all of the output generated so far is kept in memory. The total output
+/// is limited to [`MAX_XOF_READER_OUTPUT_LEN`] bytes; use [`xof`] when the
+/// output length is known in advance.
+pub struct XofReader {
+    algorithm: &'static XofAlgorithm,
+    // The state after absorbing all input.
+    digest_ctx: DigestContext,
+    // The output generated so far.
+    output: Zeroizing<Vec<u8>>,
+    // The number of bytes of `output` already returned.
+    position: usize,
+}
+
+impl XofReader {
+    /// Fills `out` with the next `out.len()` bytes of output.
+    ///
+    /// # Panics
+    /// Panics if the output cannot be generated, or if the total output read
+    /// from this reader would exceed [`MAX_XOF_READER_OUTPUT_LEN`] bytes.
+    pub fn squeeze(&mut self, out: &mut [u8]) {
+        Self::try_squeeze(self, out).expect("XOF squeeze failed");
+    }
+
+    fn try_squeeze(&mut self, out: &mut [u8]) -> Result<(), Unspecified> {
+        let end = self.position.checked_add(out.len()).ok_or(Unspecified)?;
+        if end > MAX_XOF_READER_OUTPUT_LEN {
+            return Err(Unspecified);
+        }
+        if end > self.output.len() {
+            self.generate(end)?;
+        }
+        out.copy_from_slice(&self.output[self.position..end]);
+        self.position = end;
+        Ok(())
+    }
+
+    // The output is regenerated from a copy of the absorbed state. The amount
+    // generated at least doubles each time, up to the output limit, so that
+    // squeezing in many small pieces costs linear time overall.
+    fn generate(&mut self, min_len: usize) -> Result<(), Unspecified> {
+        let len = min_len
+            .max(self.output.len().saturating_mul(2))
+            .max(self.algorithm.block_len)
+            .min(MAX_XOF_READER_OUTPUT_LEN);
+        let mut output = Zeroizing::new(vec![0u8; len]);
+        let mut ctx = self.digest_ctx.clone();
+        if 1 != unsafe { EVP_DigestFinalXOF(ctx.as_mut_ptr(), output.as_mut_ptr(), len) } {
+            return Err(Unspecified);
+        }
+        self.output = output;
+        Ok(())
+    }
+
+    /// The algorithm that this reader is using.
+    #[inline]
+    #[must_use]
+    pub fn algorithm(&self) -> &'static XofAlgorithm {
+        self.algorithm
+    }
+}
+
+/// Fills `out` with the output of the extendable-output function `algorithm`
+/// applied to `data`.
+///
+/// # Panics
+/// Panics if the output cannot be generated.
+///
+/// # Examples:
+///
+/// ```
+/// use aws_lc_rs::{digest, test};
+/// let expected_hex = "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26";
+/// let expected: Vec<u8> = test::from_hex(expected_hex).unwrap();
+/// let mut actual = [0u8; 32];
+/// digest::xof(&digest::SHAKE128, b"", &mut actual);
+///
+/// assert_eq!(&expected, &actual);
+/// ```
+pub fn xof(algorithm: &'static XofAlgorithm, data: &[u8], out: &mut [u8]) {
+    let mut ctx = XofContext::new(algorithm);
+    ctx.update(data);
+    ctx.finish(out).expect("XOF finalization failed");
+}
@@ -29,8 +29,8 @@ use aws_lc::{
     EC_POINT_point2oct, EVP_DigestVerify, EVP_DigestVerifyInit, EVP_PKEY_CTX_new_id,
     EVP_PKEY_CTX_set_ec_paramgen_curve_nid, EVP_PKEY_assign_EC_KEY, EVP_PKEY_get0_EC_KEY,
     EVP_PKEY_keygen, EVP_PKEY_keygen_init, EVP_PKEY_new, EVP_parse_public_key,
-    NID_X9_62_prime256v1, NID_secp256k1, NID_secp384r1, NID_secp521r1, BIGNUM, CBS, ECDSA_SIG,
-    EC_GROUP, EC_KEY, EC_POINT, EVP_PKEY, EVP_PKEY_EC,
+    NID_X9_62_prime256v1, NID_secp224r1, NID_secp256k1, NID_secp384r1, NID_secp521r1, BIGNUM, CBS,
+    ECDSA_SIG, EC_GROUP, EC_KEY, EC_POINT, EVP_PKEY, EVP_PKEY_EC,
 };
 
 use crate::digest::digest_ctx::DigestContext;
@@ -96,6 +96,7 @@ pub(crate) enum EcdsaSignatureFormat {
 #[derive(Debug, Eq, PartialEq)]
 #[allow(non_camel_case_types)]
 pub(crate) enum AlgorithmID {
+    ECDSA_P224,
     ECDSA_P256,
     ECDSA_P384,
     ECDSA_P521,
@@ -106,6 +107,7 @@ impl AlgorithmID {
     #[inline]
     pub(crate) fn nid(&'static self) -> i32 {
         match self {
+            AlgorithmID::ECDSA_P224 => NID_secp224r1,
             AlgorithmID::ECDSA_P256 => NID_X9_62_prime256v1,
             AlgorithmID::ECDSA_P384 => NID_secp384r1,
             AlgorithmID::ECDSA_P521 => NID_secp521r1,
@@ -114,6 +116,7 @@ impl AlgorithmID {
     }
     pub(crate) fn private_key_size(&self) -> usize {
         match self {
+            AlgorithmID::ECDSA_P224 => 28,
             AlgorithmID::ECDSA_P256 | AlgorithmID::ECDSA_P256K1 => 32,
             AlgorithmID::ECDSA_P384 => 48,
             AlgorithmID::ECDSA_P521 => 66,
@@ -123,6 +126,7 @@ impl AlgorithmID {
     #[inline]
     const fn compressed_pub_key_len(&self) -> usize {
         match self {
+            AlgorithmID::ECDSA_P224 => compressed_public_key_size_bytes(224),
             AlgorithmID::ECDSA_P256 | AlgorithmID::ECDSA_P256K1 => {
                 compressed_public_key_size_bytes(256)
             }
@@ -340,7 +344,7 @@ pub(crate) fn verify_evp_key_nid(
 }
 
 #[inline]
-fn validate_evp_key(
+pub(crate) fn validate_evp_key(
     evp_pkey: &ConstPointer<EVP_PKEY>,
     expected_curve_nid: i32,
 ) -> Result<(), KeyRejected> {
@@ -462,7 +466,7 @@ pub(crate) fn try_parse_public_key_bytes(
         ))
 }
 
-fn try_parse_subject_public_key_info_bytes(
+pub(crate) fn try_parse_subject_public_key_info_bytes(
     key_bytes: &[u8],
 ) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
     // Try to parse as SubjectPublicKeyInfo first
@@ -476,7 +480,7 @@ fn try_parse_subject_public_key_info_bytes(
     Ok(LcPtr::new(unsafe { EVP_parse_public_key(&mut cbs) })?)
 }
 
-fn try_parse_public_key_raw_bytes(
+pub(crate) fn try_parse_public_key_raw_bytes(
     key_bytes: &[u8],
     expected_curve_nid: i32,
 ) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
@@ -13,7 +13,8 @@
 //! Frequently all the data to be signed in a message is available in a single
 //! contiguous piece. In that case, the module-level `sign` function can be
 //! used. Otherwise, if the input is in multiple parts, `Context` should be
-//! used.
+//! used, or `HmacReader` and `HmacWriter` if the input is read or written
+//! through `std::io`.
 //!
 //! # Examples:
 //!
@@ -106,6 +107,7 @@ use core::mem::MaybeUninit;
 use core::ptr::null_mut;
 // TODO: Uncomment when MSRV >= 1.64
 // use core::ffi::c_uint;
+use std::io::{self, Read, Write};
 use std::os::raw::c_uint;
 
 /// A deprecated alias for `Tag`.
@@ -439,6 +441,121 @@ impl Context {
     }
 }
 
+/// A [`Write`] adapter that calculates the HMAC of the data written through it to an inner
+/// writer.
+///
+/// ```
+/// use aws_lc_rs::hmac;
+///
+/// let key = hmac::Key::new(hmac::HMAC_SHA256, b"key");
+/// let mut writer = hmac::HmacWriter::new(&key, Vec::new());
+/// std::io::copy(&mut &b"hello, world"[..], &mut writer).unwrap();
+/// let (output, tag) = writer.sign();
+///
+/// assert_eq!(b"hello, world", output.as_slice());
+/// hmac::verify(&key, b"hello, world", tag.as_ref()).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct HmacWriter<W> {
+    context: Context,
+    inner: W,
+}
+
+impl<W: Write> HmacWriter<W> {
+    /// Constructs a writer that calculates the HMAC using `key` of the data written to `inner`.
+    #[must_use]
+    pub fn new(key: &Key, inner: W) -> Self {
+        Self {
+            context: Context::with_key(key),
+            inner,
+        }
+    }
+
+    /// Returns a reference to the inner writer.
+    #[must_use]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns the inner writer and the HMAC of the data written to it.
+    ///
+    /// # Panics
+    /// Panics if the HMAC calculation cannot be finalized
+    #[must_use]
+    pub fn sign(self) -> (W, Tag) {
+        (self.inner, self.context.sign())
+    }
+}
+
+impl<W: Write> Write for HmacWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = self.inner.write(buf)?;
+        self.context.update(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W> core::fmt::Debug for HmacWriter<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        f.debug_struct("HmacWriter")
+            .field("context", &self.context)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`Read`] adapter that calculates the HMAC of the data read through it from an inner reader.
+#[derive(Clone)]
+pub struct HmacReader<R> {
+    context: Context,
+    inner: R,
+}
+
+impl<R: Read> HmacReader<R> {
+    /// Constructs a reader that calculates the HMAC using `key` of the data read from `inner`.
+    #[must_use]
+    pub fn new(key: &Key, inner: R) -> Self {
+        Self {
+            context: Context::with_key(key),
+            inner,
+        }
+    }
+
+    /// Returns a reference to the inner reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns the inner reader and the HMAC of the data read from it.
+    ///
+    /// # Panics
+    /// Panics if the HMAC calculation cannot be finalized
+    #[must_use]
+    pub fn sign(self) -> (R, Tag) {
+        (self.inner, self.context.sign())
+    }
+}
+
+impl<R: Read> Read for HmacReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.context.update(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<R> core::fmt::Debug for HmacReader<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
+        f.debug_struct("HmacReader")
+            .field("context", &self.context)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Calculates the HMAC of `data` using the key `key` in one step.
 ///
 /// Use `Context` to calculate HMACs where the input is in multiple parts.
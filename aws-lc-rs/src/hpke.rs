@@ -0,0 +1,1135 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! Hybrid Public Key Encryption (HPKE) as specified in [RFC 9180].
+//!
+//! A sender establishes a [`SenderContext`] with the recipient's public key, and transmits the
+//! resulting [`EncapsulatedKey`] to the recipient. The recipient establishes the matching
+//! [`RecipientContext`] from its [`PrivateKey`] and the encapsulated key. Messages sealed by the
+//! sender context can then be opened, in the same order, by the recipient context.
+//!
+//! Base mode is supported by [`SenderContext::new`] and [`RecipientContext::new`]. Auth mode,
+//! in which the sender also authenticates with its own private key, is supported by
+//! [`SenderContext::new_auth`] and [`RecipientContext::new_auth`]. The PSK modes are not
+//! supported.
+//!
+//! # Example
+//!
+//! ```
+//! use aws_lc_rs::hpke::{
+//!     PrivateKey, RecipientContext, SenderContext, Suite, AES_128_GCM,
+//!     DHKEM_X25519_HKDF_SHA256, HKDF_SHA256,
+//! };
+//!
+//! let suite = Suite::new(&DHKEM_X25519_HKDF_SHA256, &HKDF_SHA256, &AES_128_GCM);
+//! let info = b"application info";
+//!
+//! // The recipient generates their private key, and publishes the public key.
+//! let recipient_private_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256)?;
+//! let recipient_public_key = recipient_private_key.public_key()?;
+//!
+//! // The sender sets up a context, and sends the encapsulated key with the sealed messages.
+//! let (encapsulated_key, mut sender) =
+//!     SenderContext::new(&suite, recipient_public_key.as_ref(), info)?;
+//! let first = sender.seal(b"aad", b"first message")?;
+//! let second = sender.seal(b"aad", b"second message")?;
+//!
+//! // The recipient sets up their context, and opens the messages in order.
+//! let mut recipient = RecipientContext::new(
+//!     &suite,
+//!     &recipient_private_key,
+//!     encapsulated_key.as_ref(),
+//!     info,
+//! )?;
+//! assert_eq!(recipient.open(b"aad", &first)?, b"first message");
+//! assert_eq!(recipient.open(b"aad", &second)?, b"second message");
+//!
+//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! ```
+//!
+//! [RFC 9180]: https://www.rfc-editor.org/rfc/rfc9180
+
+use crate::encoding::{AsBigEndian, Curve25519SeedBin};
+use crate::error::{KeyRejected, Unspecified};
+use crate::hex;
+use crate::ptr::LcPtr;
+use aws_lc::{
+    EVP_HPKE_CTX_export, EVP_HPKE_CTX_max_overhead, EVP_HPKE_CTX_new, EVP_HPKE_CTX_open,
+    EVP_HPKE_CTX_seal, EVP_HPKE_CTX_setup_recipient, EVP_HPKE_CTX_setup_sender,
+    EVP_HPKE_CTX_setup_sender_with_seed_for_testing, EVP_HPKE_KEY_generate, EVP_HPKE_KEY_init,
+    EVP_HPKE_KEY_new, EVP_HPKE_KEY_private_key, EVP_HPKE_KEY_public_key, EVP_hpke_aes_128_gcm,
+    EVP_hpke_aes_256_gcm, EVP_hpke_chacha20_poly1305, EVP_hpke_hkdf_sha256,
+    EVP_hpke_x25519_hkdf_sha256, EVP_HPKE_AEAD, EVP_HPKE_CTX, EVP_HPKE_KDF, EVP_HPKE_KEM,
+    EVP_HPKE_KEY, EVP_HPKE_MAX_ENC_LENGTH, EVP_HPKE_MAX_PRIVATE_KEY_LENGTH,
+    EVP_HPKE_MAX_PUBLIC_KEY_LENGTH,
+};
+#[cfg(not(feature = "fips"))]
+use aws_lc::{
+    EVP_HPKE_CTX_setup_auth_recipient, EVP_HPKE_CTX_setup_auth_sender,
+    EVP_HPKE_CTX_setup_auth_sender_with_seed_for_testing,
+};
+use core::fmt;
+use core::fmt::{Debug, Formatter};
+use zeroize::Zeroizing;
+
+const MAX_ENC_LEN: usize = EVP_HPKE_MAX_ENC_LENGTH as usize;
+const MAX_PRIVATE_KEY_LEN: usize = EVP_HPKE_MAX_PRIVATE_KEY_LENGTH as usize;
+const MAX_PUBLIC_KEY_LEN: usize = EVP_HPKE_MAX_PUBLIC_KEY_LENGTH as usize;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KemId {
+    DHKEM_X25519_HKDF_SHA256,
+}
+
+/// An HPKE key encapsulation mechanism (KEM).
+#[derive(PartialEq, Eq)]
+pub struct Kem {
+    id: KemId,
+}
+
+impl Kem {
+    /// The KEM identifier assigned in RFC 9180, Section 7.1.
+    #[must_use]
+    pub fn id(&self) -> u16 {
+        match self.id {
+            KemId::DHKEM_X25519_HKDF_SHA256 => 0x0020,
+        }
+    }
+
+    fn evp_hpke_kem(&self) -> *const EVP_HPKE_KEM {
+        match self.id {
+            KemId::DHKEM_X25519_HKDF_SHA256 => unsafe { EVP_hpke_x25519_hkdf_sha256() },
+        }
+    }
+}
+
+impl Debug for Kem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// DHKEM(X25519, HKDF-SHA256)
+pub static DHKEM_X25519_HKDF_SHA256: Kem = Kem {
+    id: KemId::DHKEM_X25519_HKDF_SHA256,
+};
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum KdfId {
+    HKDF_SHA256,
+}
+
+/// An HPKE key derivation function (KDF).
+#[derive(PartialEq, Eq)]
+pub struct Kdf {
+    id: KdfId,
+}
+
+impl Kdf {
+    /// The KDF identifier assigned in RFC 9180, Section 7.2.
+    #[must_use]
+    pub fn id(&self) -> u16 {
+        match self.id {
+            KdfId::HKDF_SHA256 => 0x0001,
+        }
+    }
+
+    fn evp_hpke_kdf(&self) -> *const EVP_HPKE_KDF {
+        match self.id {
+            KdfId::HKDF_SHA256 => unsafe { EVP_hpke_hkdf_sha256() },
+        }
+    }
+}
+
+impl Debug for Kdf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// HKDF-SHA256
+pub static HKDF_SHA256: Kdf = Kdf {
+    id: KdfId::HKDF_SHA256,
+};
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AeadId {
+    AES_128_GCM,
+    AES_256_GCM,
+    CHACHA20_POLY1305,
+}
+
+/// An HPKE authenticated encryption with associated data (AEAD) algorithm.
+#[derive(PartialEq, Eq)]
+pub struct Aead {
+    id: AeadId,
+}
+
+impl Aead {
+    /// The AEAD identifier assigned in RFC 9180, Section 7.3.
+    #[must_use]
+    pub fn id(&self) -> u16 {
+        match self.id {
+            AeadId::AES_128_GCM => 0x0001,
+            AeadId::AES_256_GCM => 0x0002,
+            AeadId::CHACHA20_POLY1305 => 0x0003,
+        }
+    }
+
+    fn evp_hpke_aead(&self) -> *const EVP_HPKE_AEAD {
+        match self.id {
+            AeadId::AES_128_GCM => unsafe { EVP_hpke_aes_128_gcm() },
+            AeadId::AES_256_GCM => unsafe { EVP_hpke_aes_256_gcm() },
+            AeadId::CHACHA20_POLY1305 => unsafe { EVP_hpke_chacha20_poly1305() },
+        }
+    }
+}
+
+impl Debug for Aead {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        Debug::fmt(&self.id, f)
+    }
+}
+
+/// AES-128-GCM
+pub static AES_128_GCM: Aead = Aead {
+    id: AeadId::AES_128_GCM,
+};
+
+/// AES-256-GCM
+pub static AES_256_GCM: Aead = Aead {
+    id: AeadId::AES_256_GCM,
+};
+
+/// ChaCha20-Poly1305
+pub static CHACHA20_POLY1305: Aead = Aead {
+    id: AeadId::CHACHA20_POLY1305,
+};
+
+/// An HPKE ciphersuite, the combination of a KEM, KDF and AEAD.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suite {
+    kem: &'static Kem,
+    kdf: &'static Kdf,
+    aead: &'static Aead,
+}
+
+impl Suite {
+    /// Constructs a new ciphersuite.
+    #[must_use]
+    pub const fn new(kem: &'static Kem, kdf: &'static Kdf, aead: &'static Aead) -> Self {
+        Self { kem, kdf, aead }
+    }
+
+    /// The KEM of this ciphersuite.
+    #[must_use]
+    pub fn kem(&self) -> &'static Kem {
+        self.kem
+    }
+
+    /// The KDF of this ciphersuite.
+    #[must_use]
+    pub fn kdf(&self) -> &'static Kdf {
+        self.kdf
+    }
+
+    /// The AEAD of this ciphersuite.
+    #[must_use]
+    pub fn aead(&self) -> &'static Aead {
+        self.aead
+    }
+}
+
+/// A KEM private key, used by the recipient to establish a [`RecipientContext`], or by the
+/// sender to authenticate in auth mode.
+pub struct PrivateKey {
+    kem: &'static Kem,
+    key: LcPtr<EVP_HPKE_KEY>,
+}
+
+impl PrivateKey {
+    /// Generates a new private key for the given KEM.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn generate(kem: &'static Kem) -> Result<Self, Unspecified> {
+        let mut key = LcPtr::new(unsafe { EVP_HPKE_KEY_new() })?;
+        if 1 != unsafe { EVP_HPKE_KEY_generate(*key.as_mut(), kem.evp_hpke_kem()) } {
+            return Err(Unspecified);
+        }
+        Ok(Self { kem, key })
+    }
+
+    /// Constructs a private key for the given KEM from its serialized form, as defined by
+    /// `SerializePrivateKey` in RFC 9180.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` if the key is not a valid private key for the KEM.
+    pub fn from_private_key(kem: &'static Kem, key_bytes: &[u8]) -> Result<Self, KeyRejected> {
+        let mut key = LcPtr::new(unsafe { EVP_HPKE_KEY_new() })?;
+        if 1 != unsafe {
+            EVP_HPKE_KEY_init(
+                *key.as_mut(),
+                kem.evp_hpke_kem(),
+                key_bytes.as_ptr(),
+                key_bytes.len(),
+            )
+        } {
+            return Err(KeyRejected::invalid_encoding());
+        }
+        Ok(Self { kem, key })
+    }
+
+    /// The KEM for the private key.
+    #[must_use]
+    pub fn kem(&self) -> &'static Kem {
+        self.kem
+    }
+
+    /// Computes the public key for the private key, serialized as defined by
+    /// `SerializePublicKey` in RFC 9180.
+    ///
+    /// # Errors
+    /// `error::Unspecified` when operation fails due to internal error.
+    pub fn public_key(&self) -> Result<PublicKey, Unspecified> {
+        let mut public_key = [0u8; MAX_PUBLIC_KEY_LEN];
+        let mut len = 0;
+        if 1 != unsafe {
+            EVP_HPKE_KEY_public_key(
+                *self.key.as_const(),
+                public_key.as_mut_ptr(),
+                &mut len,
+                public_key.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(PublicKey {
+            kem: self.kem,
+            public_key,
+            len,
+        })
+    }
+}
+
+impl AsBigEndian<Curve25519SeedBin<'static>> for PrivateKey {
+    /// Exposes the private key, serialized as defined by `SerializePrivateKey` in RFC 9180.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if serialization failed.
+    fn as_be_bytes(&self) -> Result<Curve25519SeedBin<'static>, Unspecified> {
+        if KemId::DHKEM_X25519_HKDF_SHA256 != self.kem.id {
+            return Err(Unspecified);
+        }
+        let mut buffer = Zeroizing::new([0u8; MAX_PRIVATE_KEY_LEN]);
+        let mut len = 0;
+        if 1 != unsafe {
+            EVP_HPKE_KEY_private_key(
+                *self.key.as_const(),
+                buffer.as_mut_ptr(),
+                &mut len,
+                buffer.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Curve25519SeedBin::take_from_slice(&mut buffer[..len]))
+    }
+}
+
+unsafe impl Send for PrivateKey {}
+
+unsafe impl Sync for PrivateKey {}
+
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!("PrivateKey {{ kem: {:?} }}", self.kem))
+    }
+}
+
+/// A KEM public key.
+#[derive(Clone)]
+pub struct PublicKey {
+    kem: &'static Kem,
+    public_key: [u8; MAX_PUBLIC_KEY_LEN],
+    len: usize,
+}
+
+impl PublicKey {
+    /// The KEM for the public key.
+    #[must_use]
+    pub fn kem(&self) -> &'static Kem {
+        self.kem
+    }
+}
+
+impl AsRef<[u8]> for PublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.public_key[..self.len]
+    }
+}
+
+impl Debug for PublicKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "PublicKey {{ kem: {:?}, bytes: \"{}\" }}",
+            self.kem,
+            hex::encode(self.as_ref())
+        ))
+    }
+}
+
+/// The encapsulated key produced when setting up a [`SenderContext`], which is sent to the
+/// recipient.
+#[derive(Clone)]
+pub struct EncapsulatedKey {
+    enc: [u8; MAX_ENC_LEN],
+    len: usize,
+}
+
+impl AsRef<[u8]> for EncapsulatedKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.enc[..self.len]
+    }
+}
+
+impl Debug for EncapsulatedKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str(&format!(
+            "EncapsulatedKey(\"{}\")",
+            hex::encode(self.as_ref())
+        ))
+    }
+}
+
+/// The sender's HPKE encryption context.
+pub struct SenderContext {
+    suite: Suite,
+    ctx: LcPtr<EVP_HPKE_CTX>,
+    seq: u64,
+}
+
+impl SenderContext {
+    /// Sets up a base mode sender context for the recipient's public key.
+    ///
+    /// `recipient_public_key` is the recipient's KEM public key, and `info` is the application
+    /// supplied information bound to the context.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the recipient's public key is invalid, or when operation fails
+    /// due to internal error.
+    pub fn new(
+        suite: &Suite,
+        recipient_public_key: &[u8],
+        info: &[u8],
+    ) -> Result<(EncapsulatedKey, Self), Unspecified> {
+        Self::setup(suite, None, recipient_public_key, info, None)
+    }
+
+    /// Sets up an auth mode sender context for the recipient's public key, authenticated by
+    /// the sender's private key.
+    ///
+    /// `sender_private_key` must be a key for the ciphersuite's KEM.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the recipient's public key is invalid, if the sender's private key
+    /// does not match the ciphersuite's KEM, or when operation fails due to internal error.
+    #[cfg(not(feature = "fips"))]
+    pub fn new_auth(
+        suite: &Suite,
+        sender_private_key: &PrivateKey,
+        recipient_public_key: &[u8],
+        info: &[u8],
+    ) -> Result<(EncapsulatedKey, Self), Unspecified> {
+        Self::setup(
+            suite,
+            Some(sender_private_key),
+            recipient_public_key,
+            info,
+            None,
+        )
+    }
+
+    // Sets up the context in auth mode when `sender_private_key` is given, and in base mode
+    // otherwise. `seed` replaces the random ephemeral key generation, for known-answer tests.
+    fn setup(
+        suite: &Suite,
+        sender_private_key: Option<&PrivateKey>,
+        recipient_public_key: &[u8],
+        info: &[u8],
+        seed: Option<&[u8]>,
+    ) -> Result<(EncapsulatedKey, Self), Unspecified> {
+        let mut ctx = LcPtr::new(unsafe { EVP_HPKE_CTX_new() })?;
+        let mut enc = [0u8; MAX_ENC_LEN];
+        let mut len = 0;
+        let result = match (sender_private_key, seed) {
+            (None, None) => unsafe {
+                EVP_HPKE_CTX_setup_sender(
+                    *ctx.as_mut(),
+                    enc.as_mut_ptr(),
+                    &mut len,
+                    enc.len(),
+                    suite.kem.evp_hpke_kem(),
+                    suite.kdf.evp_hpke_kdf(),
+                    suite.aead.evp_hpke_aead(),
+                    recipient_public_key.as_ptr(),
+                    recipient_public_key.len(),
+                    info.as_ptr(),
+                    info.len(),
+                )
+            },
+            (None, Some(seed)) => unsafe {
+                EVP_HPKE_CTX_setup_sender_with_seed_for_testing(
+                    *ctx.as_mut(),
+                    enc.as_mut_ptr(),
+                    &mut len,
+                    enc.len(),
+                    suite.kem.evp_hpke_kem(),
+                    suite.kdf.evp_hpke_kdf(),
+                    suite.aead.evp_hpke_aead(),
+                    recipient_public_key.as_ptr(),
+                    recipient_public_key.len(),
+                    info.as_ptr(),
+                    info.len(),
+                    seed.as_ptr(),
+                    seed.len(),
+                )
+            },
+            #[cfg(not(feature = "fips"))]
+            (Some(sender_private_key), seed) => {
+                if sender_private_key.kem != suite.kem {
+                    return Err(Unspecified);
+                }
+                match seed {
+                    None => unsafe {
+                        EVP_HPKE_CTX_setup_auth_sender(
+                            *ctx.as_mut(),
+                            enc.as_mut_ptr(),
+                            &mut len,
+                            enc.len(),
+                            *sender_private_key.key.as_const(),
+                            suite.kdf.evp_hpke_kdf(),
+                            suite.aead.evp_hpke_aead(),
+                            recipient_public_key.as_ptr(),
+                            recipient_public_key.len(),
+                            info.as_ptr(),
+                            info.len(),
+                        )
+                    },
+                    Some(seed) => unsafe {
+                        EVP_HPKE_CTX_setup_auth_sender_with_seed_for_testing(
+                            *ctx.as_mut(),
+                            enc.as_mut_ptr(),
+                            &mut len,
+                            enc.len(),
+                            *sender_private_key.key.as_const(),
+                            suite.kdf.evp_hpke_kdf(),
+                            suite.aead.evp_hpke_aead(),
+                            recipient_public_key.as_ptr(),
+                            recipient_public_key.len(),
+                            info.as_ptr(),
+                            info.len(),
+                            seed.as_ptr(),
+                            seed.len(),
+                        )
+                    },
+                }
+            }
+            // Auth mode is not available in the FIPS module.
+            #[cfg(feature = "fips")]
+            (Some(_), _) => return Err(Unspecified),
+        };
+        if 1 != result {
+            return Err(Unspecified);
+        }
+        Ok((
+            EncapsulatedKey { enc, len },
+            Self {
+                suite: *suite,
+                ctx,
+                seq: 0,
+            },
+        ))
+    }
+
+    /// The ciphersuite of this context.
+    #[must_use]
+    pub fn suite(&self) -> &Suite {
+        &self.suite
+    }
+
+    /// The sequence number of the next message to be sealed.
+    #[must_use]
+    pub fn sequence_number(&self) -> u64 {
+        self.seq
+    }
+
+    /// Encrypts and authenticates `plaintext` with the associated data `aad`, returning the
+    /// ciphertext. The sequence number is incremented after each message.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the sequence number is exhausted, or when operation fails due to
+    /// internal error.
+    pub fn seal(&mut self, aad: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        let max_out_len = plaintext
+            .len()
+            .checked_add(unsafe { EVP_HPKE_CTX_max_overhead(*self.ctx.as_const()) })
+            .ok_or(Unspecified)?;
+        let mut out = vec![0u8; max_out_len];
+        let mut out_len = 0;
+        let next_seq = self.seq.checked_add(1).ok_or(Unspecified)?;
+        if 1 != unsafe {
+            EVP_HPKE_CTX_seal(
+                *self.ctx.as_mut(),
+                out.as_mut_ptr(),
+                &mut out_len,
+                out.len(),
+                plaintext.as_ptr(),
+                plaintext.len(),
+                aad.as_ptr(),
+                aad.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        self.seq = next_seq;
+        out.truncate(out_len);
+        Ok(out)
+    }
+
+    /// Derives `out.len()` bytes of secret from the context's exporter secret and
+    /// `exporter_context`, as defined by the secret export interface of RFC 9180.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `out` is longer than the KDF allows, or when operation fails
+    /// due to internal error.
+    pub fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), Unspecified> {
+        export(&self.ctx, exporter_context, out)
+    }
+}
+
+unsafe impl Send for SenderContext {}
+
+unsafe impl Sync for SenderContext {}
+
+impl Debug for SenderContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("SenderContext")
+            .field("suite", &self.suite)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The recipient's HPKE decryption context.
+pub struct RecipientContext {
+    suite: Suite,
+    ctx: LcPtr<EVP_HPKE_CTX>,
+    seq: u64,
+}
+
+impl RecipientContext {
+    /// Sets up a base mode recipient context from the encapsulated key sent by the sender.
+    ///
+    /// `recipient_private_key` must be a key for the ciphersuite's KEM, and `info` must match
+    /// the value used by the sender.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the encapsulated key is invalid, if the private key does not match
+    /// the ciphersuite's KEM, or when operation fails due to internal error.
+    pub fn new(
+        suite: &Suite,
+        recipient_private_key: &PrivateKey,
+        encapsulated_key: &[u8],
+        info: &[u8],
+    ) -> Result<Self, Unspecified> {
+        if recipient_private_key.kem != suite.kem {
+            return Err(Unspecified);
+        }
+        let mut ctx = LcPtr::new(unsafe { EVP_HPKE_CTX_new() })?;
+        if 1 != unsafe {
+            EVP_HPKE_CTX_setup_recipient(
+                *ctx.as_mut(),
+                *recipient_private_key.key.as_const(),
+                suite.kdf.evp_hpke_kdf(),
+                suite.aead.evp_hpke_aead(),
+                encapsulated_key.as_ptr(),
+                encapsulated_key.len(),
+                info.as_ptr(),
+                info.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            suite: *suite,
+            ctx,
+            seq: 0,
+        })
+    }
+
+    /// Sets up an auth mode recipient context from the encapsulated key sent by the sender,
+    /// authenticating the sender's public key.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the encapsulated key or the sender's public key is invalid, if the
+    /// private key does not match the ciphersuite's KEM, or when operation fails due to internal
+    /// error.
+    #[cfg(not(feature = "fips"))]
+    pub fn new_auth(
+        suite: &Suite,
+        recipient_private_key: &PrivateKey,
+        encapsulated_key: &[u8],
+        sender_public_key: &[u8],
+        info: &[u8],
+    ) -> Result<Self, Unspecified> {
+        if recipient_private_key.kem != suite.kem {
+            return Err(Unspecified);
+        }
+        let mut ctx = LcPtr::new(unsafe { EVP_HPKE_CTX_new() })?;
+        if 1 != unsafe {
+            EVP_HPKE_CTX_setup_auth_recipient(
+                *ctx.as_mut(),
+                *recipient_private_key.key.as_const(),
+                suite.kdf.evp_hpke_kdf(),
+                suite.aead.evp_hpke_aead(),
+                encapsulated_key.as_ptr(),
+                encapsulated_key.len(),
+                info.as_ptr(),
+                info.len(),
+                sender_public_key.as_ptr(),
+                sender_public_key.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        Ok(Self {
+            suite: *suite,
+            ctx,
+            seq: 0,
+        })
+    }
+
+    /// The ciphersuite of this context.
+    #[must_use]
+    pub fn suite(&self) -> &Suite {
+        &self.suite
+    }
+
+    /// The sequence number of the next message to be opened.
+    #[must_use]
+    pub fn sequence_number(&self) -> u64 {
+        self.seq
+    }
+
+    /// Authenticates and decrypts `ciphertext` with the associated data `aad`, returning the
+    /// plaintext. The sequence number is incremented after each successfully opened message.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the ciphertext fails to authenticate, if the sequence number is
+    /// exhausted, or when operation fails due to internal error.
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Unspecified> {
+        let mut out = vec![0u8; ciphertext.len()];
+        let mut out_len = 0;
+        let next_seq = self.seq.checked_add(1).ok_or(Unspecified)?;
+        if 1 != unsafe {
+            EVP_HPKE_CTX_open(
+                *self.ctx.as_mut(),
+                out.as_mut_ptr(),
+                &mut out_len,
+                out.len(),
+                ciphertext.as_ptr(),
+                ciphertext.len(),
+                aad.as_ptr(),
+                aad.len(),
+            )
+        } {
+            return Err(Unspecified);
+        }
+        self.seq = next_seq;
+        out.truncate(out_len);
+        Ok(out)
+    }
+
+    /// Derives `out.len()` bytes of secret from the context's exporter secret and
+    /// `exporter_context`, as defined by the secret export interface of RFC 9180.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if `out` is longer than the KDF allows, or when operation fails
+    /// due to internal error.
+    pub fn export(&self, exporter_context: &[u8], out: &mut [u8]) -> Result<(), Unspecified> {
+        export(&self.ctx, exporter_context, out)
+    }
+}
+
+unsafe impl Send for RecipientContext {}
+
+unsafe impl Sync for RecipientContext {}
+
+impl Debug for RecipientContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        f.debug_struct("RecipientContext")
+            .field("suite", &self.suite)
+            .finish_non_exhaustive()
+    }
+}
+
+fn export(
+    ctx: &LcPtr<EVP_HPKE_CTX>,
+    exporter_context: &[u8],
+    out: &mut [u8],
+) -> Result<(), Unspecified> {
+    if 1 != unsafe {
+        EVP_HPKE_CTX_export(
+            *ctx.as_const(),
+            out.as_mut_ptr(),
+            out.len(),
+            exporter_context.as_ptr(),
+            exporter_context.len(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// Encrypts a single message to the recipient's public key in base mode, returning the
+/// encapsulated key and the ciphertext.
+///
+/// This is equivalent to sealing one message with a new [`SenderContext`].
+///
+/// # Errors
+/// `error::Unspecified` if the recipient's public key is invalid, or when operation fails due to
+/// internal error.
+pub fn seal(
+    suite: &Suite,
+    recipient_public_key: &[u8],
+    info: &[u8],
+    aad: &[u8],
+    plaintext: &[u8],
+) -> Result<(EncapsulatedKey, Vec<u8>), Unspecified> {
+    let (encapsulated_key, mut ctx) = SenderContext::new(suite, recipient_public_key, info)?;
+    let ciphertext = ctx.seal(aad, plaintext)?;
+    Ok((encapsulated_key, ciphertext))
+}
+
+/// Decrypts a single message sealed with [`seal`], returning the plaintext.
+///
+/// This is equivalent to opening one message with a new [`RecipientContext`].
+///
+/// # Errors
+/// `error::Unspecified` if the encapsulated key is invalid, if the ciphertext fails to
+/// authenticate, or when operation fails due to internal error.
+pub fn open(
+    suite: &Suite,
+    recipient_private_key: &PrivateKey,
+    encapsulated_key: &[u8],
+    info: &[u8],
+    aad: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, Unspecified> {
+    let mut ctx = RecipientContext::new(suite, recipient_private_key, encapsulated_key, info)?;
+    ctx.open(aad, ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        open, seal, Aead, PrivateKey, RecipientContext, SenderContext, Suite, AES_128_GCM,
+        AES_256_GCM, CHACHA20_POLY1305, DHKEM_X25519_HKDF_SHA256, HKDF_SHA256,
+    };
+    use crate::encoding::{AsBigEndian, Curve25519SeedBin};
+    use crate::{error, test, test_file};
+
+    fn aead_from_name(name: &str) -> &'static Aead {
+        match name {
+            "AES_128_GCM" => &AES_128_GCM,
+            "AES_256_GCM" => &AES_256_GCM,
+            "CHACHA20_POLY1305" => &CHACHA20_POLY1305,
+            _ => unreachable!(),
+        }
+    }
+
+    fn check_contexts(
+        test_case: &mut test::TestCase,
+        sender: &mut SenderContext,
+        recipient: &mut RecipientContext,
+    ) -> Result<(), error::Unspecified> {
+        let plaintext = test_case.consume_bytes("Plaintext");
+        let mut sequence = 0;
+        while let Some(ciphertext) =
+            test_case.consume_optional_bytes(&format!("Ciphertext{sequence}"))
+        {
+            let aad = format!("Count-{sequence}");
+            assert_eq!(sender.sequence_number(), sequence);
+            assert_eq!(sender.seal(aad.as_bytes(), &plaintext)?, ciphertext);
+            assert_eq!(recipient.sequence_number(), sequence);
+            assert_eq!(recipient.open(aad.as_bytes(), &ciphertext)?, plaintext);
+            sequence += 1;
+        }
+        assert!(sequence > 0);
+
+        let mut index = 0;
+        while let Some(exporter_context) =
+            test_case.consume_optional_bytes(&format!("ExporterContext{index}"))
+        {
+            let exported_value = test_case.consume_bytes(&format!("ExportedValue{index}"));
+            let mut out = vec![0u8; exported_value.len()];
+            sender.export(&exporter_context, &mut out)?;
+            assert_eq!(out, exported_value);
+            recipient.export(&exporter_context, &mut out)?;
+            assert_eq!(out, exported_value);
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn consume_private_key(test_case: &mut test::TestCase, name: &str) -> PrivateKey {
+        let key_bytes = test_case.consume_bytes(&format!("Sk{name}m"));
+        let key = PrivateKey::from_private_key(&DHKEM_X25519_HKDF_SHA256, &key_bytes).unwrap();
+        let serialized: Curve25519SeedBin = key.as_be_bytes().unwrap();
+        assert_eq!(serialized.as_ref(), key_bytes.as_slice());
+        let public_key = test_case.consume_bytes(&format!("Pk{name}m"));
+        assert_eq!(key.public_key().unwrap().as_ref(), public_key.as_slice());
+        key
+    }
+
+    #[test]
+    fn hpke_base_tests() {
+        test::run(
+            test_file!("data/hpke_base_tests.txt"),
+            |section, test_case| {
+                assert_eq!(section, "");
+                let suite = Suite::new(
+                    &DHKEM_X25519_HKDF_SHA256,
+                    &HKDF_SHA256,
+                    aead_from_name(&test_case.consume_string("Aead")),
+                );
+                let info = test_case.consume_bytes("Info");
+                let sk_em = test_case.consume_bytes("SkEm");
+                let recipient_key = consume_private_key(test_case, "R");
+                let pk_rm = recipient_key.public_key()?;
+
+                let (enc, mut sender) =
+                    SenderContext::setup(&suite, None, pk_rm.as_ref(), &info, Some(&sk_em))?;
+                assert_eq!(enc.as_ref(), test_case.consume_bytes("Enc").as_slice());
+                let mut recipient =
+                    RecipientContext::new(&suite, &recipient_key, enc.as_ref(), &info)?;
+
+                check_contexts(test_case, &mut sender, &mut recipient)
+            },
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn hpke_auth_tests() {
+        test::run(
+            test_file!("data/hpke_auth_tests.txt"),
+            |section, test_case| {
+                assert_eq!(section, "");
+                let suite = Suite::new(
+                    &DHKEM_X25519_HKDF_SHA256,
+                    &HKDF_SHA256,
+                    aead_from_name(&test_case.consume_string("Aead")),
+                );
+                let info = test_case.consume_bytes("Info");
+                let sk_em = test_case.consume_bytes("SkEm");
+                let recipient_key = consume_private_key(test_case, "R");
+                let sender_key = consume_private_key(test_case, "S");
+                let pk_rm = recipient_key.public_key()?;
+                let pk_sm = sender_key.public_key()?;
+
+                let (enc, mut sender) = SenderContext::setup(
+                    &suite,
+                    Some(&sender_key),
+                    pk_rm.as_ref(),
+                    &info,
+                    Some(&sk_em),
+                )?;
+                assert_eq!(enc.as_ref(), test_case.consume_bytes("Enc").as_slice());
+                let mut recipient = RecipientContext::new_auth(
+                    &suite,
+                    &recipient_key,
+                    enc.as_ref(),
+                    pk_sm.as_ref(),
+                    &info,
+                )?;
+
+                check_contexts(test_case, &mut sender, &mut recipient)
+            },
+        );
+    }
+
+    #[test]
+    fn hpke_single_shot() {
+        for aead in [&AES_128_GCM, &AES_256_GCM, &CHACHA20_POLY1305] {
+            let suite = Suite::new(&DHKEM_X25519_HKDF_SHA256, &HKDF_SHA256, aead);
+            let private_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+            let public_key = private_key.public_key().unwrap();
+
+            let (enc, ciphertext) =
+                seal(&suite, public_key.as_ref(), b"info", b"aad", b"plaintext").unwrap();
+            assert_eq!(ciphertext.len(), b"plaintext".len() + 16);
+            let plaintext = open(
+                &suite,
+                &private_key,
+                enc.as_ref(),
+                b"info",
+                b"aad",
+                &ciphertext,
+            )
+            .unwrap();
+            assert_eq!(plaintext, b"plaintext");
+
+            // Mismatched info, aad or ciphertext fail to open.
+            assert!(open(
+                &suite,
+                &private_key,
+                enc.as_ref(),
+                b"other",
+                b"aad",
+                &ciphertext
+            )
+            .is_err());
+            assert!(open(
+                &suite,
+                &private_key,
+                enc.as_ref(),
+                b"info",
+                b"other",
+                &ciphertext
+            )
+            .is_err());
+            let mut tampered = ciphertext.clone();
+            tampered[0] ^= 1;
+            assert!(open(
+                &suite,
+                &private_key,
+                enc.as_ref(),
+                b"info",
+                b"aad",
+                &tampered
+            )
+            .is_err());
+            assert!(open(
+                &suite,
+                &private_key,
+                &enc.as_ref()[1..],
+                b"info",
+                b"aad",
+                &ciphertext
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn hpke_out_of_order() {
+        let suite = Suite::new(&DHKEM_X25519_HKDF_SHA256, &HKDF_SHA256, &AES_128_GCM);
+        let private_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+        let public_key = private_key.public_key().unwrap();
+
+        let (enc, mut sender) = SenderContext::new(&suite, public_key.as_ref(), b"").unwrap();
+        let first = sender.seal(b"", b"first").unwrap();
+        let second = sender.seal(b"", b"second").unwrap();
+        assert_eq!(sender.sequence_number(), 2);
+
+        let mut recipient = RecipientContext::new(&suite, &private_key, enc.as_ref(), b"").unwrap();
+        assert!(recipient.open(b"", &second).is_err());
+        assert_eq!(recipient.sequence_number(), 0);
+        assert_eq!(recipient.open(b"", &first).unwrap(), b"first");
+        assert_eq!(recipient.open(b"", &second).unwrap(), b"second");
+        assert_eq!(recipient.sequence_number(), 2);
+    }
+
+    #[test]
+    fn hpke_sequence_number_exhausted() {
+        let suite = Suite::new(&DHKEM_X25519_HKDF_SHA256, &HKDF_SHA256, &AES_128_GCM);
+        let private_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+        let public_key = private_key.public_key().unwrap();
+
+        let (enc, mut sender) = SenderContext::new(&suite, public_key.as_ref(), b"").unwrap();
+        let ciphertext = sender.seal(b"", b"plaintext").unwrap();
+        sender.seq = u64::MAX;
+        assert!(sender.seal(b"", b"plaintext").is_err());
+        assert_eq!(sender.sequence_number(), u64::MAX);
+
+        let mut recipient = RecipientContext::new(&suite, &private_key, enc.as_ref(), b"").unwrap();
+        recipient.seq = u64::MAX;
+        assert!(recipient.open(b"", &ciphertext).is_err());
+        assert_eq!(recipient.sequence_number(), u64::MAX);
+    }
+
+    #[test]
+    fn hpke_invalid_keys() {
+        assert!(PrivateKey::from_private_key(&DHKEM_X25519_HKDF_SHA256, &[0u8; 31]).is_err());
+        let suite = Suite::new(&DHKEM_X25519_HKDF_SHA256, &HKDF_SHA256, &AES_128_GCM);
+        assert!(SenderContext::new(&suite, &[0u8; 31], b"").is_err());
+        // The all-zero public key yields an all-zero shared secret, which is rejected.
+        assert!(SenderContext::new(&suite, &[0u8; 32], b"").is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn hpke_auth_mode() {
+        let suite = Suite::new(&DHKEM_X25519_HKDF_SHA256, &HKDF_SHA256, &CHACHA20_POLY1305);
+        let recipient_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+        let sender_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+        let other_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+
+        let (enc, mut sender) = SenderContext::new_auth(
+            &suite,
+            &sender_key,
+            recipient_key.public_key().unwrap().as_ref(),
+            b"info",
+        )
+        .unwrap();
+        let ciphertext = sender.seal(b"aad", b"plaintext").unwrap();
+
+        let mut recipient = RecipientContext::new_auth(
+            &suite,
+            &recipient_key,
+            enc.as_ref(),
+            sender_key.public_key().unwrap().as_ref(),
+            b"info",
+        )
+        .unwrap();
+        assert_eq!(recipient.open(b"aad", &ciphertext).unwrap(), b"plaintext");
+
+        // The wrong sender public key, or base mode, derive a different key.
+        let mut recipient = RecipientContext::new_auth(
+            &suite,
+            &recipient_key,
+            enc.as_ref(),
+            other_key.public_key().unwrap().as_ref(),
+            b"info",
+        )
+        .unwrap();
+        assert!(recipient.open(b"aad", &ciphertext).is_err());
+        let mut recipient =
+            RecipientContext::new(&suite, &recipient_key, enc.as_ref(), b"info").unwrap();
+        assert!(recipient.open(b"aad", &ciphertext).is_err());
+    }
+
+    #[test]
+    fn hpke_debug() {
+        let suite = Suite::new(&DHKEM_X25519_HKDF_SHA256, &HKDF_SHA256, &AES_128_GCM);
+        assert_eq!(
+            format!("{suite:?}"),
+            "Suite { kem: DHKEM_X25519_HKDF_SHA256, kdf: HKDF_SHA256, aead: AES_128_GCM }"
+        );
+        let private_key = PrivateKey::generate(&DHKEM_X25519_HKDF_SHA256).unwrap();
+        assert_eq!(
+            format!("{private_key:?}"),
+            "PrivateKey { kem: DHKEM_X25519_HKDF_SHA256 }"
+        );
+        assert_eq!(DHKEM_X25519_HKDF_SHA256.id(), 0x0020);
+        assert_eq!(HKDF_SHA256.id(), 0x0001);
+        assert_eq!(AES_128_GCM.id(), 0x0001);
+        assert_eq!(AES_256_GCM.id(), 0x0002);
+        assert_eq!(CHACHA20_POLY1305.id(), 0x0003);
+    }
+}
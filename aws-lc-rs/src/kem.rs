@@ -1,25 +1,25 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0 OR ISC
 
-//! Key-Encapsulation Mechanisms (KEMs), including support for Kyber Round 3 Submission.
+//! Key-Encapsulation Mechanisms (KEMs), including support for ML-KEM (FIPS 203).
+//!
+//! The ML-KEM algorithms are backed by AWS-LC's `NID_MLKEM512IPD`, `NID_MLKEM768IPD` and
+//! `NID_MLKEM1024IPD` identifiers, which were named after the FIPS 203 initial public draft (IPD).
+//! They are not available when the `fips` feature is enabled.
 //!
 //! # Example
 //!
-//! Note that this example uses the Kyber-512 Round 3 algorithm, but other algorithms can be used
+//! Note that this example uses the ML-KEM-512 algorithm, but other algorithms can be used
 //! in the exact same way by substituting
-//! `kem::<desired_algorithm_here>` for `kem::KYBER512_R3`.
-//!
-//! ```ignore
-//! use aws_lc_rs::{
-//!     error::Unspecified,
-//!     kem::{Ciphertext, DecapsulationKey, EncapsulationKey},
-//!     unstable::kem::{AlgorithmId, get_algorithm}
-//! };
+//! `kem::<desired_algorithm_here>` for `kem::ML_KEM_512`.
 //!
-//! let kyber512_r3 = get_algorithm(AlgorithmId::Kyber512_R3).ok_or(Unspecified)?;
+//! ```
+//! # #[cfg(not(feature = "fips"))]
+//! # fn main() -> Result<(), aws_lc_rs::error::Unspecified> {
+//! use aws_lc_rs::kem::{Ciphertext, DecapsulationKey, EncapsulationKey, ML_KEM_512};
 //!
 //! // Alice generates their (private) decapsulation key.
-//! let decapsulation_key = DecapsulationKey::generate(kyber512_r3)?;
+//! let decapsulation_key = DecapsulationKey::generate(&ML_KEM_512)?;
 //!
 //! // Alices computes the (public) encapsulation key.
 //! let encapsulation_key = decapsulation_key.encapsulation_key()?;
@@ -31,7 +31,7 @@
 //! let encapsulation_key_bytes = encapsulation_key_bytes.as_ref();
 //!
 //! // Bob constructs the (public) encapsulation key from the key bytes provided by Alice.
-//! let retrieved_encapsulation_key = EncapsulationKey::new(kyber512_r3, encapsulation_key_bytes)?;
+//! let retrieved_encapsulation_key = EncapsulationKey::new(&ML_KEM_512, encapsulation_key_bytes)?;
 //!
 //! // Bob executes the encapsulation algorithm to to produce their copy of the secret, and associated ciphertext.
 //! let (ciphertext, bob_secret) = retrieved_encapsulation_key.encapsulate()?;
@@ -45,8 +45,10 @@
 //!
 //! // Alice and Bob have now arrived to the same secret
 //! assert_eq!(alice_secret.as_ref(), bob_secret.as_ref());
-//!
-//! # Ok::<(), aws_lc_rs::error::Unspecified>(())
+//! # Ok(())
+//! # }
+//! # #[cfg(feature = "fips")]
+//! # fn main() {}
 //! ```
 use crate::{
     buffer::Buffer,
@@ -57,12 +59,73 @@ use crate::{
 use alloc::borrow::Cow;
 use aws_lc::{
     EVP_PKEY_CTX_kem_set_params, EVP_PKEY_CTX_new_id, EVP_PKEY_decapsulate, EVP_PKEY_encapsulate,
-    EVP_PKEY_get_raw_private_key, EVP_PKEY_get_raw_public_key, EVP_PKEY_kem_new_raw_public_key,
-    EVP_PKEY_keygen, EVP_PKEY_keygen_init, EVP_PKEY, EVP_PKEY_KEM,
+    EVP_PKEY_get_raw_private_key, EVP_PKEY_get_raw_public_key, EVP_PKEY_kem_new_raw_key,
+    EVP_PKEY_kem_new_raw_public_key, EVP_PKEY_keygen, EVP_PKEY_keygen_init, EVP_PKEY, EVP_PKEY_KEM,
 };
+#[cfg(not(feature = "fips"))]
+use aws_lc::{NID_MLKEM1024IPD, NID_MLKEM512IPD, NID_MLKEM768IPD};
 use core::{cmp::Ordering, ptr::null_mut};
 use zeroize::Zeroize;
 
+// Key lengths defined as stated in NIST FIPS 203, Section 8, Table 3.
+
+#[cfg(not(feature = "fips"))]
+const ML_KEM_512_SECRET_KEY_LENGTH: usize = 1632;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_512_CIPHERTEXT_LENGTH: usize = 768;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_512_PUBLIC_KEY_LENGTH: usize = 800;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_512_SHARED_SECRET_LENGTH: usize = 32;
+
+#[cfg(not(feature = "fips"))]
+const ML_KEM_768_SECRET_KEY_LENGTH: usize = 2400;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_768_CIPHERTEXT_LENGTH: usize = 1088;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_768_PUBLIC_KEY_LENGTH: usize = 1184;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_768_SHARED_SECRET_LENGTH: usize = 32;
+
+#[cfg(not(feature = "fips"))]
+const ML_KEM_1024_SECRET_KEY_LENGTH: usize = 3168;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_1024_CIPHERTEXT_LENGTH: usize = 1568;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_1024_PUBLIC_KEY_LENGTH: usize = 1568;
+#[cfg(not(feature = "fips"))]
+const ML_KEM_1024_SHARED_SECRET_LENGTH: usize = 32;
+
+/// ML-KEM-512 algorithm, backed by AWS-LC's FIPS 203 draft identifier `NID_MLKEM512IPD`.
+#[cfg(not(feature = "fips"))]
+pub static ML_KEM_512: Algorithm<AlgorithmId> = Algorithm {
+    id: AlgorithmId::MlKem512,
+    decapsulate_key_size: ML_KEM_512_SECRET_KEY_LENGTH,
+    encapsulate_key_size: ML_KEM_512_PUBLIC_KEY_LENGTH,
+    ciphertext_size: ML_KEM_512_CIPHERTEXT_LENGTH,
+    shared_secret_size: ML_KEM_512_SHARED_SECRET_LENGTH,
+};
+
+/// ML-KEM-768 algorithm, backed by AWS-LC's FIPS 203 draft identifier `NID_MLKEM768IPD`.
+#[cfg(not(feature = "fips"))]
+pub static ML_KEM_768: Algorithm<AlgorithmId> = Algorithm {
+    id: AlgorithmId::MlKem768,
+    decapsulate_key_size: ML_KEM_768_SECRET_KEY_LENGTH,
+    encapsulate_key_size: ML_KEM_768_PUBLIC_KEY_LENGTH,
+    ciphertext_size: ML_KEM_768_CIPHERTEXT_LENGTH,
+    shared_secret_size: ML_KEM_768_SHARED_SECRET_LENGTH,
+};
+
+/// ML-KEM-1024 algorithm, backed by AWS-LC's FIPS 203 draft identifier `NID_MLKEM1024IPD`.
+#[cfg(not(feature = "fips"))]
+pub static ML_KEM_1024: Algorithm<AlgorithmId> = Algorithm {
+    id: AlgorithmId::MlKem1024,
+    decapsulate_key_size: ML_KEM_1024_SECRET_KEY_LENGTH,
+    encapsulate_key_size: ML_KEM_1024_PUBLIC_KEY_LENGTH,
+    ciphertext_size: ML_KEM_1024_CIPHERTEXT_LENGTH,
+    shared_secret_size: ML_KEM_1024_SHARED_SECRET_LENGTH,
+};
+
 /// An identifier for a KEM algorithm.
 pub trait AlgorithmIdentifier:
     Copy + Clone + Debug + PartialEq + crate::sealed::Sealed + 'static
@@ -113,6 +176,13 @@ where
     pub(crate) fn shared_secret_size(&self) -> usize {
         self.shared_secret_size
     }
+
+    // Both Kyber and ML-KEM serialize the decapsulation key as `dk_PKE || ek || H(ek) || z`,
+    // where `H(ek)` and `z` are each 32 bytes (FIPS 203, Algorithm 16).
+    #[inline]
+    fn encapsulate_key_offset(&self) -> usize {
+        self.decapsulate_key_size - self.encapsulate_key_size - 64
+    }
 }
 
 impl<Id> Debug for Algorithm<Id>
@@ -139,11 +209,31 @@ where
 /// access to algorithms not subject to semantic versioning gurantees.
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub enum AlgorithmId {}
+pub enum AlgorithmId {
+    /// ML-KEM-512, backed by AWS-LC's FIPS 203 draft identifier `NID_MLKEM512IPD`.
+    #[cfg(not(feature = "fips"))]
+    MlKem512,
+
+    /// ML-KEM-768, backed by AWS-LC's FIPS 203 draft identifier `NID_MLKEM768IPD`.
+    #[cfg(not(feature = "fips"))]
+    MlKem768,
+
+    /// ML-KEM-1024, backed by AWS-LC's FIPS 203 draft identifier `NID_MLKEM1024IPD`.
+    #[cfg(not(feature = "fips"))]
+    MlKem1024,
+}
 
 impl AlgorithmIdentifier for AlgorithmId {
+    #[inline]
     fn nid(self) -> i32 {
-        unreachable!()
+        match self {
+            #[cfg(not(feature = "fips"))]
+            AlgorithmId::MlKem512 => NID_MLKEM512IPD,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmId::MlKem768 => NID_MLKEM768IPD,
+            #[cfg(not(feature = "fips"))]
+            AlgorithmId::MlKem1024 => NID_MLKEM1024IPD,
+        }
     }
 }
 
@@ -182,6 +272,72 @@ where
         self.algorithm
     }
 
+    /// Returns the `DecapsulationKey` bytes.
+    ///
+    /// The bytes are the decapsulation key encoding defined by the algorithm's specification,
+    /// e.g. `dk` as defined in NIST FIPS 203 for ML-KEM.
+    ///
+    /// # Errors
+    /// * `Unspecified`: Any failure to retrieve the `DecapsulationKey` bytes.
+    pub fn key_bytes(&self) -> Result<DecapsulationKeyBytes<'static>, Unspecified> {
+        let mut decapsulate_key_size = self.algorithm.decapsulate_key_size();
+        let mut decapsulate_bytes = vec![0u8; decapsulate_key_size];
+        if 1 != unsafe {
+            EVP_PKEY_get_raw_private_key(
+                *self.evp_pkey.as_const(),
+                decapsulate_bytes.as_mut_ptr(),
+                &mut decapsulate_key_size,
+            )
+        } {
+            return Err(Unspecified);
+        }
+
+        // This is currently pedantic but done for safety in-case the decapsulation key
+        // size changes in the future. `EVP_PKEY_get_raw_private_key` writes the total length
+        // to `decapsulate_key_size` in the event that the buffer we provide is larger then
+        // required.
+        debug_assert_eq!(decapsulate_key_size, decapsulate_bytes.len());
+        decapsulate_bytes.truncate(decapsulate_key_size);
+
+        Ok(DecapsulationKeyBytes::new(decapsulate_bytes))
+    }
+
+    /// Creates a new KEM decapsulation key from raw bytes. This method MUST NOT be used to generate
+    /// a new decapsulation key, rather it MUST be used to construct `DecapsulationKey` previously
+    /// serialized to raw bytes.
+    ///
+    /// `alg` is the [`Algorithm`] to be associated with the generated `DecapsulationKey`.
+    ///
+    /// `bytes` is a slice of raw bytes representing a `DecapsulationKey`, as returned by
+    /// [`DecapsulationKey::key_bytes`] or by another implementation of the algorithm.
+    ///
+    /// # Errors
+    /// `error::KeyRejected` when operation fails during key creation.
+    pub fn new(alg: &'static Algorithm<Id>, bytes: &[u8]) -> Result<Self, KeyRejected> {
+        match bytes.len().cmp(&alg.decapsulate_key_size()) {
+            Ordering::Less => Err(KeyRejected::too_small()),
+            Ordering::Greater => Err(KeyRejected::too_large()),
+            Ordering::Equal => Ok(()),
+        }?;
+        // The encapsulation key is embedded in the decapsulation key, providing it as well allows
+        // `DecapsulationKey::encapsulation_key` to be used on the resulting key.
+        let offset = alg.encapsulate_key_offset();
+        let public_bytes = &bytes[offset..offset + alg.encapsulate_key_size()];
+        let evp_pkey = LcPtr::new(unsafe {
+            EVP_PKEY_kem_new_raw_key(
+                alg.id.nid(),
+                public_bytes.as_ptr(),
+                public_bytes.len(),
+                bytes.as_ptr(),
+                bytes.len(),
+            )
+        })?;
+        Ok(DecapsulationKey {
+            algorithm: alg,
+            evp_pkey,
+        })
+    }
+
     /// Computes the KEM encapsulation key from the KEM decapsulation key.
     ///
     /// # Errors
@@ -253,7 +409,7 @@ where
 
 use paste::paste;
 
-generated_encodings!(EncapsulationKeyBytes);
+generated_encodings!(EncapsulationKeyBytes, DecapsulationKeyBytes);
 
 /// A serializable encapsulation key usable with KEM algorithms. Constructed
 /// from either a `DecapsulationKey` or raw bytes.
@@ -421,7 +577,7 @@ impl<'a> From<&'a [u8]> for Ciphertext<'a> {
 pub struct SharedSecret(Box<[u8]>);
 
 impl SharedSecret {
-    fn new(value: Box<[u8]>) -> Self {
+    pub(crate) fn new(value: Box<[u8]>) -> Self {
         Self(value)
     }
 }
@@ -459,6 +615,25 @@ fn kem_key_generate(nid: i32) -> Result<LcPtr<EVP_PKEY>, Unspecified> {
 mod tests {
     use super::{Ciphertext, SharedSecret};
 
+    #[cfg(not(feature = "fips"))]
+    use super::{DecapsulationKey, EncapsulationKey, ML_KEM_1024, ML_KEM_512, ML_KEM_768};
+
+    #[cfg(not(feature = "fips"))]
+    use super::{Algorithm, AlgorithmIdentifier};
+    #[cfg(not(feature = "fips"))]
+    use crate::error::KeyRejected;
+    #[cfg(not(feature = "fips"))]
+    use crate::ptr::LcPtr;
+    #[cfg(not(feature = "fips"))]
+    use crate::{test, test_file};
+    #[cfg(not(feature = "fips"))]
+    use aws_lc::{
+        EVP_PKEY_CTX_kem_set_params, EVP_PKEY_CTX_new_id, EVP_PKEY_encapsulate_deterministic,
+        EVP_PKEY_keygen_deterministic, EVP_PKEY_keygen_init, EVP_PKEY, EVP_PKEY_KEM,
+    };
+    #[cfg(not(feature = "fips"))]
+    use core::ptr::null_mut;
+
     #[test]
     fn ciphertext() {
         let ciphertext_bytes = vec![42u8; 4];
@@ -477,4 +652,237 @@ mod tests {
         let shared_secret = SharedSecret::new(secret_bytes.into_boxed_slice());
         assert_eq!(shared_secret.as_ref(), &[42, 42, 42, 42]);
     }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_kem_serialize() {
+        for algorithm in [&ML_KEM_512, &ML_KEM_768, &ML_KEM_1024] {
+            let priv_key = DecapsulationKey::generate(algorithm).unwrap();
+            assert_eq!(priv_key.algorithm(), algorithm);
+
+            let pub_key = priv_key.encapsulation_key().unwrap();
+            let pubkey_raw_bytes = pub_key.key_bytes().unwrap();
+            let pub_key_from_bytes =
+                EncapsulationKey::new(algorithm, pubkey_raw_bytes.as_ref()).unwrap();
+
+            assert_eq!(
+                pub_key.key_bytes().unwrap().as_ref(),
+                pub_key_from_bytes.key_bytes().unwrap().as_ref()
+            );
+            assert_eq!(pub_key.algorithm(), pub_key_from_bytes.algorithm());
+
+            let privkey_raw_bytes = priv_key.key_bytes().unwrap();
+            assert_eq!(
+                privkey_raw_bytes.as_ref().len(),
+                algorithm.decapsulate_key_size()
+            );
+            let priv_key_from_bytes =
+                DecapsulationKey::new(algorithm, privkey_raw_bytes.as_ref()).unwrap();
+
+            assert_eq!(
+                privkey_raw_bytes.as_ref(),
+                priv_key_from_bytes.key_bytes().unwrap().as_ref()
+            );
+            assert_eq!(
+                pub_key.key_bytes().unwrap().as_ref(),
+                priv_key_from_bytes
+                    .encapsulation_key()
+                    .unwrap()
+                    .key_bytes()
+                    .unwrap()
+                    .as_ref()
+            );
+            assert_eq!(priv_key.algorithm(), priv_key_from_bytes.algorithm());
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_kem_wrong_sizes() {
+        for algorithm in [&ML_KEM_512, &ML_KEM_768, &ML_KEM_1024] {
+            let too_long_bytes = vec![0u8; algorithm.encapsulate_key_size() + 1];
+            let long_pub_key_from_bytes = EncapsulationKey::new(algorithm, &too_long_bytes);
+            assert_eq!(
+                long_pub_key_from_bytes.err(),
+                Some(KeyRejected::too_large())
+            );
+
+            let too_short_bytes = vec![0u8; algorithm.encapsulate_key_size() - 1];
+            let short_pub_key_from_bytes = EncapsulationKey::new(algorithm, &too_short_bytes);
+            assert_eq!(
+                short_pub_key_from_bytes.err(),
+                Some(KeyRejected::too_small())
+            );
+
+            let too_long_bytes = vec![0u8; algorithm.decapsulate_key_size() + 1];
+            let long_priv_key_from_bytes = DecapsulationKey::new(algorithm, &too_long_bytes);
+            assert_eq!(
+                long_priv_key_from_bytes.err(),
+                Some(KeyRejected::too_large())
+            );
+
+            let too_short_bytes = vec![0u8; algorithm.decapsulate_key_size() - 1];
+            let short_priv_key_from_bytes = DecapsulationKey::new(algorithm, &too_short_bytes);
+            assert_eq!(
+                short_priv_key_from_bytes.err(),
+                Some(KeyRejected::too_small())
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_kem_e2e() {
+        for algorithm in [&ML_KEM_512, &ML_KEM_768, &ML_KEM_1024] {
+            let priv_key = DecapsulationKey::generate(algorithm).unwrap();
+            assert_eq!(priv_key.algorithm(), algorithm);
+
+            let pub_key = priv_key.encapsulation_key().unwrap();
+
+            let (alice_ciphertext, alice_secret) =
+                pub_key.encapsulate().expect("encapsulate successful");
+
+            let bob_secret = priv_key
+                .decapsulate(alice_ciphertext)
+                .expect("decapsulate successful");
+
+            assert_eq!(alice_secret.as_ref(), bob_secret.as_ref());
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_serialized_kem_e2e() {
+        for algorithm in [&ML_KEM_512, &ML_KEM_768, &ML_KEM_1024] {
+            let priv_key = DecapsulationKey::generate(algorithm).unwrap();
+            assert_eq!(priv_key.algorithm(), algorithm);
+
+            let pub_key = priv_key.encapsulation_key().unwrap();
+
+            // Generate public key bytes to send to bob
+            let pub_key_bytes = pub_key.key_bytes().unwrap();
+
+            // Test that priv_key's EVP_PKEY isn't entirely freed since we remove this pub_key's reference.
+            drop(pub_key);
+
+            let retrieved_pub_key =
+                EncapsulationKey::new(algorithm, pub_key_bytes.as_ref()).unwrap();
+            let (ciphertext, bob_secret) = retrieved_pub_key
+                .encapsulate()
+                .expect("encapsulate successful");
+
+            // Restore the decapsulation key from its serialized form.
+            let priv_key_bytes = priv_key.key_bytes().unwrap();
+            drop(priv_key);
+            let retrieved_priv_key =
+                DecapsulationKey::new(algorithm, priv_key_bytes.as_ref()).unwrap();
+
+            let alice_secret = retrieved_priv_key
+                .decapsulate(ciphertext)
+                .expect("decapsulate successful");
+
+            assert_eq!(alice_secret.as_ref(), bob_secret.as_ref());
+        }
+    }
+
+    // ML-KEM.KeyGen_internal seeded with `d || z`.
+    #[cfg(not(feature = "fips"))]
+    fn deterministic_key_pair(algorithm: &'static Algorithm, seed: &[u8]) -> DecapsulationKey {
+        let mut ctx = LcPtr::new(unsafe { EVP_PKEY_CTX_new_id(EVP_PKEY_KEM, null_mut()) }).unwrap();
+        assert_eq!(1, unsafe {
+            EVP_PKEY_CTX_kem_set_params(*ctx.as_mut(), algorithm.id().nid())
+        });
+        assert_eq!(1, unsafe { EVP_PKEY_keygen_init(*ctx.as_mut()) });
+
+        let mut key_raw: *mut EVP_PKEY = null_mut();
+        let mut seed_len = seed.len();
+        assert_eq!(1, unsafe {
+            EVP_PKEY_keygen_deterministic(*ctx.as_mut(), &mut key_raw, seed.as_ptr(), &mut seed_len)
+        });
+        DecapsulationKey {
+            algorithm,
+            evp_pkey: LcPtr::new(key_raw).unwrap(),
+        }
+    }
+
+    // ML-KEM.Encaps_internal seeded with `m`.
+    #[cfg(not(feature = "fips"))]
+    fn deterministic_encapsulate(key: &EncapsulationKey, m: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let mut ciphertext_len = key.algorithm().ciphertext_size();
+        let mut shared_secret_len = key.algorithm().shared_secret_size();
+        let mut ciphertext = vec![0u8; ciphertext_len];
+        let mut shared_secret = vec![0u8; shared_secret_len];
+        let mut m_len = m.len();
+
+        let mut ctx = key.evp_pkey.create_EVP_PKEY_CTX().unwrap();
+        assert_eq!(1, unsafe {
+            EVP_PKEY_encapsulate_deterministic(
+                *ctx.as_mut(),
+                ciphertext.as_mut_ptr(),
+                &mut ciphertext_len,
+                shared_secret.as_mut_ptr(),
+                &mut shared_secret_len,
+                m.as_ptr(),
+                &mut m_len,
+            )
+        });
+        ciphertext.truncate(ciphertext_len);
+        shared_secret.truncate(shared_secret_len);
+        (ciphertext, shared_secret)
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_kem_deterministic_known_answer() {
+        test::run(test_file!("data/mlkem_tests.txt"), |section, test_case| {
+            let algorithm: &'static Algorithm = match section {
+                "ML_KEM_512" => &ML_KEM_512,
+                "ML_KEM_768" => &ML_KEM_768,
+                "ML_KEM_1024" => &ML_KEM_1024,
+                _ => unreachable!(),
+            };
+            let mut seed = test_case.consume_bytes("D");
+            seed.extend_from_slice(&test_case.consume_bytes("Z"));
+            let ek = test_case.consume_bytes("EncapsulationKey");
+            let dk = test_case.consume_bytes("DecapsulationKey");
+            let m = test_case.consume_bytes("M");
+            let expected_ciphertext = test_case.consume_bytes("Ciphertext");
+            let expected_shared_secret = test_case.consume_bytes("SharedSecret");
+
+            // keyGen
+            let private_key = deterministic_key_pair(algorithm, &seed);
+            assert_eq!(private_key.key_bytes()?.as_ref(), dk.as_slice());
+            let public_key = private_key.encapsulation_key()?;
+            assert_eq!(public_key.key_bytes()?.as_ref(), ek.as_slice());
+
+            // encapDecap, starting from the serialized keys.
+            let public_key = EncapsulationKey::new(algorithm, &ek)?;
+            let (ciphertext, shared_secret) = deterministic_encapsulate(&public_key, &m);
+            assert_eq!(ciphertext, expected_ciphertext);
+            assert_eq!(shared_secret, expected_shared_secret);
+
+            let private_key = DecapsulationKey::new(algorithm, &dk)?;
+            let shared_secret = private_key.decapsulate(Ciphertext::from(ciphertext.as_ref()))?;
+            assert_eq!(shared_secret.as_ref(), expected_shared_secret.as_slice());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[cfg(not(feature = "fips"))]
+    fn test_debug_fmt() {
+        let private = DecapsulationKey::generate(&ML_KEM_512).expect("successful generation");
+        assert_eq!(
+            format!("{private:?}"),
+            "DecapsulationKey { algorithm: MlKem512, .. }"
+        );
+        assert_eq!(
+            format!(
+                "{:?}",
+                private.encapsulation_key().expect("public key retrievable")
+            ),
+            "EncapsulationKey { algorithm: MlKem512, .. }"
+        );
+    }
 }
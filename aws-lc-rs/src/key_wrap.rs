@@ -48,6 +48,9 @@ pub enum BlockCipherId {
     /// AES Block Cipher with 128-bit key.
     Aes128,
 
+    /// AES Block Cipher with 192-bit key.
+    Aes192,
+
     /// AES Block Cipher with 256-bit key.
     Aes256,
 }
@@ -97,6 +100,12 @@ pub const AES_128: AesBlockCipher = AesBlockCipher {
     key_len: 16,
 };
 
+/// AES Block Cipher with 192-bit key.
+pub const AES_192: AesBlockCipher = AesBlockCipher {
+    id: BlockCipherId::Aes192,
+    key_len: 24,
+};
+
 /// AES Block Cipher with 256-bit key.
 pub const AES_256: AesBlockCipher = AesBlockCipher {
     id: BlockCipherId::Aes256,
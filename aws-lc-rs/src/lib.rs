@@ -145,17 +145,20 @@ extern crate aws_lc_sys as aws_lc;
 extern crate core;
 pub mod aead;
 pub mod agreement;
+pub mod cmac;
 pub mod constant_time;
 pub mod digest;
 pub mod error;
 pub mod hkdf;
 pub mod hmac;
+pub mod hpke;
 #[cfg(feature = "ring-io")]
 pub mod io;
 pub mod key_wrap;
 pub mod pbkdf2;
 pub mod pkcs8;
 pub mod rand;
+pub mod scrypt;
 pub mod signature;
 pub mod test;
 
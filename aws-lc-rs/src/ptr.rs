@@ -4,10 +4,11 @@
 use core::ops::Deref;
 
 use aws_lc::{
-    BN_free, ECDSA_SIG_free, EC_GROUP_free, EC_KEY_free, EC_POINT_free, EVP_AEAD_CTX_free,
-    EVP_CIPHER_CTX_free, EVP_PKEY_CTX_free, EVP_PKEY_free, OPENSSL_free, RSA_free, BIGNUM,
-    ECDSA_SIG, EC_GROUP, EC_KEY, EC_POINT, EVP_AEAD_CTX, EVP_CIPHER_CTX, EVP_PKEY, EVP_PKEY_CTX,
-    RSA,
+    BN_free, CMAC_CTX_free, DH_free, DH_up_ref, ECDSA_SIG_free, EC_GROUP_free, EC_KEY_free,
+    EC_POINT_free, EVP_AEAD_CTX_free, EVP_CIPHER_CTX_free, EVP_HPKE_CTX_free, EVP_HPKE_KEY_free,
+    EVP_PKEY_CTX_free, EVP_PKEY_free, OPENSSL_free, RSA_free, SPAKE2_CTX_free, BIGNUM, CMAC_CTX,
+    DH, ECDSA_SIG, EC_GROUP, EC_KEY, EC_POINT, EVP_AEAD_CTX, EVP_CIPHER_CTX, EVP_HPKE_CTX,
+    EVP_HPKE_KEY, EVP_PKEY, EVP_PKEY_CTX, RSA, SPAKE2_CTX,
 };
 
 use mirai_annotations::verify_unreachable;
@@ -70,6 +71,17 @@ impl<P: Pointer> ManagedPointer<P> {
     }
 }
 
+impl Clone for LcPtr<DH> {
+    fn clone(&self) -> Self {
+        assert_eq!(
+            1,
+            unsafe { DH_up_ref(*self.as_mut_unsafe()) },
+            "infallible AWS-LC function"
+        );
+        Self::new(unsafe { *self.as_mut_unsafe() }).expect("non-null AWS-LC DH pointer")
+    }
+}
+
 impl<P: Pointer> DetachablePointer<P> {
     #[inline]
     pub fn as_const(&self) -> ConstPointer<P::T> {
@@ -242,8 +254,13 @@ create_pointer!(BIGNUM, BN_free);
 create_pointer!(EVP_PKEY, EVP_PKEY_free);
 create_pointer!(EVP_PKEY_CTX, EVP_PKEY_CTX_free);
 create_pointer!(RSA, RSA_free);
+create_pointer!(DH, DH_free);
 create_pointer!(EVP_AEAD_CTX, EVP_AEAD_CTX_free);
 create_pointer!(EVP_CIPHER_CTX, EVP_CIPHER_CTX_free);
+create_pointer!(EVP_HPKE_CTX, EVP_HPKE_CTX_free);
+create_pointer!(EVP_HPKE_KEY, EVP_HPKE_KEY_free);
+create_pointer!(CMAC_CTX, CMAC_CTX_free);
+create_pointer!(SPAKE2_CTX, SPAKE2_CTX_free);
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,336 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+//! scrypt derivation and verification.
+//!
+//! Use `derive` to derive scrypt outputs. Use `verify` to verify secret
+//! against previously-derived outputs.
+//!
+//! scrypt is a memory-hard password-based key derivation function specified in
+//! [RFC 7914]. Its cost is controlled by the CPU/memory cost parameter `N`, the
+//! block size `r` and the parallelization parameter `p`. Deriving a key requires
+//! approximately `128 * r * (N + p + 1)` bytes of memory, which must not exceed
+//! the memory limit of the `Params`.
+//!
+//! [RFC 7914]: https://tools.ietf.org/html/rfc7914
+//!
+//! # FIPS
+//! scrypt is not a FIPS approved algorithm, and the APIs offered in this module
+//! must not be used.
+//!
+//! # Examples
+//!
+//! ## Password Database Example
+//!
+//! ```
+//! use aws_lc_rs::scrypt;
+//! use std::collections::HashMap;
+//!
+//! const CREDENTIAL_LEN: usize = 32;
+//! pub type Credential = [u8; CREDENTIAL_LEN];
+//!
+//! enum Error {
+//!     WrongUsernameOrPassword
+//! }
+//!
+//! struct PasswordDatabase {
+//!     scrypt_params: scrypt::Params,
+//!     db_salt_component: [u8; 16],
+//!
+//!     // Normally this would be a persistent database.
+//!     storage: HashMap<String, Credential>,
+//! }
+//!
+//! impl PasswordDatabase {
+//!     pub fn store_password(&mut self, username: &str, password: &str) {
+//!         let salt = self.salt(username);
+//!         let mut to_store: Credential = [0u8; CREDENTIAL_LEN];
+//!         scrypt::derive(&self.scrypt_params, &salt, password.as_bytes(), &mut to_store)
+//!             .unwrap();
+//!         self.storage.insert(String::from(username), to_store);
+//!     }
+//!
+//!     pub fn verify_password(&self, username: &str, attempted_password: &str)
+//!                            -> Result<(), Error> {
+//!         match self.storage.get(username) {
+//!            Some(actual_password) => {
+//!                let salt = self.salt(username);
+//!                scrypt::verify(&self.scrypt_params, &salt,
+//!                               attempted_password.as_bytes(),
+//!                               actual_password)
+//!                     .map_err(|_| Error::WrongUsernameOrPassword)
+//!            },
+//!
+//!            None => Err(Error::WrongUsernameOrPassword)
+//!         }
+//!     }
+//!
+//!     fn salt(&self, username: &str) -> Vec<u8> {
+//!         let mut salt = Vec::with_capacity(self.db_salt_component.len() +
+//!                                           username.as_bytes().len());
+//!         salt.extend(self.db_salt_component.as_ref());
+//!         salt.extend(username.as_bytes());
+//!         salt
+//!     }
+//! }
+//!
+//! fn main() {
+//!     // Normally these parameters would be loaded from a configuration file.
+//!     let mut db = PasswordDatabase {
+//!         scrypt_params: scrypt::Params::new(1 << 14, 8, 1).unwrap(),
+//!         db_salt_component: [
+//!             // This value was generated from a secure PRNG.
+//!             0xd6, 0x26, 0x98, 0xda, 0xf4, 0xdc, 0x50, 0x52,
+//!             0x24, 0xf2, 0x27, 0xd1, 0xfe, 0x39, 0x01, 0x8a
+//!         ],
+//!         storage: HashMap::new(),
+//!     };
+//!
+//!     db.store_password("alice", "@74d7]404j|W}6u");
+//!
+//!     // An attempt to log in with the wrong password fails.
+//!     assert!(db.verify_password("alice", "wrong password").is_err());
+//!
+//!     // An attempt to log in with the right password succeeds.
+//!     assert!(db.verify_password("alice", "@74d7]404j|W}6u").is_ok());
+//! }
+//! ```
+
+use crate::constant_time;
+use crate::error::Unspecified;
+use aws_lc::EVP_PBE_scrypt;
+use zeroize::Zeroize;
+
+/// The default memory limit for `Params`: 32 MiB.
+pub const DEFAULT_MAX_MEMORY: usize = 32 * 1024 * 1024;
+
+// The upper bound on `p * r` imposed by RFC 7914 Section 2.
+const MAX_PR: u64 = (1 << 30) - 1;
+
+// scrypt operates on 64-byte blocks; each `r` corresponds to two of them.
+const BLOCK_LEN: u64 = 64;
+
+/// scrypt cost parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Params {
+    n: u64,
+    r: u32,
+    p: u32,
+    max_memory: usize,
+}
+
+impl Params {
+    /// Constructs scrypt parameters with the default memory limit of
+    /// [`DEFAULT_MAX_MEMORY`] bytes.
+    ///
+    /// | Parameter | RFC 7914 Section 2 Term
+    /// |-----------|-------------------------------------
+    /// | `n`       | N (CPU/memory cost parameter)
+    /// | `r`       | r (block size parameter)
+    /// | `p`       | p (parallelization parameter)
+    ///
+    /// # Errors
+    /// `error::Unspecified` if:
+    /// * `n` is not a power of two greater than one, or is larger than 2**32,
+    /// * `n` is not less than 2**(16 * r),
+    /// * `r` or `p` is zero, or `p * r` is not less than 2**30, or
+    /// * the memory required exceeds [`DEFAULT_MAX_MEMORY`].
+    pub fn new(n: u64, r: u32, p: u32) -> Result<Self, Unspecified> {
+        Self::with_max_memory(n, r, p, DEFAULT_MAX_MEMORY)
+    }
+
+    /// Constructs scrypt parameters that may use up to `max_memory` bytes.
+    ///
+    /// # Errors
+    /// `error::Unspecified` if the parameters are invalid, as for [`Params::new`], or if
+    /// the memory required exceeds `max_memory`.
+    pub fn with_max_memory(n: u64, r: u32, p: u32, max_memory: usize) -> Result<Self, Unspecified> {
+        let (r64, p64) = (u64::from(r), u64::from(p));
+        if r == 0 || p == 0 || p64 > MAX_PR / r64 {
+            return Err(Unspecified);
+        }
+        if n < 2 || !n.is_power_of_two() || n > 1 << 32 {
+            return Err(Unspecified);
+        }
+        if 16 * r64 < 64 && n >= 1 << (16 * r64) {
+            return Err(Unspecified);
+        }
+        let params = Self {
+            n,
+            r,
+            p,
+            max_memory,
+        };
+        let max_blocks = max_memory as u64 / BLOCK_LEN;
+        match params.memory_blocks() {
+            Some(blocks) if blocks <= max_blocks => Ok(params),
+            _ => Err(Unspecified),
+        }
+    }
+
+    // The number of 64-byte blocks used: B (p * 2r), T (2r) and V (N * 2r).
+    fn memory_blocks(&self) -> Option<u64> {
+        let two_r = 2 * u64::from(self.r);
+        let b_blocks = u64::from(self.p).checked_mul(two_r)?;
+        let v_blocks = self.n.checked_mul(two_r)?;
+        b_blocks.checked_add(two_r)?.checked_add(v_blocks)
+    }
+
+    /// The approximate number of bytes of memory required to derive a key.
+    #[must_use]
+    pub fn memory_required(&self) -> u64 {
+        // Checked during construction.
+        self.memory_blocks()
+            .unwrap_or(u64::MAX)
+            .saturating_mul(BLOCK_LEN)
+    }
+
+    /// The CPU/memory cost parameter `N`.
+    #[must_use]
+    pub fn n(&self) -> u64 {
+        self.n
+    }
+
+    /// The block size parameter `r`.
+    #[must_use]
+    pub fn r(&self) -> u32 {
+        self.r
+    }
+
+    /// The parallelization parameter `p`.
+    #[must_use]
+    pub fn p(&self) -> u32 {
+        self.p
+    }
+
+    /// The memory limit in bytes.
+    #[must_use]
+    pub fn max_memory(&self) -> usize {
+        self.max_memory
+    }
+}
+
+/// Fills `out` with the key derived using scrypt with the given inputs.
+///
+/// Do not use `derive` as part of verifying a secret; use `verify` instead, to
+/// minimize the effectiveness of timing attacks.
+///
+/// | Parameter   | RFC 7914 Section 2 Term
+/// |-------------|-------------------------------------------
+/// | `params`    | N, r, p
+/// | `salt`      | S (salt)
+/// | `secret`    | P (passphrase)
+/// | `out`       | DK (derived key)
+/// | `out.len()` | dkLen (derived key length)
+///
+/// # Errors
+/// `error::Unspecified` if `out` is empty, or if the derivation fails.
+//
+// # FIPS
+// This function must not be used.
+pub fn derive(
+    params: &Params,
+    salt: &[u8],
+    secret: &[u8],
+    out: &mut [u8],
+) -> Result<(), Unspecified> {
+    if out.is_empty() {
+        return Err(Unspecified);
+    }
+    if 1 != unsafe {
+        EVP_PBE_scrypt(
+            secret.as_ptr().cast(),
+            secret.len(),
+            salt.as_ptr(),
+            salt.len(),
+            params.n,
+            u64::from(params.r),
+            u64::from(params.p),
+            params.max_memory,
+            out.as_mut_ptr(),
+            out.len(),
+        )
+    } {
+        return Err(Unspecified);
+    }
+    Ok(())
+}
+
+/// Verifies that a previously-derived (e.g., using `derive`) scrypt value
+/// matches the scrypt value derived from the other inputs.
+///
+/// The comparison is done in constant time to prevent timing attacks. The
+/// comparison will fail if `previously_derived` is empty (has a length of
+/// zero).
+///
+/// | Parameter                  | RFC 7914 Section 2 Term
+/// |----------------------------|--------------------------------------------
+/// | `params`                   | N, r, p
+/// | `salt`                     | S (salt)
+/// | `secret`                   | P (passphrase)
+/// | `previously_derived`       | DK (derived key)
+/// | `previously_derived.len()` | dkLen (derived key length)
+///
+/// # Errors
+/// `error::Unspecified` is the inputs were not verified.
+//
+// # FIPS
+// This function must not be used.
+pub fn verify(
+    params: &Params,
+    salt: &[u8],
+    secret: &[u8],
+    previously_derived: &[u8],
+) -> Result<(), Unspecified> {
+    if previously_derived.is_empty() {
+        return Err(Unspecified);
+    }
+
+    // Create a vector with the expected output length.
+    let mut derived_buf = vec![0u8; previously_derived.len()];
+
+    derive(params, salt, secret, &mut derived_buf)?;
+
+    let result = constant_time::verify_slices_are_equal(&derived_buf, previously_derived);
+    derived_buf.zeroize();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scrypt::{derive, Params, DEFAULT_MAX_MEMORY};
+
+    #[test]
+    fn scrypt_params() {
+        assert!(Params::new(1 << 14, 8, 1).is_ok());
+        assert_eq!(
+            128 * 8 * ((1 << 14) + 1 + 1),
+            Params::new(1 << 14, 8, 1).unwrap().memory_required()
+        );
+
+        // N must be a power of two greater than one.
+        assert!(Params::new(0, 8, 1).is_err());
+        assert!(Params::new(1, 8, 1).is_err());
+        assert!(Params::new(1000, 8, 1).is_err());
+        // N must be less than 2^(16 * r).
+        assert!(Params::new(1 << 16, 1, 1).is_err());
+        assert!(Params::new(1 << 15, 1, 1).is_ok());
+        // r and p must be positive, and p * r less than 2^30.
+        assert!(Params::new(16, 0, 1).is_err());
+        assert!(Params::new(16, 8, 0).is_err());
+        assert!(Params::with_max_memory(16, 1 << 15, 1 << 15, usize::MAX).is_err());
+        // The memory limit must be respected.
+        assert!(Params::new(1 << 20, 8, 1).is_err());
+        assert!(Params::with_max_memory(1 << 20, 8, 1, 2 * DEFAULT_MAX_MEMORY).is_err());
+        assert!(Params::with_max_memory(1 << 20, 8, 1, 1 << 31).is_ok());
+        assert!(Params::new(16, u32::MAX, u32::MAX).is_err());
+        assert!(Params::new(1 << 32, 8, 1).is_err());
+        assert!(Params::with_max_memory(1 << 33, 8, 1, usize::MAX).is_err());
+    }
+
+    #[test]
+    fn scrypt_empty_output() {
+        let params = Params::new(16, 1, 1).unwrap();
+        assert!(derive(&params, b"salt", b"password", &mut []).is_err());
+    }
+}
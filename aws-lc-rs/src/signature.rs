@@ -529,6 +529,16 @@ pub static RSA_PKCS1_SHA512: RsaSignatureEncoding = RsaSignatureEncoding::new(
     &RsaSigningAlgorithmId::RSA_PKCS1_SHA512,
 );
 
+/// Verification of fixed-length (PKCS#11 style) ECDSA signatures using the P-224 curve and SHA-224.
+/// # ⚠️ Warning
+/// P-224 provides a security strength of only 112 bits.
+/// This scheme should only be used for backwards compatibility purposes.
+pub static ECDSA_P224_SHA224_FIXED: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
+    id: &ec::AlgorithmID::ECDSA_P224,
+    digest: &digest::SHA224,
+    sig_format: EcdsaSignatureFormat::Fixed,
+};
+
 /// Verification of fixed-length (PKCS#11 style) ECDSA signatures using the P-256 curve and SHA-256.
 pub static ECDSA_P256_SHA256_FIXED: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
     id: &ec::AlgorithmID::ECDSA_P256,
@@ -606,6 +616,16 @@ pub static ECDSA_P256K1_SHA3_256_FIXED: EcdsaVerificationAlgorithm = EcdsaVerifi
     sig_format: EcdsaSignatureFormat::Fixed,
 };
 
+/// Verification of ASN.1 DER-encoded ECDSA signatures using the P-224 curve and SHA-224.
+/// # ⚠️ Warning
+/// P-224 provides a security strength of only 112 bits.
+/// This scheme should only be used for backwards compatibility purposes.
+pub static ECDSA_P224_SHA224_ASN1: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
+    id: &ec::AlgorithmID::ECDSA_P224,
+    digest: &digest::SHA224,
+    sig_format: EcdsaSignatureFormat::ASN1,
+};
+
 /// Verification of ASN.1 DER-encoded ECDSA signatures using the P-256 curve and SHA-256.
 pub static ECDSA_P256_SHA256_ASN1: EcdsaVerificationAlgorithm = EcdsaVerificationAlgorithm {
     id: &ec::AlgorithmID::ECDSA_P256,
@@ -697,6 +717,13 @@ pub static ECDSA_P256K1_SHA3_256_ASN1: EcdsaVerificationAlgorithm = EcdsaVerific
     sig_format: EcdsaSignatureFormat::ASN1,
 };
 
+/// Signing of fixed-length (PKCS#11 style) ECDSA signatures using the P-224 curve and SHA-224.
+/// # ⚠️ Warning
+/// P-224 provides a security strength of only 112 bits.
+/// This scheme should only be used for backwards compatibility purposes.
+pub static ECDSA_P224_SHA224_FIXED_SIGNING: EcdsaSigningAlgorithm =
+    EcdsaSigningAlgorithm(&ECDSA_P224_SHA224_FIXED);
+
 /// Signing of fixed-length (PKCS#11 style) ECDSA signatures using the P-256 curve and SHA-256.
 pub static ECDSA_P256_SHA256_FIXED_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P256_SHA256_FIXED);
@@ -746,6 +773,13 @@ pub static ECDSA_P256K1_SHA256_FIXED_SIGNING: EcdsaSigningAlgorithm =
 pub static ECDSA_P256K1_SHA3_256_FIXED_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P256K1_SHA3_256_FIXED);
 
+/// Signing of ASN.1 DER-encoded ECDSA signatures using the P-224 curve and SHA-224.
+/// # ⚠️ Warning
+/// P-224 provides a security strength of only 112 bits.
+/// This scheme should only be used for backwards compatibility purposes.
+pub static ECDSA_P224_SHA224_ASN1_SIGNING: EcdsaSigningAlgorithm =
+    EcdsaSigningAlgorithm(&ECDSA_P224_SHA224_ASN1);
+
 /// Signing of ASN.1 DER-encoded ECDSA signatures using the P-256 curve and SHA-256.
 pub static ECDSA_P256_SHA256_ASN1_SIGNING: EcdsaSigningAlgorithm =
     EcdsaSigningAlgorithm(&ECDSA_P256_SHA256_ASN1);
@@ -0,0 +1,175 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::aead::siv::{
+    Algorithm, Key, AES_SIV_CMAC_256, AES_SIV_CMAC_384, AES_SIV_CMAC_512,
+    MAX_ASSOCIATED_DATA_COMPONENTS, TAG_LEN,
+};
+use aws_lc_rs::test::from_hex;
+
+fn test_siv_kat(
+    algorithm: &'static Algorithm,
+    key: &str,
+    associated_data: &[&str],
+    plaintext: &str,
+    ciphertext: &str,
+) {
+    let key = Key::new(algorithm, &from_hex(key).unwrap()).unwrap();
+    assert_eq!(algorithm, key.algorithm());
+    let associated_data: Vec<Vec<u8>> = associated_data
+        .iter()
+        .map(|ad| from_hex(ad).unwrap())
+        .collect();
+    let associated_data: Vec<&[u8]> = associated_data.iter().map(Vec::as_slice).collect();
+    let plaintext = from_hex(plaintext).unwrap();
+    let ciphertext = from_hex(ciphertext).unwrap();
+
+    let mut in_out = plaintext.clone();
+    key.seal_in_place_prepend_tag(&associated_data, &mut in_out)
+        .unwrap();
+    assert_eq!(ciphertext, in_out);
+
+    let mut in_out = plaintext.clone();
+    let tag = key
+        .seal_in_place_separate_tag(&associated_data, &mut in_out)
+        .unwrap();
+    assert_eq!(&ciphertext[..TAG_LEN], tag.as_ref());
+    assert_eq!(&ciphertext[TAG_LEN..], in_out.as_slice());
+    let opened = key
+        .open_in_place_separate_tag(&associated_data, tag.as_ref(), &mut in_out)
+        .unwrap();
+    assert_eq!(plaintext.as_slice(), opened);
+
+    let mut in_out = ciphertext.clone();
+    let opened = key.open_in_place(&associated_data, &mut in_out).unwrap();
+    assert_eq!(plaintext.as_slice(), opened);
+
+    // Modifying the ciphertext, or any associated data component, fails authentication.
+    for i in 0..ciphertext.len() {
+        let mut in_out = ciphertext.clone();
+        in_out[i] ^= 1;
+        assert!(key.open_in_place(&associated_data, &mut in_out).is_err());
+        assert!(in_out[TAG_LEN..].iter().all(|b| *b == 0));
+    }
+    for i in 0..associated_data.len() {
+        let mut modified = associated_data.clone();
+        modified.remove(i);
+        let mut in_out = ciphertext.clone();
+        assert!(key.open_in_place(&modified, &mut in_out).is_err());
+    }
+}
+
+#[test]
+fn test_rfc5297_deterministic() {
+    // RFC 5297 Appendix A.1
+    test_siv_kat(
+        &AES_SIV_CMAC_256,
+        "fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+        &["101112131415161718191a1b1c1d1e1f2021222324252627"],
+        "112233445566778899aabbccddee",
+        "85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c",
+    );
+}
+
+#[test]
+fn test_rfc5297_nonce_based() {
+    // RFC 5297 Appendix A.2
+    test_siv_kat(
+        &AES_SIV_CMAC_256,
+        "7f7e7d7c7b7a79787776757473727170404142434445464748494a4b4c4d4e4f",
+        &[
+            "00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100",
+            "102030405060708090a0",
+            "09f911029d74e35bd84156c5635688c0",
+        ],
+        "7468697320697320736f6d6520706c61696e7465787420746f20656e6372797074207573696e67205349562d414553",
+        "7bdb6e3b432667eb06f4d14bff2fbd0fcb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d",
+    );
+}
+
+// Generated with the AES-SIV implementation of the Python `cryptography` package, using the key
+// bytes 00, 01, ..., the associated data "header" and "nonce-0123", and the plaintext bytes
+// 64, 65, ...
+#[test]
+fn test_aes_siv_cmac_384_kat() {
+    let key = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f";
+    let associated_data = ["686561646572", "6e6f6e63652d30313233"];
+    for (plaintext, ciphertext) in [
+        ("", "859f896900bc6519a469f03d60bd4e61"),
+        ("64", "765a7acdc917734fa76f3608a2c0642d00"),
+        (
+            "6465666768696a6b6c6d6e6f707172",
+            "db84b3bee88f395c28de90f39a0a38bf08eb6b9d22faa16bf483b8e298277e",
+        ),
+        (
+            "6465666768696a6b6c6d6e6f70717273",
+            "c291e2bc2fef0b54c9ea843f9d6566c44d635df8ea272ae9f32bbb778db90c98",
+        ),
+        (
+            "6465666768696a6b6c6d6e6f7071727374",
+            "9dc26dec3b0f65b4191a9a64c4dd2b99044449a02ed060e65a45d4d4fc04b8364f",
+        ),
+    ] {
+        test_siv_kat(
+            &AES_SIV_CMAC_384,
+            key,
+            &associated_data,
+            plaintext,
+            ciphertext,
+        );
+    }
+}
+
+#[test]
+fn test_aes_siv_cmac_512_kat() {
+    let key = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f";
+    let associated_data = ["686561646572", "6e6f6e63652d30313233"];
+    for (plaintext, ciphertext) in [
+        ("", "168e7794586dc087972fd8694a3eff53"),
+        ("64", "13b19c0bbb3f63dcae83cff5b51232ee42"),
+        (
+            "6465666768696a6b6c6d6e6f707172",
+            "51c86f56e08382f5c9cae08a179545acae9462eb0593554dc4a1076fb02e2f",
+        ),
+        (
+            "6465666768696a6b6c6d6e6f70717273",
+            "13d5f3d526599b6411ada691255a5569d86dc471a61b109d7c6eb8f8b9d16c83",
+        ),
+        (
+            "6465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f808182838485868788898a8b",
+            "b97105de9b43b53ba1b1511cbeb4988f2f2cb097116f390f3482bff5c6b3198a3f4588c84a5f09afac8c20f34cf85fe25dea827e42df7d5d",
+        ),
+    ] {
+        test_siv_kat(
+            &AES_SIV_CMAC_512,
+            key,
+            &associated_data,
+            plaintext,
+            ciphertext,
+        );
+    }
+}
+
+#[test]
+fn test_aes_siv_invalid() {
+    let key = Key::new(&AES_SIV_CMAC_256, &[0u8; 32]).unwrap();
+
+    // Too many associated data components.
+    let associated_data = vec![&b"ad"[..]; MAX_ASSOCIATED_DATA_COMPONENTS + 1];
+    let mut in_out = vec![0u8; 20];
+    assert!(key
+        .seal_in_place_separate_tag(&associated_data, &mut in_out)
+        .is_err());
+    assert!(key.open_in_place(&associated_data, &mut in_out).is_err());
+    let associated_data = vec![&b"ad"[..]; MAX_ASSOCIATED_DATA_COMPONENTS];
+    let mut in_out = vec![0u8; 20];
+    key.seal_in_place_prepend_tag(&associated_data, &mut in_out)
+        .unwrap();
+    assert!(key.open_in_place(&associated_data, &mut in_out).is_ok());
+
+    // The ciphertext must contain the synthetic IV.
+    assert!(key.open_in_place(&[], &mut [0u8; TAG_LEN - 1]).is_err());
+    assert!(key
+        .open_in_place_separate_tag(&[], &[0u8; TAG_LEN - 1], &mut [0u8; 4])
+        .is_err());
+}
@@ -5,7 +5,7 @@
 
 use aws_lc_rs::{aead, error, test, test_file};
 
-use aws_lc_rs::aead::{Nonce, NONCE_LEN};
+use aws_lc_rs::aead::{ExtendedNonce, Nonce, EXTENDED_NONCE_LEN, NONCE_LEN};
 use core::ops::RangeFrom;
 use mirai_annotations::unrecoverable;
 
@@ -17,6 +17,14 @@ fn aead_aes_gcm_128() {
     );
 }
 
+#[test]
+fn aead_aes_gcm_192() {
+    test_aead_all(
+        &aead::AES_192_GCM,
+        test_file!("data/aead_aes_192_gcm_tests.txt"),
+    );
+}
+
 #[test]
 fn aead_aes_gcm_256() {
     test_aead_all(
@@ -49,6 +57,55 @@ fn aead_chacha20_poly1305() {
     );
 }
 
+#[test]
+fn aead_xchacha20_poly1305() {
+    test_aead_all(
+        &aead::XCHACHA20_POLY1305,
+        test_file!("data/aead_xchacha20_poly1305_tests.txt"),
+    );
+}
+
+#[test]
+fn aead_aes_128_ccm_bluetooth() {
+    test_aead_all(
+        &aead::AES_128_CCM_BLUETOOTH,
+        test_file!("data/aead_aes_128_ccm_bluetooth_tests.txt"),
+    );
+}
+
+#[test]
+fn aead_aes_128_ccm_bluetooth_8() {
+    test_aead_all(
+        &aead::AES_128_CCM_BLUETOOTH_8,
+        test_file!("data/aead_aes_128_ccm_bluetooth_8_tests.txt"),
+    );
+}
+
+#[test]
+fn aead_aes_128_ccm_matter() {
+    test_aead_all(
+        &aead::AES_128_CCM_MATTER,
+        test_file!("data/aead_aes_128_ccm_matter_tests.txt"),
+    );
+}
+
+#[cfg(not(feature = "fips"))]
+#[test]
+fn aead_aes_128_ccm() {
+    test_aead_all(
+        aead::aes_128_ccm(4, 10).unwrap(),
+        test_file!("data/aead_aes_128_ccm_4_10_tests.txt"),
+    );
+    test_aead_all(
+        aead::aes_128_ccm(8, 7).unwrap(),
+        test_file!("data/aead_aes_128_ccm_8_7_tests.txt"),
+    );
+    test_aead_all(
+        aead::aes_128_ccm(16, 12).unwrap(),
+        test_file!("data/aead_aes_128_ccm_16_12_tests.txt"),
+    );
+}
+
 /// Tests all combinations of sealer and opener functions
 fn test_aead_all(aead_alg: &'static aead::Algorithm, test_file: test::File) {
     let mut sealers = vec![seal_with_key, seal_with_less_safe_key];
@@ -77,14 +134,14 @@ fn test_aead<Seal, Open>(
     Seal: Fn(
         &'static aead::Algorithm,
         &[u8],
-        Nonce,
+        ExtendedNonce,
         aead::Aad<&[u8]>,
         &mut Vec<u8>,
     ) -> Result<(), error::Unspecified>,
     Open: for<'a> Fn(
         &'static aead::Algorithm,
         &[u8],
-        Nonce,
+        ExtendedNonce,
         aead::Aad<&[u8]>,
         &'a mut [u8],
         RangeFrom<usize>,
@@ -153,14 +210,14 @@ fn test_aead<Seal, Open>(
 
         match &error {
             Some(err) if err == "WRONG_NONCE_LENGTH" => {
-                assert!(Nonce::try_assume_unique_for_key(&nonce_bytes).is_err());
+                assert!(make_nonce(aead_alg, &nonce_bytes).is_err());
                 return Ok(());
             }
             _ => (),
         };
 
         let mut s_in_out = plaintext.clone();
-        let nonce = Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap();
+        let nonce = make_nonce(aead_alg, &nonce_bytes).unwrap();
         let s_result = seal(
             aead_alg,
             &key_bytes[..],
@@ -197,7 +254,7 @@ fn test_aead<Seal, Open>(
             o_in_out.extend_from_slice(&ct[..]);
 
             let o_in_out_clone = o_in_out.clone();
-            let nonce = Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap();
+            let nonce = make_nonce(aead_alg, &nonce_bytes).unwrap();
             let o_result = open(
                 aead_alg,
                 &key_bytes,
@@ -251,7 +308,7 @@ fn aead_open_bad_tag<Open>(
     Open: for<'a> Fn(
         &'static aead::Algorithm,
         &[u8],
-        Nonce,
+        ExtendedNonce,
         aead::Aad<&[u8]>,
         &'a mut [u8],
         RangeFrom<usize>,
@@ -262,7 +319,7 @@ fn aead_open_bad_tag<Open>(
     in_out[in_out_len - 1] ^= 0x08;
     let nonce_bytes = Vec::from(nonce_bytes);
     let aad_bytes = Vec::from(aad_bytes);
-    let nonce = Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap();
+    let nonce = make_nonce(aead_alg, &nonce_bytes).unwrap();
     let aad = aead::Aad::from(aad_bytes.as_slice());
     let err_result = open(
         aead_alg,
@@ -287,7 +344,7 @@ fn aead_open_bad_nonce<Open>(
     Open: for<'a> Fn(
         &'static aead::Algorithm,
         &[u8],
-        Nonce,
+        ExtendedNonce,
         aead::Aad<&[u8]>,
         &'a mut [u8],
         RangeFrom<usize>,
@@ -295,9 +352,10 @@ fn aead_open_bad_nonce<Open>(
 {
     let mut in_out = Vec::from(in_out);
     let mut nonce_bytes = Vec::from(nonce_bytes);
-    nonce_bytes[NONCE_LEN - 1] ^= 0x80;
+    let nonce_len = nonce_bytes.len();
+    nonce_bytes[nonce_len - 1] ^= 0x80;
     let aad_bytes = Vec::from(aad_bytes);
-    let nonce = Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap();
+    let nonce = make_nonce(aead_alg, &nonce_bytes).unwrap();
     let aad = aead::Aad::from(aad_bytes.as_slice());
     let err_result = open(
         aead_alg,
@@ -322,7 +380,7 @@ fn aead_open_bad_aad<Open>(
     Open: for<'a> Fn(
         &'static aead::Algorithm,
         &[u8],
-        Nonce,
+        ExtendedNonce,
         aead::Aad<&[u8]>,
         &'a mut [u8],
         RangeFrom<usize>,
@@ -337,7 +395,7 @@ fn aead_open_bad_aad<Open>(
     } else {
         aad_bytes[aad_len - 1] ^= 0x08;
     }
-    let nonce = Nonce::try_assume_unique_for_key(&nonce_bytes).unwrap();
+    let nonce = make_nonce(aead_alg, &nonce_bytes).unwrap();
     let aad = aead::Aad::from(aad_bytes.as_slice());
     let err_result = open(
         aead_alg,
@@ -353,7 +411,7 @@ fn aead_open_bad_aad<Open>(
 fn seal_with_key(
     algorithm: &'static aead::Algorithm,
     key: &[u8],
-    nonce: Nonce,
+    nonce: ExtendedNonce,
     aad: aead::Aad<&[u8]>,
     in_out: &mut Vec<u8>,
 ) -> Result<(), error::Unspecified> {
@@ -364,7 +422,7 @@ fn seal_with_key(
 fn open_with_key<'a>(
     algorithm: &'static aead::Algorithm,
     key: &[u8],
-    nonce: Nonce,
+    nonce: ExtendedNonce,
     aad: aead::Aad<&[u8]>,
     in_out: &'a mut [u8],
     ciphertext_and_tag: RangeFrom<usize>,
@@ -376,7 +434,7 @@ fn open_with_key<'a>(
 fn seal_with_less_safe_key(
     algorithm: &'static aead::Algorithm,
     key: &[u8],
-    nonce: Nonce,
+    nonce: ExtendedNonce,
     aad: aead::Aad<&[u8]>,
     in_out: &mut Vec<u8>,
 ) -> Result<(), error::Unspecified> {
@@ -387,7 +445,7 @@ fn seal_with_less_safe_key(
 fn seal_with_less_safe_key_scatter(
     algorithm: &'static aead::Algorithm,
     key: &[u8],
-    nonce: Nonce,
+    nonce: ExtendedNonce,
     aad: aead::Aad<&[u8]>,
     in_out: &mut Vec<u8>,
 ) -> Result<(), error::Unspecified> {
@@ -414,7 +472,7 @@ fn seal_with_less_safe_key_scatter(
 fn open_with_less_safe_key<'a>(
     algorithm: &'static aead::Algorithm,
     key: &[u8],
-    nonce: Nonce,
+    nonce: ExtendedNonce,
     aad: aead::Aad<&[u8]>,
     in_out: &'a mut [u8],
     ciphertext_and_tag: RangeFrom<usize>,
@@ -426,7 +484,7 @@ fn open_with_less_safe_key<'a>(
 fn open_with_less_safe_key_gather<'a>(
     algorithm: &'static aead::Algorithm,
     key: &[u8],
-    nonce: Nonce,
+    nonce: ExtendedNonce,
     aad: aead::Aad<&[u8]>,
     in_out: &'a mut [u8],
     ciphertext_and_tag: RangeFrom<usize>,
@@ -471,6 +529,17 @@ fn test_aead_key_sizes(aead_alg: &'static aead::Algorithm) {
     assert!(aead::UnboundKey::new(aead_alg, &[0]).is_err());
 }
 
+fn make_nonce(
+    aead_alg: &'static aead::Algorithm,
+    nonce_bytes: &[u8],
+) -> Result<ExtendedNonce, error::Unspecified> {
+    match aead_alg.nonce_len() {
+        NONCE_LEN => Nonce::try_assume_unique_for_key(nonce_bytes).map(ExtendedNonce::from),
+        EXTENDED_NONCE_LEN => ExtendedNonce::try_assume_unique_for_key(nonce_bytes),
+        _ => ExtendedNonce::try_assume_unique_for_algorithm(aead_alg, nonce_bytes),
+    }
+}
+
 // Test that we reject non-standard nonce sizes.
 #[allow(clippy::range_plus_one)]
 #[test]
@@ -488,6 +557,61 @@ fn test_aead_nonce_sizes() {
     assert!(Nonce::try_assume_unique_for_key(&nonce[..16]).is_err()); // 128 bits.
 }
 
+// Test that keys reject nonces of the wrong size for their algorithm.
+#[test]
+fn test_aead_nonce_algorithm_mismatch() {
+    let standard = || ExtendedNonce::from(Nonce::assume_unique_for_key([0u8; NONCE_LEN]));
+    let extended = || ExtendedNonce::assume_unique_for_key([0u8; EXTENDED_NONCE_LEN]);
+
+    for (aead_alg, wrong_nonce) in [
+        (&aead::AES_128_GCM, &extended as &dyn Fn() -> ExtendedNonce),
+        (&aead::AES_192_GCM, &extended),
+        (&aead::AES_256_GCM, &extended),
+        (&aead::CHACHA20_POLY1305, &extended),
+        (&aead::XCHACHA20_POLY1305, &standard),
+        (&aead::AES_128_CCM_BLUETOOTH, &standard),
+        (&aead::AES_128_CCM_MATTER, &extended),
+    ] {
+        let key = make_less_safe_key(aead_alg, &vec![0u8; aead_alg.key_len()]);
+        let mut in_out = vec![0u8; 32];
+        assert!(key
+            .seal_in_place_append_tag(wrong_nonce(), aead::Aad::empty(), &mut in_out)
+            .is_err());
+        assert!(key
+            .seal_in_place_separate_tag(wrong_nonce(), aead::Aad::empty(), &mut in_out)
+            .is_err());
+        assert!(key
+            .open_in_place(wrong_nonce(), aead::Aad::empty(), &mut in_out)
+            .is_err());
+    }
+}
+
+#[test]
+fn test_aead_ccm_short_tags() {
+    let key_bytes = [0x42u8; 16];
+    let nonce_bytes = [0x24u8; 13];
+
+    for aead_alg in [&aead::AES_128_CCM_BLUETOOTH, &aead::AES_128_CCM_BLUETOOTH_8] {
+        let key = make_less_safe_key(aead_alg, &key_bytes);
+        let plaintext = b"short tags";
+
+        let mut in_out = plaintext.to_vec();
+        let nonce = ExtendedNonce::try_assume_unique_for_algorithm(aead_alg, &nonce_bytes).unwrap();
+        let tag = key
+            .seal_in_place_separate_tag(nonce, aead::Aad::empty(), &mut in_out)
+            .unwrap();
+        assert_eq!(aead_alg.tag_len(), tag.as_ref().len());
+        assert!(tag.as_ref().len() < aead::MAX_TAG_LEN);
+
+        in_out.extend_from_slice(tag.as_ref());
+        let nonce = ExtendedNonce::try_assume_unique_for_algorithm(aead_alg, &nonce_bytes).unwrap();
+        let opened = key
+            .open_in_place(nonce, aead::Aad::empty(), &mut in_out)
+            .unwrap();
+        assert_eq!(plaintext, opened);
+    }
+}
+
 #[allow(clippy::range_plus_one, clippy::cast_possible_truncation)]
 #[test]
 fn aead_chacha20_poly1305_openssh() {
@@ -600,6 +724,172 @@ fn test_aead_thread_safeness() {
     }
 }
 
+// Makes a distinct nonce of the algorithm's length for each counter value.
+fn batch_nonce(algorithm: &'static aead::Algorithm, counter: u64) -> ExtendedNonce {
+    let mut nonce = vec![0u8; algorithm.nonce_len()];
+    let len = nonce.len();
+    nonce[len - 8..].copy_from_slice(&counter.to_be_bytes());
+    ExtendedNonce::try_assume_unique_for_algorithm(algorithm, &nonce).unwrap()
+}
+
+#[test]
+fn test_aead_batch() {
+    for algorithm in [
+        &aead::AES_128_GCM,
+        &aead::AES_256_GCM,
+        &aead::CHACHA20_POLY1305,
+        &aead::XCHACHA20_POLY1305,
+        &aead::AES_128_CCM_MATTER,
+    ] {
+        let key = make_less_safe_key(algorithm, &[0x42; 32][..algorithm.key_len()]);
+        let plaintexts: Vec<Vec<u8>> = (0..20usize).map(|len| vec![len as u8; len * 7]).collect();
+
+        let mut sealed = plaintexts.clone();
+        let tags = key.seal_in_place_separate_tag_batch(sealed.iter_mut().enumerate().map(
+            |(i, in_out)| {
+                (
+                    batch_nonce(algorithm, i as u64),
+                    aead::Aad::from([i as u8]),
+                    in_out.as_mut_slice(),
+                )
+            },
+        ));
+        assert_eq!(plaintexts.len(), tags.len());
+
+        let mut in_outs = Vec::new();
+        for (i, (plaintext, tag)) in plaintexts.iter().zip(tags).enumerate() {
+            let mut expected = plaintext.clone();
+            let expected_tag = key
+                .seal_in_place_separate_tag(
+                    batch_nonce(algorithm, i as u64),
+                    aead::Aad::from([i as u8]),
+                    &mut expected,
+                )
+                .unwrap();
+            assert_eq!(expected, sealed[i]);
+            assert_eq!(expected_tag.as_ref(), tag.unwrap().as_ref());
+
+            let mut in_out = expected;
+            in_out.extend_from_slice(expected_tag.as_ref());
+            in_outs.push(in_out);
+        }
+
+        // A message that fails to open does not affect the others.
+        in_outs[3][0] ^= 1;
+        let opened = key.open_in_place_batch(in_outs.iter_mut().enumerate().map(|(i, in_out)| {
+            (
+                batch_nonce(algorithm, i as u64),
+                aead::Aad::from([i as u8]),
+                in_out.as_mut_slice(),
+            )
+        }));
+        for (i, (plaintext, result)) in plaintexts.iter().zip(opened).enumerate() {
+            if i == 3 {
+                assert!(result.is_err());
+            } else {
+                assert_eq!(plaintext.as_slice(), result.unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_aead_batch_invalid_items() {
+    let key = make_less_safe_key(&aead::AES_128_GCM, &[0x42; 16]);
+    let mut in_outs = vec![vec![0x5a; 16]; 3];
+    let wrong_nonce = ExtendedNonce::assume_unique_for_key([0u8; EXTENDED_NONCE_LEN]);
+    let nonces = [
+        batch_nonce(&aead::AES_128_GCM, 0),
+        wrong_nonce,
+        batch_nonce(&aead::AES_128_GCM, 2),
+    ];
+    let tags = key.seal_in_place_separate_tag_batch(
+        nonces
+            .into_iter()
+            .zip(in_outs.iter_mut())
+            .map(|(nonce, in_out)| (nonce, aead::Aad::empty(), in_out.as_mut_slice())),
+    );
+    assert!(tags[0].is_ok());
+    assert!(tags[1].is_err());
+    assert!(tags[2].is_ok());
+    // The message with the invalid nonce is left untouched.
+    assert_eq!(vec![0x5a; 16], in_outs[1]);
+}
+
+#[test]
+fn test_aead_batch_randomized_nonce() {
+    let key = aead::RandomizedNonceKey::new(&aead::AES_256_GCM, &[0x42; 32]).unwrap();
+    let plaintexts: Vec<Vec<u8>> = (0..10usize).map(|len| vec![len as u8; len * 13]).collect();
+
+    let mut in_outs = plaintexts.clone();
+    let sealed = key.seal_in_place_separate_tag_batch(
+        in_outs
+            .iter_mut()
+            .map(|in_out| (aead::Aad::empty(), in_out.as_mut_slice())),
+    );
+    let mut nonces = Vec::new();
+    for (in_out, result) in in_outs.iter_mut().zip(sealed) {
+        let (nonce, tag) = result.unwrap();
+        in_out.extend_from_slice(tag.as_ref());
+        nonces.push(nonce);
+    }
+
+    let opened = key.open_in_place_batch(
+        nonces
+            .into_iter()
+            .zip(in_outs.iter_mut())
+            .map(|(nonce, in_out)| (nonce, aead::Aad::empty(), in_out.as_mut_slice())),
+    );
+    for (plaintext, result) in plaintexts.iter().zip(opened) {
+        assert_eq!(plaintext.as_slice(), result.unwrap());
+    }
+
+    // The nonces of XChaCha20-Poly1305 can't be returned as a `Nonce`.
+    let key = aead::RandomizedNonceKey::new(&aead::XCHACHA20_POLY1305, &[0x42; 32]).unwrap();
+    let mut in_out = vec![0x5a; 16];
+    let sealed = key.seal_in_place_separate_tag_batch([(aead::Aad::empty(), &mut in_out[..])]);
+    assert!(sealed[0].is_err());
+    assert_eq!(vec![0x5a; 16], in_out);
+}
+
+#[test]
+fn test_aead_batch_tls() {
+    let mut key =
+        aead::TlsRecordSealingKey::new(&aead::AES_128_GCM, aead::TlsProtocolId::TLS12, &[0x42; 16])
+            .unwrap();
+    let opening_key = make_less_safe_key(&aead::AES_128_GCM, &[0x42; 16]);
+    let tls_nonce = |counter: u64| {
+        let mut nonce = [0u8; NONCE_LEN];
+        nonce[NONCE_LEN - 8..].copy_from_slice(&counter.to_be_bytes());
+        Nonce::assume_unique_for_key(nonce)
+    };
+
+    // The repeated nonce of the third record is rejected.
+    let counters = [0u64, 1, 1, 2];
+    let mut in_outs: Vec<Vec<u8>> = counters.iter().map(|_| vec![0x5a; 100]).collect();
+    let tags = key.seal_in_place_separate_tag_batch(counters.iter().zip(in_outs.iter_mut()).map(
+        |(counter, in_out)| {
+            (
+                tls_nonce(*counter),
+                aead::Aad::empty(),
+                in_out.as_mut_slice(),
+            )
+        },
+    ));
+    assert!(tags[2].is_err());
+    for (i, (counter, tag)) in counters.iter().zip(tags).enumerate() {
+        if i == 2 {
+            continue;
+        }
+        let in_out = &mut in_outs[i];
+        in_out.extend_from_slice(tag.unwrap().as_ref());
+        let plaintext = opening_key
+            .open_in_place(tls_nonce(*counter), aead::Aad::empty(), in_out)
+            .unwrap();
+        assert_eq!(&[0x5a; 100][..], plaintext);
+    }
+}
+
 #[test]
 fn test_aead_key_debug() {
     let key_bytes = [0; 32];
@@ -634,10 +924,10 @@ fn test_aead_key_debug() {
 fn make_key<K: aead::BoundKey<OneNonceSequence>>(
     algorithm: &'static aead::Algorithm,
     key: &[u8],
-    nonce: Nonce,
+    nonce: impl Into<ExtendedNonce>,
 ) -> K {
     let key = aead::UnboundKey::new(algorithm, key).unwrap();
-    let nonce_sequence = OneNonceSequence::new(nonce);
+    let nonce_sequence = OneNonceSequence::new(nonce.into());
     K::new(key, nonce_sequence)
 }
 
@@ -646,18 +936,23 @@ fn make_less_safe_key(algorithm: &'static aead::Algorithm, key: &[u8]) -> aead::
     aead::LessSafeKey::new(key)
 }
 
-struct OneNonceSequence(Option<Nonce>);
+struct OneNonceSequence(Option<ExtendedNonce>);
 
 impl OneNonceSequence {
     /// Constructs the sequence allowing `advance()` to be called
     /// `allowed_invocations` times.
-    fn new(nonce: Nonce) -> Self {
+    fn new(nonce: ExtendedNonce) -> Self {
         Self(Some(nonce))
     }
 }
 
 impl aead::NonceSequence for OneNonceSequence {
     fn advance(&mut self) -> Result<Nonce, error::Unspecified> {
+        let nonce = self.advance_extended()?;
+        Nonce::try_assume_unique_for_key(nonce.as_ref())
+    }
+
+    fn advance_extended(&mut self) -> Result<ExtendedNonce, error::Unspecified> {
         self.0.take().ok_or(error::Unspecified)
     }
 }
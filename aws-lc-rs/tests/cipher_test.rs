@@ -4,7 +4,8 @@
 use aws_lc_rs::cipher::{
     DecryptingKey, DecryptionContext, EncryptingKey, EncryptionContext, OperatingMode,
     PaddedBlockDecryptingKey, PaddedBlockEncryptingKey, StreamingDecryptingKey,
-    StreamingEncryptingKey, UnboundCipherKey, AES_128, AES_256,
+    StreamingEncryptingKey, UnboundCipherKey, AES_128, AES_192, AES_256, AES_256_XTS, CHACHA20,
+    CHACHA20_NONCE_LEN, XCHACHA20, XCHACHA20_NONCE_LEN,
 };
 use aws_lc_rs::iv::{FixedLength, IV_LEN_128_BIT};
 use aws_lc_rs::test::from_hex;
@@ -40,7 +41,15 @@ fn step_encrypt(
             break;
         }
     }
-    let out_end = out_idx + alg.block_len();
+    // XTS buffers the whole data unit and writes it when finishing, and the ciphertext
+    // stealing modes hold back up to two blocks.
+    let out_end = match mode {
+        OperatingMode::XTS => out_idx + n,
+        OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => {
+            out_idx + 2 * alg.block_len()
+        }
+        _ => out_idx + alg.block_len(),
+    };
     let (decrypt_iv, output) = encrypting_key
         .finish(&mut ciphertext[out_idx..out_end])
         .unwrap();
@@ -51,7 +60,11 @@ fn step_encrypt(
             assert!(ciphertext.len() > plaintext.len());
             assert!(ciphertext.len() <= plaintext.len() + alg.block_len());
         }
-        OperatingMode::CTR => {
+        OperatingMode::CTR
+        | OperatingMode::XTS
+        | OperatingMode::CBC_CS1
+        | OperatingMode::CBC_CS2
+        | OperatingMode::CBC_CS3 => {
             assert_eq!(ciphertext.len(), plaintext.len());
         }
         _ => panic!("Unknown cipher mode"),
@@ -90,7 +103,15 @@ fn step_decrypt(
             break;
         }
     }
-    let out_end = out_idx + alg.block_len();
+    // XTS buffers the whole data unit and writes it when finishing, and the ciphertext
+    // stealing modes hold back up to two blocks.
+    let out_end = match mode {
+        OperatingMode::XTS => out_idx + n,
+        OperatingMode::CBC_CS1 | OperatingMode::CBC_CS2 | OperatingMode::CBC_CS3 => {
+            out_idx + 2 * alg.block_len()
+        }
+        _ => out_idx + alg.block_len(),
+    };
     let output = decrypting_key
         .finish(&mut plaintext[out_idx..out_end])
         .unwrap();
@@ -101,7 +122,11 @@ fn step_decrypt(
             assert!(ciphertext.len() > plaintext.len());
             assert!(ciphertext.len() <= plaintext.len() + alg.block_len());
         }
-        OperatingMode::CTR => {
+        OperatingMode::CTR
+        | OperatingMode::XTS
+        | OperatingMode::CBC_CS1
+        | OperatingMode::CBC_CS2
+        | OperatingMode::CBC_CS3 => {
             assert_eq!(ciphertext.len(), plaintext.len());
         }
         _ => panic!("Unknown cipher mode"),
@@ -311,6 +336,63 @@ macro_rules! cipher_rt {
     };
 }
 
+macro_rules! xts_cipher_kat {
+    ($name:ident, $key:literal, $sector:literal, $plaintext:literal, $ciphertext:literal) => {
+        paste! {
+        #[test]
+        fn $name() {
+            let key = from_hex($key).unwrap();
+            let input = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+
+            let unbound_key = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+            let encrypting_key = EncryptingKey::xts(unbound_key).unwrap();
+            assert_eq!(OperatingMode::XTS, encrypting_key.mode());
+            assert_eq!(&AES_256_XTS, encrypting_key.algorithm());
+            let mut in_out = input.clone();
+            let context = encrypting_key
+                .less_safe_encrypt(in_out.as_mut_slice(), EncryptionContext::xts_sector($sector))
+                .unwrap();
+            assert_eq!(expected_ciphertext.as_slice(), in_out);
+
+            let unbound_key2 = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+            let decrypting_key = DecryptingKey::xts(unbound_key2).unwrap();
+            assert_eq!(OperatingMode::XTS, decrypting_key.mode());
+            assert_eq!(&AES_256_XTS, decrypting_key.algorithm());
+            let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+            assert_eq!(input.as_slice(), plaintext);
+        }
+
+        #[test]
+        fn [<$name _streaming>]() {
+            let key = from_hex($key).unwrap();
+            let input = from_hex($plaintext).unwrap();
+            let expected_ciphertext = from_hex($ciphertext).unwrap();
+
+            for step in 2..=9 {
+                let unbound_key = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+                let encrypting_key = StreamingEncryptingKey::less_safe_xts(
+                    unbound_key,
+                    EncryptionContext::xts_sector($sector),
+                )
+                .unwrap();
+
+                let (ciphertext, decrypt_ctx) = step_encrypt(encrypting_key, &input, step);
+
+                assert_eq!(expected_ciphertext.as_slice(), ciphertext.as_ref());
+
+                let unbound_key2 = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+                let decrypting_key =
+                    StreamingDecryptingKey::xts(unbound_key2, decrypt_ctx).unwrap();
+
+                let plaintext = step_decrypt(decrypting_key, &ciphertext, step);
+                assert_eq!(input.as_slice(), plaintext.as_ref());
+            }
+        }
+        }
+    };
+}
+
 padded_cipher_kat!(
     test_kat_aes_128_cbc_16_bytes,
     &AES_128,
@@ -410,6 +492,140 @@ padded_cipher_kat!(
     "f1af484830a149ee0387b854d65fe87ca0e62efc1c8e6909d4b9ab8666470453"
 );
 
+// AES-192 vectors from NIST SP 800-38A, Appendix F.2.3 and F.5.5.
+padded_cipher_kat!(
+    test_kat_aes_192_cbc_32_bytes,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc_pkcs7,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "4f021db243bc633d7178183a9fa071e8b4d9ada9ad7dedf4e5e738763f69145ac81ca99c3a1e883fa8d834316a2275ec"
+);
+
+cipher_kat!(
+    test_kat_aes_192_ctr_64_bytes,
+    &AES_192,
+    OperatingMode::CTR,
+    ctr,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "1abc932417521ca24f2b0459fe7e6e0b090339ec0aa6faefd5ccc2c6f4ce8e941e36b26bd1ebc670d1bd1d665620abf74f78a7f6d29809585a97daec58c6b050"
+);
+
+cipher_kat!(
+    test_kat_aes_192_ctr_15_bytes,
+    &AES_192,
+    OperatingMode::CTR,
+    ctr,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+    "6bc1bee22e409f96e93d7e11739317",
+    "1abc932417521ca24f2b0459fe7e6e"
+);
+
+// Vectors from NIST SP 800-38A, Appendix F.3 and F.4.
+cipher_kat!(
+    test_kat_aes_128_cfb8_18_bytes,
+    &AES_128,
+    OperatingMode::CFB8,
+    cfb8,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d",
+    "3b79424c9c0dd436bace9e0ed4586a4f32b9"
+);
+
+cipher_kat!(
+    test_kat_aes_192_cfb8_18_bytes,
+    &AES_192,
+    OperatingMode::CFB8,
+    cfb8,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d",
+    "cda2521ef0a905ca44cd057cbf0d47a0678a"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cfb8_18_bytes,
+    &AES_256,
+    OperatingMode::CFB8,
+    cfb8,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d",
+    "dc1f1a8520a64db55fcc8ac554844e889700"
+);
+
+cipher_kat!(
+    test_kat_aes_128_cfb128_64_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "3b3fd92eb72dad20333449f8e83cfb4ac8a64537a0b3a93fcde3cdad9f1ce58b26751f67a3cbb140b1808cf187a4f4dfc04b05357c5d1c0eeac4c66f9ff7f2e6"
+);
+
+cipher_kat!(
+    test_kat_aes_192_cfb128_64_bytes,
+    &AES_192,
+    OperatingMode::CFB128,
+    cfb128,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "cdc80d6fddf18cab34c25909c99a417467ce7f7f81173621961a2b70171d3d7a2e1e8a1dd59b88b1c8e60fed1efac4c9c05f9f9ca9834fa042ae8fba584b09ff"
+);
+
+cipher_kat!(
+    test_kat_aes_256_cfb128_64_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "dc7e84bfda79164b7ecd8486985d386039ffed143b28b1c832113c6331e5407bdf10132415e54b92a13ed0a8267ae2f975a385741ab9cef82031623d55b1e471"
+);
+
+cipher_kat!(
+    test_kat_aes_128_ofb_64_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "3b3fd92eb72dad20333449f8e83cfb4a7789508d16918f03f53c52dac54ed8259740051e9c5fecf64344f7a82260edcc304c6528f659c77866a510d9c1d6ae5e"
+);
+
+cipher_kat!(
+    test_kat_aes_192_ofb_64_bytes,
+    &AES_192,
+    OperatingMode::OFB,
+    ofb,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "cdc80d6fddf18cab34c25909c99a4174fcc28b8d4c63837c09e81700c11004018d9a9aeac0f6596f559c6d4daf59a5f26d9f200857ca6c3e9cac524bd9acc92a"
+);
+
+cipher_kat!(
+    test_kat_aes_256_ofb_64_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "dc7e84bfda79164b7ecd8486985d38604febdc6740d20b3ac88f6ad82a4fb08d71ab47a086e86eedf39d1c5bba97c4080126141d67f37be8538f5a8be740e484"
+);
+
 padded_cipher_rt!(
     test_rt_aes_128_cbc_16_bytes,
     &AES_128,
@@ -509,6 +725,78 @@ padded_cipher_rt!(
     "aece7b5e3c3df1ffc9802d2dfe296dc734"
 );
 
+padded_cipher_rt!(
+    test_rt_aes_192_cbc_17_bytes,
+    &AES_192,
+    OperatingMode::CBC,
+    cbc_pkcs7,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
+cipher_rt!(
+    test_rt_aes_192_ctr_17_bytes,
+    &AES_192,
+    OperatingMode::CTR,
+    ctr,
+    "8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
+cipher_rt!(
+    test_rt_aes_128_cfb8_17_bytes,
+    &AES_128,
+    OperatingMode::CFB8,
+    cfb8,
+    "000102030405060708090a0b0c0d0e0f",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
+cipher_rt!(
+    test_rt_aes_256_cfb8_17_bytes,
+    &AES_256,
+    OperatingMode::CFB8,
+    cfb8,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
+cipher_rt!(
+    test_rt_aes_128_cfb128_17_bytes,
+    &AES_128,
+    OperatingMode::CFB128,
+    cfb128,
+    "000102030405060708090a0b0c0d0e0f",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
+cipher_rt!(
+    test_rt_aes_256_cfb128_17_bytes,
+    &AES_256,
+    OperatingMode::CFB128,
+    cfb128,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
+cipher_rt!(
+    test_rt_aes_128_ofb_17_bytes,
+    &AES_128,
+    OperatingMode::OFB,
+    ofb,
+    "000102030405060708090a0b0c0d0e0f",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
+cipher_rt!(
+    test_rt_aes_256_ofb_17_bytes,
+    &AES_256,
+    OperatingMode::OFB,
+    ofb,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "a39c1fdf77ea3e1f18178c0ec237c70a34"
+);
+
 padded_cipher_rt!(
     test_rt_aes_256_cbc_16_bytes,
     &AES_256,
@@ -526,3 +814,650 @@ padded_cipher_rt!(
     "d4a8206dcae01242f9db79a4ecfe277d0f7bb8ccbafd8f9809adb39f35aa9b41",
     "a39c1fdf77ea3e1f18178c0ec237c70a34"
 );
+
+xts_cipher_kat!(
+    test_kat_aes_256_xts_ieee1619_vector_10,
+    "2718281828459045235360287471352662497757247093699959574966967627\
+    3141592653589793238462643383279502884197169399375105820974944592",
+    0xff,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+    202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f\
+    404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f\
+    606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f\
+    808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f\
+    a0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebf\
+    c0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedf\
+    e0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff\
+    000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+    202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f\
+    404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f\
+    606162636465666768696a6b6c6d6e6f707172737475767778797a7b7c7d7e7f\
+    808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f\
+    a0a1a2a3a4a5a6a7a8a9aaabacadaeafb0b1b2b3b4b5b6b7b8b9babbbcbdbebf\
+    c0c1c2c3c4c5c6c7c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedf\
+    e0e1e2e3e4e5e6e7e8e9eaebecedeeeff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+    "1c3b3a102f770386e4836c99e370cf9bea00803f5e482357a4ae12d414a3e63b\
+    5d31e276f8fe4a8d66b317f9ac683f44680a86ac35adfc3345befecb4bb188fd\
+    5776926c49a3095eb108fd1098baec70aaa66999a72a82f27d848b21d4a741b0\
+    c5cd4d5fff9dac89aeba122961d03a757123e9870f8acf1000020887891429ca\
+    2a3e7a7d7df7b10355165c8b9a6d0a7de8b062c4500dc4cd120c0f7418dae3d0\
+    b5781c34803fa75421c790dfe1de1834f280d7667b327f6c8cd7557e12ac3a0f\
+    93ec05c52e0493ef31a12d3d9260f79a289d6a379bc70c50841473d1a8cc81ec\
+    583e9645e07b8d9670655ba5bbcfecc6dc3966380ad8fecb17b6ba02469a020a\
+    84e18e8f84252070c13e9f1f289be54fbc481457778f616015e1327a02b140f1\
+    505eb309326d68378f8374595c849d84f4c333ec4423885143cb47bd71c5edae\
+    9be69a2ffeceb1bec9de244fbe15992b11b77c040f12bd8f6a975a44a0f90c29\
+    a9abc3d4d893927284c58754cce294529f8614dcd2aba991925fedc4ae74ffac\
+    6e333b93eb4aff0479da9a410e4450e0dd7ae4c6e2910900575da401fc07059f\
+    645e8b7e9bfdef33943054ff84011493c27b3429eaedb4ed5376441a77ed4385\
+    1ad77f16f541dfd269d50d6a5f14fb0aab1cbb4c1550be97f7ab4066193c4caa\
+    773dad38014bd2092fa755c824bb5e54c4f36ffda9fcea70b9c6e693e148c151"
+);
+
+xts_cipher_kat!(
+    test_kat_aes_256_xts_16_bytes,
+    "2718281828459045235360287471352662497757247093699959574966967627\
+    3141592653589793238462643383279502884197169399375105820974944592",
+    0x0,
+    "000102030405060708090a0b0c0d0e0f",
+    "3a060a8cad115a6f44572e3759e43c8f"
+);
+
+xts_cipher_kat!(
+    test_kat_aes_256_xts_17_bytes,
+    "2718281828459045235360287471352662497757247093699959574966967627\
+    3141592653589793238462643383279502884197169399375105820974944592",
+    0x1,
+    "000102030405060708090a0b0c0d0e0f10",
+    "a798c8b0d0e45a6fcfaf9d8b98613cd6cd"
+);
+
+xts_cipher_kat!(
+    test_kat_aes_256_xts_31_bytes,
+    "2718281828459045235360287471352662497757247093699959574966967627\
+    3141592653589793238462643383279502884197169399375105820974944592",
+    0x1234,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e",
+    "7dcb0344eb478c1fbab35b18f333c889c5bb5b6de17b94142c64ddb476e7f6"
+);
+
+xts_cipher_kat!(
+    test_kat_aes_256_xts_100_bytes,
+    "2718281828459045235360287471352662497757247093699959574966967627\
+    3141592653589793238462643383279502884197169399375105820974944592",
+    0xfffffffffffffffff,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+    202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f\
+    404142434445464748494a4b4c4d4e4f505152535455565758595a5b5c5d5e5f\
+    60616263",
+    "60dd70c94664c647742440940b2c136286b8519ad34ff67538c5d2a15131530b\
+    5a2ea7c71c452a83f40a5deabe6bbeb572f7ee7b5aadcbf23032372b23d74185\
+    14b67fb19e714758e82a85198a1e0f016ec842b9f80e61388d638d704138aa2a\
+    876e3a43"
+);
+
+cipher_rt!(
+    test_rt_aes_256_xts_16_bytes,
+    &AES_256_XTS,
+    OperatingMode::XTS,
+    xts,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+    202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    "00112233445566778899aabbccddeeff"
+);
+
+cipher_rt!(
+    test_rt_aes_256_xts_33_bytes,
+    &AES_256_XTS,
+    OperatingMode::XTS,
+    xts,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+    202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    "00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff01"
+);
+
+// RFC 3962 Appendix B: AES-128 CBC with ciphertext stealing as used by Kerberos, i.e. CS3.
+cipher_kat!(
+    test_kat_rfc3962_aes_128_cbc_cs3_17_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "636869636b656e207465726979616b69",
+    "00000000000000000000000000000000",
+    "4920776f756c64206c696b652074686520",
+    "c6353568f2bf8cb4d8a580362da7ff7f97"
+);
+
+cipher_kat!(
+    test_kat_rfc3962_aes_128_cbc_cs3_31_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "636869636b656e207465726979616b69",
+    "00000000000000000000000000000000",
+    "4920776f756c64206c696b65207468652047656e6572616c20476175277320",
+    "fc00783e0efdb2c1d445d4c8eff7ed2297687268d6ecccc0c07b25e25ecfe5"
+);
+
+cipher_kat!(
+    test_kat_rfc3962_aes_128_cbc_cs3_32_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "636869636b656e207465726979616b69",
+    "00000000000000000000000000000000",
+    "4920776f756c64206c696b65207468652047656e6572616c2047617527732043",
+    "39312523a78662d5be7fcbcc98ebf5a897687268d6ecccc0c07b25e25ecfe584"
+);
+
+cipher_kat!(
+    test_kat_rfc3962_aes_128_cbc_cs3_47_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "636869636b656e207465726979616b69",
+    "00000000000000000000000000000000",
+    "4920776f756c64206c696b65207468652047656e6572616c20476175277320436869636b656e2c20706c656173652c",
+    "97687268d6ecccc0c07b25e25ecfe584b3fffd940c16a18c1b5549d2f838029e39312523a78662d5be7fcbcc98ebf5"
+);
+
+cipher_kat!(
+    test_kat_rfc3962_aes_128_cbc_cs3_48_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "636869636b656e207465726979616b69",
+    "00000000000000000000000000000000",
+    "4920776f756c64206c696b65207468652047656e6572616c20476175277320436869636b656e2c20706c656173652c20",
+    "97687268d6ecccc0c07b25e25ecfe5849dad8bbb96c4cdc03bc103e1a194bbd839312523a78662d5be7fcbcc98ebf5a8"
+);
+
+cipher_kat!(
+    test_kat_rfc3962_aes_128_cbc_cs3_64_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "636869636b656e207465726979616b69",
+    "00000000000000000000000000000000",
+    "4920776f756c64206c696b65207468652047656e6572616c20476175277320436869636b656e2c20706c656173652c20616e6420776f6e746f6e20736f75702e",
+    "97687268d6ecccc0c07b25e25ecfe58439312523a78662d5be7fcbcc98ebf5a84807efe836ee89a526730dbc2f7bc8409dad8bbb96c4cdc03bc103e1a194bbd8"
+);
+
+// The CBC-AES128 example of NIST SP 800-38A F.2.1 under the ciphertext stealing variants of its
+// Addendum. When the final block is complete, CS1 and CS2 produce the plain CBC ciphertext.
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs1_17_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS1,
+    cbc_cs1,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae",
+    "76b8d266c62a614f00d7c901dc791ecea9"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs2_17_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS2,
+    cbc_cs2,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae",
+    "b8d266c62a614f00d7c901dc791ecea976"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs1_31_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS1,
+    cbc_cs1,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e",
+    "7649abac8119b246cee98e9b12e91947937b55f8652154c6e9a6f35bafbb56"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs2_31_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS2,
+    cbc_cs2,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e",
+    "47937b55f8652154c6e9a6f35bafbb567649abac8119b246cee98e9b12e919"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs1_32_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS1,
+    cbc_cs1,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs2_32_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS2,
+    cbc_cs2,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51",
+    "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs1_47_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS1,
+    cbc_cs1,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52",
+    "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a9176789dc43313f849e395374eac6507d949b7"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs2_47_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS2,
+    cbc_cs2,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52",
+    "7649abac8119b246cee98e9b12e9197d9dc43313f849e395374eac6507d949b75086cb9b507219ee95db113a917678"
+);
+
+cipher_kat!(
+    test_kat_sp800_38a_aes_128_cbc_cs3_64_bytes,
+    &AES_128,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "2b7e151628aed2a6abf7158809cf4f3c",
+    "000102030405060708090a0b0c0d0e0f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b23ff1caa1681fac09120eca307586e1a773bed6b8e3c1743b7116e69e22229516"
+);
+
+cipher_rt!(
+    test_rt_aes_192_cbc_cs1_33_bytes,
+    &AES_192,
+    OperatingMode::CBC_CS1,
+    cbc_cs1,
+    "000102030405060708090a0b0c0d0e0f1011121314151617",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130"
+);
+
+cipher_rt!(
+    test_rt_aes_256_cbc_cs2_45_bytes,
+    &AES_256,
+    OperatingMode::CBC_CS2,
+    cbc_cs2,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e5130c81c46a35ce411e5fbc1191a"
+);
+
+cipher_rt!(
+    test_rt_aes_256_cbc_cs3_16_bytes,
+    &AES_256,
+    OperatingMode::CBC_CS3,
+    cbc_cs3,
+    "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+    "6bc1bee22e409f96e93d7e117393172a"
+);
+
+#[test]
+fn test_aes_128_cbc_unpadded_kat() {
+    // NIST SP 800-38A F.2.1 and F.2.2
+    let key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let iv = from_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+    let input = from_hex(
+        "6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51\
+         30c81c46a35ce411e5fbc1191a0a52eff69f2445df4f9b17ad2b417be66c3710",
+    )
+    .unwrap();
+    let expected_ciphertext = from_hex(
+        "7649abac8119b246cee98e9b12e9197d5086cb9b507219ee95db113a917678b2\
+         73bed6b8e3c1743b7116e69e222295163ff1caa1681fac09120eca307586e1a7",
+    )
+    .unwrap();
+
+    let encrypting_key =
+        EncryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    assert_eq!(OperatingMode::CBC, encrypting_key.mode());
+    let context = EncryptionContext::Iv128(FixedLength::try_from(iv.as_slice()).unwrap());
+    let mut in_out = input.clone();
+    let context = encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .unwrap();
+    assert_eq!(expected_ciphertext, in_out);
+
+    let decrypting_key =
+        DecryptingKey::cbc(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    assert_eq!(OperatingMode::CBC, decrypting_key.mode());
+    let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+    assert_eq!(input.as_slice(), plaintext);
+
+    // Without padding, the input must be a multiple of the block length.
+    let mut in_out = input[..31].to_vec();
+    assert!(encrypting_key.encrypt(&mut in_out).is_err());
+}
+
+#[test]
+fn test_aes_128_cbc_cts_invalid() {
+    let key = [0u8; 16];
+    for (encrypting_key, decrypting_key) in [
+        (
+            EncryptingKey::cbc_cs1(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+            DecryptingKey::cbc_cs1(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+        ),
+        (
+            EncryptingKey::cbc_cs2(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+            DecryptingKey::cbc_cs2(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+        ),
+        (
+            EncryptingKey::cbc_cs3(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+            DecryptingKey::cbc_cs3(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap(),
+        ),
+    ] {
+        // The input must be at least one block long.
+        assert!(encrypting_key.encrypt(&mut []).is_err());
+        assert!(encrypting_key.encrypt(&mut [0u8; 15]).is_err());
+        let context = encrypting_key.encrypt(&mut [0u8; 16]).unwrap();
+        assert!(decrypting_key.decrypt(&mut [0u8; 15], context).is_err());
+    }
+
+    // Only AES keys are supported.
+    let chacha_key = UnboundCipherKey::new(&CHACHA20, &[1u8; 32]).unwrap();
+    assert!(EncryptingKey::cbc_cs3(chacha_key)
+        .unwrap()
+        .encrypt(&mut [0u8; 32])
+        .is_err());
+}
+
+#[test]
+fn test_aes_256_xts_invalid() {
+    // The two halves of an XTS key must differ.
+    let key = [0x42u8; 64];
+    assert!(UnboundCipherKey::new(&AES_256_XTS, &key).is_err());
+    assert!(UnboundCipherKey::new(&AES_256_XTS, &key[..32]).is_err());
+
+    let key = from_hex(
+        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f\
+        202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f",
+    )
+    .unwrap();
+
+    // XTS keys are only usable in XTS mode, and XTS mode only with XTS keys.
+    let unbound_key = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+    assert!(EncryptingKey::ctr(unbound_key).is_err());
+    let unbound_key = UnboundCipherKey::new(&AES_256, &key[..32]).unwrap();
+    assert!(EncryptingKey::xts(unbound_key).is_err());
+
+    // A data unit must be at least one block.
+    let unbound_key = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+    let encrypting_key = EncryptingKey::xts(unbound_key).unwrap();
+    let mut in_out = [0u8; 15];
+    assert!(encrypting_key.encrypt(&mut in_out).is_err());
+
+    // An IV is not a tweak.
+    let mut in_out = [0u8; 16];
+    let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .is_err());
+
+    let unbound_key = UnboundCipherKey::new(&AES_256_XTS, &key).unwrap();
+    let streaming_key = StreamingEncryptingKey::xts(unbound_key).unwrap();
+    assert!(streaming_key.finish(&mut [0u8; 16]).is_err());
+}
+
+#[test]
+fn test_ctr_random_access_kat() {
+    // NIST SP 800-38A F.5.1 CTR-AES128.Encrypt, starting within the second block.
+    let key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let iv: [u8; 16] = from_hex("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let plaintext = from_hex("1e03ac9c9eb76fac45af8e5130c81c46a35ce411").unwrap();
+    let ciphertext = from_hex("7970fdff8617187bb9fffdff5ae4df3edbd5d35e").unwrap();
+
+    let decrypting_key =
+        DecryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut in_out = ciphertext.clone();
+    let context = DecryptionContext::Iv128(FixedLength::from(iv));
+    let result = decrypting_key.decrypt_at(&mut in_out, context, 20).unwrap();
+    assert_eq!(plaintext.as_slice(), result);
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let mut in_out = plaintext.clone();
+    let context = EncryptionContext::Iv128(FixedLength::from(iv));
+    encrypting_key
+        .less_safe_encrypt_at(&mut in_out, context, 20)
+        .unwrap();
+    assert_eq!(ciphertext, in_out);
+}
+
+#[test]
+fn test_ctr_random_access() {
+    let key = from_hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4").unwrap();
+    let plaintext: Vec<u8> = (0..=255).collect();
+
+    // The counter wraps around at 2^128 and carries past 2^32, as with the streaming (EVP) key.
+    for iv in [
+        "f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff",
+        "000102030405060708090a0bfffffffe",
+        "fffffffffffffffffffffffffffffffd",
+    ] {
+        let iv: [u8; 16] = from_hex(iv).unwrap().try_into().unwrap();
+        let streaming_key = StreamingEncryptingKey::less_safe_ctr(
+            UnboundCipherKey::new(&AES_256, &key).unwrap(),
+            EncryptionContext::Iv128(FixedLength::from(iv)),
+        )
+        .unwrap();
+        let (ciphertext, _) = step_encrypt(streaming_key, &plaintext, 256);
+
+        let encrypting_key =
+            EncryptingKey::ctr(UnboundCipherKey::new(&AES_256, &key).unwrap()).unwrap();
+        let decrypting_key =
+            DecryptingKey::ctr(UnboundCipherKey::new(&AES_256, &key).unwrap()).unwrap();
+        for (start, end) in [
+            (0, 256),
+            (0, 1),
+            (1, 17),
+            (15, 16),
+            (16, 48),
+            (37, 200),
+            (255, 256),
+        ] {
+            let mut in_out = plaintext[start..end].to_vec();
+            let context = EncryptionContext::Iv128(FixedLength::from(iv));
+            encrypting_key
+                .less_safe_encrypt_at(&mut in_out, context, start as u64)
+                .unwrap();
+            assert_eq!(&ciphertext[start..end], in_out.as_slice());
+
+            let context = DecryptionContext::Iv128(FixedLength::from(iv));
+            let result = decrypting_key
+                .decrypt_at(&mut in_out, context, start as u64)
+                .unwrap();
+            assert_eq!(&plaintext[start..end], result);
+        }
+    }
+
+    // The counter block for a large offset wraps around past the all-ones IV.
+    let iv = [0xffu8; 16];
+    let decrypting_key =
+        DecryptingKey::ctr(UnboundCipherKey::new(&AES_256, &key).unwrap()).unwrap();
+    let mut wrapped = [0u8; 16];
+    let context = DecryptionContext::Iv128(FixedLength::from(iv));
+    decrypting_key
+        .decrypt_at(&mut wrapped, context, u64::MAX - 15)
+        .unwrap();
+    let mut expected = [0u8; 16];
+    let mut counter = [0u8; 16];
+    counter[8..].copy_from_slice(&(u64::MAX / 16 - 1).to_be_bytes());
+    let context = DecryptionContext::Iv128(FixedLength::from(counter));
+    decrypting_key.decrypt(&mut expected, context).unwrap();
+    assert_eq!(expected, wrapped);
+}
+
+#[test]
+fn test_ctr_random_access_invalid() {
+    let key = from_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+    let iv = [0u8; 16];
+    let mut in_out = [0u8; 16];
+
+    let decrypting_key =
+        DecryptingKey::cfb128(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    let context = DecryptionContext::Iv128(FixedLength::from(iv));
+    assert!(decrypting_key.decrypt_at(&mut in_out, context, 0).is_err());
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&AES_128, &key).unwrap()).unwrap();
+    assert!(encrypting_key
+        .less_safe_encrypt_at(&mut in_out, EncryptionContext::xts_sector(0), 0)
+        .is_err());
+}
+
+const CHACHA20_PLAINTEXT: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+#[test]
+fn test_chacha20_kat() {
+    // RFC 8439, Section 2.4.2.
+    let key = from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+    let nonce: [u8; CHACHA20_NONCE_LEN] = from_hex("000000000000004a00000000")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let ciphertext = from_hex(
+        "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dab\
+         cd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806\
+         818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d",
+    )
+    .unwrap();
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&CHACHA20, &key).unwrap()).unwrap();
+    let mut in_out = CHACHA20_PLAINTEXT.to_vec();
+    let context = EncryptionContext::Nonce96Counter32(FixedLength::from(nonce), 1);
+    let context = encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .unwrap();
+    assert_eq!(ciphertext, in_out);
+
+    let decrypting_key =
+        DecryptingKey::ctr(UnboundCipherKey::new(&CHACHA20, &key).unwrap()).unwrap();
+    let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+    assert_eq!(CHACHA20_PLAINTEXT, plaintext);
+
+    for offset in [1, 63, 64, 100] {
+        let mut in_out = ciphertext[offset..].to_vec();
+        let context = DecryptionContext::Nonce96Counter32(FixedLength::from(nonce), 1);
+        let plaintext = decrypting_key
+            .decrypt_at(&mut in_out, context, offset as u64)
+            .unwrap();
+        assert_eq!(&CHACHA20_PLAINTEXT[offset..], plaintext);
+    }
+}
+
+#[test]
+fn test_xchacha20_kat() {
+    let key = from_hex("808182838485868788898a8b8c8d8e8f909192939495969798999a9b9c9d9e9f").unwrap();
+    let nonce: [u8; XCHACHA20_NONCE_LEN] =
+        from_hex("404142434445464748494a4b4c4d4e4f5051525354555657")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    let ciphertext = from_hex(
+        "37787be99612d0f8672b4f0cead7099422a10d1d889dd7b0a91be551e09566a6d2eb485e7b270ba647fc5b16\
+         799fa8463ed44c83437c348fd54a350b862535359f600ad4349e917a8f7b07f390c1ef75462f174e6331e899\
+         b8dfd92c312063bb634e7518454de81244bf85690cf67e33b53f",
+    )
+    .unwrap();
+
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&XCHACHA20, &key).unwrap()).unwrap();
+    let mut in_out = CHACHA20_PLAINTEXT.to_vec();
+    let context = EncryptionContext::Nonce192Counter32(FixedLength::from(nonce), 0);
+    let context = encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .unwrap();
+    assert_eq!(ciphertext, in_out);
+
+    let decrypting_key =
+        DecryptingKey::ctr(UnboundCipherKey::new(&XCHACHA20, &key).unwrap()).unwrap();
+    let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+    assert_eq!(CHACHA20_PLAINTEXT, plaintext);
+
+    let mut in_out = ciphertext[70..].to_vec();
+    let context = DecryptionContext::Nonce192Counter32(FixedLength::from(nonce), 0);
+    let plaintext = decrypting_key.decrypt_at(&mut in_out, context, 70).unwrap();
+    assert_eq!(&CHACHA20_PLAINTEXT[70..], plaintext);
+}
+
+#[test]
+fn test_chacha20_round_trip() {
+    let key = from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap();
+    for algorithm in [&CHACHA20, &XCHACHA20] {
+        let encrypting_key =
+            EncryptingKey::ctr(UnboundCipherKey::new(algorithm, &key).unwrap()).unwrap();
+        let mut in_out = CHACHA20_PLAINTEXT.to_vec();
+        let context = encrypting_key.encrypt(&mut in_out).unwrap();
+        assert_ne!(CHACHA20_PLAINTEXT, in_out.as_slice());
+        match &context {
+            DecryptionContext::Nonce96Counter32(_, 0) => assert_eq!(&CHACHA20, algorithm),
+            DecryptionContext::Nonce192Counter32(_, 0) => assert_eq!(&XCHACHA20, algorithm),
+            _ => panic!("unexpected context {context:?} for {algorithm:?}"),
+        }
+
+        let decrypting_key =
+            DecryptingKey::ctr(UnboundCipherKey::new(algorithm, &key).unwrap()).unwrap();
+        let plaintext = decrypting_key.decrypt(&mut in_out, context).unwrap();
+        assert_eq!(CHACHA20_PLAINTEXT, plaintext);
+    }
+}
+
+#[test]
+fn test_chacha20_invalid() {
+    let key = [0u8; 32];
+    let nonce = [0u8; CHACHA20_NONCE_LEN];
+    let encrypting_key =
+        EncryptingKey::ctr(UnboundCipherKey::new(&CHACHA20, &key).unwrap()).unwrap();
+    let mut in_out = [0u8; 128];
+
+    // Contexts for other algorithms are rejected.
+    let context = EncryptionContext::Iv128(FixedLength::from([0u8; 16]));
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .is_err());
+    let context = EncryptionContext::Nonce192Counter32(FixedLength::from([0u8; 24]), 0);
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .is_err());
+
+    // The 32-bit block counter must not wrap around.
+    let context = EncryptionContext::Nonce96Counter32(FixedLength::from(nonce), u32::MAX);
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out, context)
+        .is_err());
+    let context = EncryptionContext::Nonce96Counter32(FixedLength::from(nonce), u32::MAX);
+    assert!(encrypting_key
+        .less_safe_encrypt(&mut in_out[..64], context)
+        .is_ok());
+
+    // ChaCha20 is only available as a stream cipher in CTR mode, with the correct key length.
+    assert!(
+        PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&CHACHA20, &key).unwrap())
+            .and_then(|key| key.encrypt(&mut vec![0u8; 16]))
+            .is_err()
+    );
+    assert!(EncryptingKey::ctr(UnboundCipherKey::new(&CHACHA20, &key[..16]).unwrap()).is_err());
+}
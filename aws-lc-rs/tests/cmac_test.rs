@@ -0,0 +1,140 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+use aws_lc_rs::cmac::sign;
+use aws_lc_rs::{cmac, test, test_file};
+
+#[test]
+fn cmac_tests() {
+    test::run(test_file!("data/cmac_tests.txt"), |section, test_case| {
+        assert_eq!(section, "");
+        let algorithm = match test_case.consume_string("CMAC").as_str() {
+            "AES128" => cmac::CMAC_AES_128,
+            "AES192" => cmac::CMAC_AES_192,
+            "AES256" => cmac::CMAC_AES_256,
+            _ => unreachable!(),
+        };
+        let key_value = test_case.consume_bytes("Key");
+        let mut input = test_case.consume_bytes("Input");
+        let output = test_case.consume_bytes("Output");
+
+        cmac_test_case_inner(algorithm, &key_value[..], &input[..], &output[..], true);
+
+        // Tamper with the input and check that verification fails.
+        if input.is_empty() {
+            input.push(0);
+        } else {
+            input[0] ^= 1;
+        }
+
+        cmac_test_case_inner(algorithm, &key_value[..], &input[..], &output[..], false);
+
+        Ok(())
+    });
+}
+
+fn cmac_test_case_inner(
+    algorithm: cmac::Algorithm,
+    key_value: &[u8],
+    input: &[u8],
+    output: &[u8],
+    is_ok: bool,
+) {
+    let key = cmac::Key::new(algorithm, key_value).unwrap();
+
+    // One-shot API.
+    {
+        let signature = sign(&key, input);
+        assert_eq!(is_ok, signature.as_ref() == output);
+        assert_eq!(is_ok, cmac::verify(&key, input, output).is_ok());
+    }
+
+    // Multi-part API, one single part.
+    {
+        let mut s_ctx = cmac::Context::with_key(&key);
+        s_ctx.update(input);
+        let signature = s_ctx.sign();
+        assert_eq!(is_ok, signature.as_ref() == output);
+    }
+
+    // Multi-part API, byte by byte.
+    {
+        let mut ctx = cmac::Context::with_key(&key);
+        for b in input {
+            ctx.update(&[*b]);
+        }
+        let signature = ctx.sign();
+        assert_eq!(is_ok, signature.as_ref() == output);
+    }
+
+    // Multi-part API, cloned mid-stream.
+    {
+        let (first, second) = input.split_at(input.len() / 2);
+        let mut ctx = cmac::Context::with_key(&key);
+        ctx.update(first);
+        let mut ctx_clone = ctx.clone();
+        ctx.update(second);
+        ctx_clone.update(second);
+        assert_eq!(ctx.sign().as_ref(), ctx_clone.sign().as_ref());
+    }
+}
+
+#[test]
+fn cmac_invalid_key_length() {
+    for algorithm in [cmac::CMAC_AES_128, cmac::CMAC_AES_192, cmac::CMAC_AES_256] {
+        for len in [0, 15, 17, 23, 25, 31, 33] {
+            assert!(cmac::Key::new(algorithm, &vec![0u8; len]).is_err());
+        }
+    }
+}
+
+#[test]
+fn cmac_debug() {
+    let key = cmac::Key::new(cmac::CMAC_AES_128, &[0; 16]).unwrap();
+    assert_eq!(
+        "Key { algorithm: Algorithm { id: Aes128, key_len: 16 } }",
+        format!("{:?}", &key)
+    );
+
+    let ctx = cmac::Context::with_key(&key);
+    assert_eq!(
+        "Context { algorithm: Algorithm { id: Aes128, key_len: 16 } }",
+        format!("{:?}", &ctx)
+    );
+}
+
+#[test]
+fn cmac_traits() {
+    test::compile_time_assert_send::<cmac::Key>();
+    test::compile_time_assert_sync::<cmac::Key>();
+    test::compile_time_assert_send::<cmac::Context>();
+}
+
+#[test]
+fn cmac_thread_safeness() {
+    use std::thread;
+    lazy_static::lazy_static! {
+        static ref SECRET_KEY: cmac::Key =
+            cmac::Key::new(cmac::CMAC_AES_256, &[7u8; 32]).unwrap();
+        static ref MSG: Vec<u8> = vec![1u8; 256];
+    }
+
+    let signature = sign(&SECRET_KEY, &MSG);
+
+    let mut join_handles = Vec::new();
+    for _ in 1..100 {
+        let join_handle = thread::spawn(|| {
+            let signature = sign(&SECRET_KEY, &MSG);
+            for _ in 1..100 {
+                let my_signature = sign(&SECRET_KEY, &MSG);
+                assert_eq!(signature.as_ref(), my_signature.as_ref());
+            }
+            signature
+        });
+        join_handles.push(join_handle);
+    }
+    for handle in join_handles {
+        let thread_signature = handle.join().unwrap();
+        assert_eq!(thread_signature.as_ref(), signature.as_ref());
+    }
+}
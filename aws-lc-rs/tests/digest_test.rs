@@ -27,6 +27,15 @@ fn digest_misc() {
         let actual_from_one_shot = digest::digest(digest_alg, &data);
         assert_eq!(&expected, &actual_from_one_shot.as_ref());
 
+        let mut reader = digest::DigestReader::new(digest_alg, data.as_slice());
+        let mut writer = digest::DigestWriter::new(digest_alg, Vec::new());
+        std::io::copy(&mut reader, &mut writer).unwrap();
+        let (_, actual_from_reader) = reader.finish();
+        assert_eq!(&expected, &actual_from_reader.as_ref());
+        let (written, actual_from_writer) = writer.finish();
+        assert_eq!(data, written);
+        assert_eq!(&expected, &actual_from_writer.as_ref());
+
         Ok(())
     });
 }
@@ -433,3 +442,67 @@ fn digest_test_fmt() {
         &format!("{:?}", digest::digest(&digest::SHA512_256, b"hello, world"))
     );
 }
+
+#[cfg(not(feature = "fips"))]
+#[test]
+fn xof_test() {
+    test::run(test_file!("data/shake_tests.txt"), |section, test_case| {
+        let msg = test_case.consume_bytes("Msg");
+        let expected = test_case.consume_bytes("Output");
+        let alg = match section {
+            "SHAKE128" => &digest::SHAKE128,
+            "SHAKE256" => &digest::SHAKE256,
+            _ => unreachable!(),
+        };
+
+        // One-shot.
+        let mut actual = vec![0u8; expected.len()];
+        digest::xof(alg, &msg, &mut actual);
+        assert_eq!(expected, actual);
+
+        // Incremental absorb, and squeezing in pieces of varying sizes.
+        let mut ctx = digest::XofContext::new(alg);
+        let (first, second) = msg.split_at(msg.len() / 2);
+        ctx.update(first);
+        let cloned = ctx.clone();
+        ctx.update(second);
+        let mut reader = ctx.squeeze();
+        let mut actual = Vec::new();
+        let mut piece_len = 1;
+        while actual.len() < expected.len() {
+            let mut piece = vec![0u8; piece_len.min(expected.len() - actual.len())];
+            reader.squeeze(&mut piece);
+            actual.extend_from_slice(&piece);
+            piece_len = piece_len * 3 + 1;
+        }
+        assert_eq!(expected, actual);
+
+        // A clone absorbs independently of the original.
+        let mut cloned = cloned;
+        cloned.update(second);
+        let mut actual = vec![0u8; expected.len()];
+        cloned.squeeze().squeeze(&mut actual);
+        assert_eq!(expected, actual);
+
+        Ok(())
+    });
+}
+
+#[cfg(not(feature = "fips"))]
+#[test]
+fn xof_reader_output_limit() {
+    let mut expected = vec![0u8; digest::MAX_XOF_READER_OUTPUT_LEN + 1];
+    digest::xof(&digest::SHAKE256, b"limit", &mut expected);
+
+    let mut ctx = digest::XofContext::new(&digest::SHAKE256);
+    ctx.update(b"limit");
+    let mut reader = ctx.squeeze();
+    let mut actual = vec![0u8; digest::MAX_XOF_READER_OUTPUT_LEN];
+    let (first, second) = actual.split_at_mut(1000);
+    reader.squeeze(first);
+    reader.squeeze(second);
+    assert_eq!(expected[..digest::MAX_XOF_READER_OUTPUT_LEN], actual);
+
+    let result = std::panic::catch_unwind(move || reader.squeeze(&mut [0u8; 1]));
+    assert!(result.is_err());
+}
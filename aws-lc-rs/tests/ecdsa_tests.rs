@@ -63,6 +63,16 @@ fn ecdsa_from_pkcs8_test() {
                         &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
                     ),
                 ),
+                "P-224" => (
+                    (
+                        &signature::ECDSA_P224_SHA224_FIXED_SIGNING,
+                        &signature::ECDSA_P224_SHA224_ASN1_SIGNING,
+                    ),
+                    (
+                        &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
+                        &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
+                    ),
+                ),
                 _ => unreachable!(),
             };
 
@@ -121,6 +131,8 @@ fn ecdsa_generate_pkcs8_test() {
     let rng = SystemRandom::new();
 
     for alg in &[
+        &signature::ECDSA_P224_SHA224_ASN1_SIGNING,
+        &signature::ECDSA_P224_SHA224_FIXED_SIGNING,
         &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
         &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
         &signature::ECDSA_P384_SHA384_ASN1_SIGNING,
@@ -170,6 +182,7 @@ fn test_signature_ecdsa_verify_asn1(data_file: test::File) {
         let is_valid = test_case.consume_string("Result") == "P (0 )";
 
         let alg = match (curve_name.as_str(), digest_name.as_str()) {
+            ("P-224", "SHA224") => &signature::ECDSA_P224_SHA224_ASN1,
             ("P-256", "SHA256") => &signature::ECDSA_P256_SHA256_ASN1,
             ("P-256", "SHA384") => &signature::ECDSA_P256_SHA384_ASN1,
             ("P-384", "SHA256") => &signature::ECDSA_P384_SHA256_ASN1,
@@ -218,6 +231,7 @@ fn test_signature_ecdsa_verify_fixed(data_file: test::File) {
         let expected_result = test_case.consume_string("Result");
 
         let alg = match (curve_name.as_str(), digest_name.as_str()) {
+            ("P-224", "SHA224") => &signature::ECDSA_P224_SHA224_FIXED,
             ("P-256", "SHA256") => &signature::ECDSA_P256_SHA256_FIXED,
             ("P-384", "SHA384") => &signature::ECDSA_P384_SHA384_FIXED,
             ("P-384", "SHA3-384") => &signature::ECDSA_P384_SHA3_384_FIXED,
@@ -305,6 +319,10 @@ fn test_signature_ecdsa_sign_fixed_sign_and_verify(data_file: test::File) {
         let _expected_result = test_case.consume_bytes("Sig");
 
         let (signing_alg, verification_alg) = match (curve_name.as_str(), digest_name.as_str()) {
+            ("P-224", "SHA224") => (
+                &signature::ECDSA_P224_SHA224_FIXED_SIGNING,
+                &signature::ECDSA_P224_SHA224_FIXED,
+            ),
             ("P-256", "SHA256") => (
                 &signature::ECDSA_P256_SHA256_FIXED_SIGNING,
                 &signature::ECDSA_P256_SHA256_FIXED,
@@ -395,6 +413,10 @@ fn test_signature_ecdsa_sign_asn1(data_file: test::File) {
         let _expected_result = test_case.consume_bytes("Sig");
 
         let (signing_alg, verification_alg) = match (curve_name.as_str(), digest_name.as_str()) {
+            ("P-224", "SHA224") => (
+                &signature::ECDSA_P224_SHA224_ASN1_SIGNING,
+                &signature::ECDSA_P224_SHA224_ASN1,
+            ),
             ("P-256", "SHA256") => (
                 &signature::ECDSA_P256_SHA256_ASN1_SIGNING,
                 &signature::ECDSA_P256_SHA256_ASN1,
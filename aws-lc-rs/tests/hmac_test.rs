@@ -5,6 +5,7 @@
 
 use aws_lc_rs::hmac::sign;
 use aws_lc_rs::{digest, hmac, test, test_file};
+use std::io::{Read, Write};
 
 #[test]
 fn hmac_tests() {
@@ -82,6 +83,24 @@ fn hmac_test_case_inner(
         let signature = ctx.sign();
         assert_eq!(is_ok, signature.as_ref() == output);
     }
+
+    // I/O adapters.
+    {
+        let mut writer = hmac::HmacWriter::new(&key, Vec::new());
+        for chunk in input.chunks(3) {
+            writer.write_all(chunk).unwrap();
+        }
+        let (written, signature) = writer.sign();
+        assert_eq!(input, written.as_slice());
+        assert_eq!(is_ok, signature.as_ref() == output);
+
+        let mut reader = hmac::HmacReader::new(&key, input);
+        let mut read = Vec::new();
+        reader.read_to_end(&mut read).unwrap();
+        let (_, signature) = reader.sign();
+        assert_eq!(input, read.as_slice());
+        assert_eq!(is_ok, signature.as_ref() == output);
+    }
 }
 
 #[test]
@@ -92,6 +111,12 @@ fn hmac_debug() {
     let ctx = hmac::Context::with_key(&key);
     assert_eq!("Context { algorithm: SHA256 }", format!("{:?}", &ctx));
 
+    let writer = hmac::HmacWriter::new(&key, Vec::new());
+    assert_eq!(
+        "HmacWriter { context: Context { algorithm: SHA256 }, .. }",
+        format!("{:?}", &writer)
+    );
+
     assert_eq!("Algorithm(SHA256)", format!("{:?}", hmac::HMAC_SHA256));
 }
 
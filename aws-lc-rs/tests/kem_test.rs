@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0 OR ISC
+
+#![cfg(not(feature = "fips"))]
+
+use aws_lc_rs::kem::{
+    Algorithm, Ciphertext, DecapsulationKey, EncapsulationKey, ML_KEM_1024, ML_KEM_512, ML_KEM_768,
+};
+use aws_lc_rs::{test, test_file};
+
+#[test]
+fn mlkem_decapsulation_tests() {
+    test::run(
+        test_file!("data/mlkem_decapsulation_tests.txt"),
+        |section, test_case| {
+            let algorithm: &'static Algorithm = match section {
+                "ML_KEM_512" => &ML_KEM_512,
+                "ML_KEM_768" => &ML_KEM_768,
+                "ML_KEM_1024" => &ML_KEM_1024,
+                _ => unreachable!(),
+            };
+            let decapsulation_key_bytes = test_case.consume_bytes("DecapsulationKey");
+            let encapsulation_key_bytes = test_case.consume_bytes("EncapsulationKey");
+            let ciphertext = test_case.consume_bytes("Ciphertext");
+            let shared_secret = test_case.consume_bytes("SharedSecret");
+            let result = test_case.consume_string("Result");
+            assert!(result == "success" || result == "implicit-rejection");
+
+            let decapsulation_key =
+                DecapsulationKey::new(algorithm, &decapsulation_key_bytes).unwrap();
+            assert_eq!(
+                decapsulation_key.key_bytes().unwrap().as_ref(),
+                decapsulation_key_bytes.as_slice()
+            );
+
+            // The encapsulation key embedded in the decapsulation key is recovered.
+            let encapsulation_key = decapsulation_key.encapsulation_key().unwrap();
+            assert_eq!(
+                encapsulation_key.key_bytes().unwrap().as_ref(),
+                encapsulation_key_bytes.as_slice()
+            );
+
+            let secret = decapsulation_key
+                .decapsulate(Ciphertext::from(ciphertext.as_slice()))
+                .unwrap();
+            assert_eq!(secret.as_ref(), shared_secret.as_slice());
+
+            // Encapsulating to the imported key is decapsulated by the imported key.
+            let encapsulation_key =
+                EncapsulationKey::new(algorithm, &encapsulation_key_bytes).unwrap();
+            let (ciphertext, bob_secret) = encapsulation_key.encapsulate().unwrap();
+            let alice_secret = decapsulation_key.decapsulate(ciphertext).unwrap();
+            assert_eq!(alice_secret.as_ref(), bob_secret.as_ref());
+
+            Ok(())
+        },
+    );
+}